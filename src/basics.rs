@@ -0,0 +1,53 @@
+use std::io::{self, BufRead, Write};
+
+// Class 1 - Hello World
+pub fn hello_world(out: &mut dyn Write) -> io::Result<()> {
+    writeln!(out, "Hello, world!")?;
+    Ok(())
+}
+
+// Class 3 - IO
+pub fn io_example(input: &mut dyn BufRead, out: &mut dyn Write) -> io::Result<()> {
+    writeln!(out, "What is your name?")?;
+    let mut name: String = String::new();
+    let greeting: &str = "Nice to meet you";
+    // Here we are passing a &mut, because we are borrowing a mutable reference from name variable
+    // the ownership is still with name variable, but the io library borrows the mutable value, for appending the result
+    // into the string. When running from the terminal, input is io::stdin(), the same as io::stdin().read_line(&mut name)
+    input.read_line(&mut name).expect("Didn't receive input");
+
+    writeln!(out, "Hello {}! {}", name.trim_end(), greeting)?;
+    Ok(())
+}
+
+// Class 4 - Constants and Shadowing
+pub fn constants_shadow(out: &mut dyn Write) -> io::Result<()> {
+    const ONE_MIL: u32 = 1_000_000;
+    const PI: f32 = std::f32::consts::PI;
+    let age: &str = "47";
+    // In Rust, you can define variables that have the same name, but as long they are different types
+    let mut age: u32 = age.trim().parse().expect("Age wasn't assigned a number");
+    age = age + 1;
+    writeln!(out, "I'm {} and I want ${}", age, ONE_MIL)?;
+    Ok(())
+}
+
+// Class 5 - Number Data types
+
+pub fn data_types(out: &mut dyn Write) -> io::Result<()> {
+    // Unsigned int: u8, u16, u32, u64, u128, usize
+    // Signed int: i8, i16, i32, i64, i128, isize
+    writeln!(out, "Max u32: {}", u32::MAX)?;
+    writeln!(out, "Max u64: {}", u64::MAX)?;
+    writeln!(out, "Max usize: {}", usize::MAX)?;
+    writeln!(out, "Max u128: {}", u128::MAX)?;
+    writeln!(out, "Max f64: {}", f64::MAX)?;
+    Ok(())
+}
+
+// Class 12 - Casting
+pub fn casting(out: &mut dyn Write) -> io::Result<()> {
+    let int_u8: u8 = 5;
+    let int2_u8: u8 = 4;
+    Ok(())
+}
@@ -0,0 +1,334 @@
+// Lesson book export
+// Assembles a book for offline reading: the README is the introduction, followed by one chapter per class,
+// with the code and comments of the class and the captured output of running it.
+// Every chapter is a file in the output directory, with an index.md/index.html table of contents,
+// and links to the previous and next chapters.
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use crate::lessons::{Lesson, LESSONS};
+
+const README: &str = include_str!("../README.md");
+
+// io_example asks for a name, the book shows it answered with this one
+const SAMPLE_INPUT: &[u8] = b"Felipe\n";
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Format {
+    Html,
+    Markdown,
+}
+
+impl Format {
+    fn extension(&self) -> &'static str {
+        match self {
+            Format::Html => "html",
+            Format::Markdown => "md",
+        }
+    }
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(format: &str) -> Result<Self, Self::Err> {
+        match format.to_lowercase().as_str() {
+            "html" => Ok(Format::Html),
+            "markdown" | "md" => Ok(Format::Markdown),
+            _ => Err(format!("unknown format `{}`, use html or markdown", format)),
+        }
+    }
+}
+
+struct Chapter {
+    file: String,
+    title: String,
+    content: Content,
+}
+
+enum Content {
+    // Markdown text, the README
+    Text(String),
+    Lesson(&'static Lesson),
+}
+
+// "Class 26 - Smart Pointers" becomes "class-26-smart-pointers"
+fn slug(text: &str) -> String {
+    let mut slug = String::new();
+    for c in text.chars() {
+        if c.is_ascii_alphanumeric() {
+            slug.push(c.to_ascii_lowercase());
+        } else if !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+    slug.trim_matches('-').to_string()
+}
+
+// The README explains how to run the project after the concepts, the book only needs the concepts
+fn introduction() -> String {
+    let end = README.find("\n## Running the lessons").unwrap_or(README.len());
+    README[..end].trim().to_string()
+}
+
+fn chapters(format: Format) -> Vec<Chapter> {
+    let mut chapters = vec![Chapter {
+        file: format!("00-introduction.{}", format.extension()),
+        title: String::from("Introduction"),
+        content: Content::Text(introduction()),
+    }];
+    for (index, lesson) in LESSONS.iter().enumerate() {
+        chapters.push(Chapter {
+            file: format!("{:02}-{}.{}", index + 1, slug(&lesson.heading()), format.extension()),
+            title: lesson.heading(),
+            content: Content::Lesson(lesson),
+        });
+    }
+    chapters
+}
+
+// Runs every example of the lesson, capturing what it writes
+fn lesson_output(lesson: &Lesson) -> io::Result<Vec<(&'static str, String)>> {
+    let mut outputs = Vec::new();
+    for example in lesson.examples {
+        let mut output: Vec<u8> = Vec::new();
+        (example.run)(&mut &SAMPLE_INPUT[..], &mut output)?;
+        outputs.push((example.name, String::from_utf8_lossy(&output).into_owned()));
+    }
+    Ok(outputs)
+}
+
+fn navigation(chapters: &[Chapter], index: usize, format: Format) -> String {
+    let mut links = Vec::new();
+    if index > 0 {
+        let previous = &chapters[index - 1];
+        links.push(link(format, &previous.file, &format!("Previous: {}", previous.title)));
+    }
+    links.push(link(format, &format!("index.{}", format.extension()), "Contents"));
+    if let Some(next) = chapters.get(index + 1) {
+        links.push(link(format, &next.file, &format!("Next: {}", next.title)));
+    }
+    links.join(" | ")
+}
+
+fn link(format: Format, href: &str, text: &str) -> String {
+    match format {
+        Format::Markdown => format!("[{}]({})", text, href),
+        Format::Html => format!("<a href=\"{}\">{}</a>", escape_html(href), escape_html(text)),
+    }
+}
+
+fn markdown_chapter(chapter: &Chapter, navigation: &str) -> io::Result<String> {
+    let mut page = format!("{}\n\n", navigation);
+    match chapter.content {
+        Content::Text(ref text) => page.push_str(text),
+        Content::Lesson(lesson) => {
+            page.push_str(&format!("# {}\n\n", chapter.title));
+            page.push_str(&format!("```rust\n{}\n```\n\n## Output\n", lesson.source_code().unwrap_or_default()));
+            for (name, output) in lesson_output(lesson)? {
+                page.push_str(&format!("\n### {}\n\n```text\n{}\n```\n", name, output.trim_end()));
+            }
+        }
+    }
+    page.push_str(&format!("\n\n{}\n", navigation));
+    Ok(page)
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+// Inline markdown of the README: `code`, **bold**, *italic* and [links](url)
+fn inline_html(text: &str) -> String {
+    let mut html = String::new();
+    let mut rest = text;
+    while let Some(c) = rest.chars().next() {
+        let closing = |marker: &str| rest[marker.len()..].find(marker).map(|end| end + marker.len());
+        if c == '`' {
+            if let Some(end) = closing("`") {
+                html.push_str(&format!("<code>{}</code>", escape_html(&rest[1..end])));
+                rest = &rest[end + 1..];
+                continue;
+            }
+        } else if rest.starts_with("**") {
+            if let Some(end) = closing("**") {
+                html.push_str(&format!("<strong>{}</strong>", inline_html(&rest[2..end])));
+                rest = &rest[end + 2..];
+                continue;
+            }
+        } else if c == '*' {
+            if let Some(end) = closing("*") {
+                html.push_str(&format!("<em>{}</em>", inline_html(&rest[1..end])));
+                rest = &rest[end + 1..];
+                continue;
+            }
+        } else if c == '[' {
+            if let Some((label, url, length)) = markdown_link(rest) {
+                html.push_str(&format!("<a href=\"{}\">{}</a>", escape_html(url), inline_html(label)));
+                rest = &rest[length..];
+                continue;
+            }
+        }
+        html.push_str(&escape_html(&c.to_string()));
+        rest = &rest[c.len_utf8()..];
+    }
+    html
+}
+
+// [label](url) at the start of the text, returning the label, the url and the length of the whole link
+fn markdown_link(text: &str) -> Option<(&str, &str, usize)> {
+    let label_end = text.find("](")?;
+    let url_end = text[label_end..].find(')')? + label_end;
+    Some((&text[1..label_end], &text[label_end + 2..url_end], url_end + 1))
+}
+
+// Just enough markdown for the README: headings, paragraphs, lists and code blocks
+fn markdown_to_html(markdown: &str) -> String {
+    let mut html = String::new();
+    let mut paragraph: Vec<&str> = Vec::new();
+    let mut in_list = false;
+    let mut code_block: Option<Vec<&str>> = None;
+
+    let flush_paragraph = |html: &mut String, paragraph: &mut Vec<&str>| {
+        if !paragraph.is_empty() {
+            html.push_str(&format!("<p>{}</p>\n", inline_html(&paragraph.join(" "))));
+            paragraph.clear();
+        }
+    };
+
+    for line in markdown.lines() {
+        if let Some(code) = code_block.as_mut() {
+            if line.starts_with("```") {
+                html.push_str(&format!("<pre><code>{}</code></pre>\n", escape_html(&code.join("\n"))));
+                code_block = None;
+            } else {
+                code.push(line);
+            }
+            continue;
+        }
+
+        let trimmed = line.trim();
+        if let Some(item) = trimmed.strip_prefix("- ") {
+            flush_paragraph(&mut html, &mut paragraph);
+            if !in_list {
+                html.push_str("<ul>\n");
+                in_list = true;
+            }
+            html.push_str(&format!("<li>{}</li>\n", inline_html(item)));
+            continue;
+        }
+        if in_list {
+            html.push_str("</ul>\n");
+            in_list = false;
+        }
+
+        if line.starts_with("```") {
+            flush_paragraph(&mut html, &mut paragraph);
+            code_block = Some(Vec::new());
+        } else if trimmed.is_empty() {
+            flush_paragraph(&mut html, &mut paragraph);
+        } else if trimmed.starts_with('#') {
+            flush_paragraph(&mut html, &mut paragraph);
+            let level = trimmed.chars().take_while(|c| *c == '#').count().min(6);
+            html.push_str(&format!("<h{0}>{1}</h{0}>\n", level, inline_html(trimmed[level..].trim())));
+        } else {
+            paragraph.push(trimmed);
+        }
+    }
+    flush_paragraph(&mut html, &mut paragraph);
+    if in_list {
+        html.push_str("</ul>\n");
+    }
+    html
+}
+
+fn html_page(title: &str, body: &str) -> String {
+    format!(
+        "<!DOCTYPE html>
+<html>
+<head>
+<meta charset=\"utf-8\">
+<title>{}</title>
+<style>
+body {{ max-width: 60rem; margin: 2rem auto; padding: 0 1rem; font-family: sans-serif; line-height: 1.5; }}
+pre {{ background: #f4f4f4; padding: 1rem; overflow-x: auto; }}
+nav {{ margin: 1rem 0; }}
+</style>
+</head>
+<body>
+{}
+</body>
+</html>
+",
+        escape_html(title),
+        body
+    )
+}
+
+fn html_chapter(chapter: &Chapter, navigation: &str) -> io::Result<String> {
+    let mut body = format!("<nav>{}</nav>\n", navigation);
+    match chapter.content {
+        Content::Text(ref text) => body.push_str(&markdown_to_html(text)),
+        Content::Lesson(lesson) => {
+            body.push_str(&format!("<h1>{}</h1>\n", escape_html(&chapter.title)));
+            body.push_str(&format!(
+                "<pre><code class=\"language-rust\">{}</code></pre>\n<h2>Output</h2>\n",
+                escape_html(lesson.source_code().unwrap_or_default())
+            ));
+            for (name, output) in lesson_output(lesson)? {
+                body.push_str(&format!(
+                    "<h3>{}</h3>\n<pre><code>{}</code></pre>\n",
+                    escape_html(name),
+                    escape_html(output.trim_end())
+                ));
+            }
+        }
+    }
+    body.push_str(&format!("<nav>{}</nav>", navigation));
+    Ok(html_page(&chapter.title, &body))
+}
+
+fn index(chapters: &[Chapter], format: Format) -> String {
+    match format {
+        Format::Markdown => {
+            let mut page = String::from("# Rust Tutorial\n\n");
+            for chapter in chapters {
+                page.push_str(&format!("- {}\n", link(format, &chapter.file, &chapter.title)));
+            }
+            page
+        }
+        Format::Html => {
+            let mut body = String::from("<h1>Rust Tutorial</h1>\n<ul>\n");
+            for chapter in chapters {
+                body.push_str(&format!("<li>{}</li>\n", link(format, &chapter.file, &chapter.title)));
+            }
+            body.push_str("</ul>");
+            html_page("Rust Tutorial", &body)
+        }
+    }
+}
+
+// Writes the book to `dir`, returning the path of the index
+pub fn export(format: Format, dir: &Path) -> io::Result<PathBuf> {
+    fs::create_dir_all(dir)?;
+    let chapters = chapters(format);
+
+    for (position, chapter) in chapters.iter().enumerate() {
+        let navigation = navigation(&chapters, position, format);
+        let page = match format {
+            Format::Markdown => markdown_chapter(chapter, &navigation)?,
+            Format::Html => html_chapter(chapter, &navigation)?,
+        };
+        fs::write(dir.join(&chapter.file), page)?;
+    }
+
+    let index_path = dir.join(format!("index.{}", format.extension()));
+    fs::write(&index_path, index(&chapters, format))?;
+    Ok(index_path)
+}
@@ -0,0 +1,31 @@
+use std::io::{self, Write};
+
+// Class 25 - Closures
+
+pub fn closures(out: &mut dyn Write) -> io::Result<()> {
+    let can_vote: fn(i32) -> bool = |age: i32| {
+        age >= 18
+    };
+    writeln!(out, "Can vote: {}", can_vote(8))?;
+
+    let mut samp1 = 5;
+    let print_var = || format!("samp1= {}", samp1);
+    samp1 = 10;
+    let mut change_var = || samp1 += 1;
+    change_var();
+    writeln!(out, "samp1 = {}", samp1)?;
+    samp1 = 10;
+    writeln!(out, "samp1 = {}", samp1)?;
+    Ok(())
+}
+
+pub fn closures2(out: &mut dyn Write) -> io::Result<()> {
+    fn use_func<T>(a: i32, b: i32, func: T) -> i32 where T: Fn(i32, i32) -> i32 {
+        func(a, b)
+    }
+    let sum = |a: i32, b: i32| a + b;
+    let prod = |a: i32, b: i32| a * b;
+    writeln!(out, "5 + 4 = {}", use_func(5, 4, sum))?;
+    writeln!(out, "5 * 4 = {}", use_func(5, 4, prod))?;
+    Ok(())
+}
@@ -0,0 +1,125 @@
+use std::collections::HashMap;
+use std::io::{self, Write};
+
+// Class 8 - Arrays and Loops
+pub fn basic_arrays(out: &mut dyn Write) -> io::Result<()> {
+    // By definition arrays have fixed length, and in Rust, there is no way to add elements to that,
+    // their lengths are fixed at compile time, for enforcing the memory safety.
+    // Arrays are allocated in memory as sequential memory addresses, and in some languages, when you are appending to an array,
+    // you are basically copying the old array with the new value, to another allocated sequence of the memory.
+    // In rust, if you want to increase the elements, you should use Vectors.
+    // Vectors in Rust, works in the same way slices works in Golang
+    // When you define a Vector, rust compiler makes an extra room in memory allocation, doubling the size of the vector
+    // against the first defined size, so you have room for more elements. In the case the number of elements exceeds the
+    // allocated memory, Rust will relocate the entire Vector to another addresses, and will double the size again.
+    let arr_1: [i32; 9] = [1, 2, 3, 4, 5, 6, 7, 8, 9];
+    writeln!(out, "1st: {}", arr_1[0])?;
+    write!(out, "Length: {}", arr_1.len())?;
+
+    let mut loop_idx = 0;
+    loop {
+        if arr_1[loop_idx] % 2 == 0 {
+            loop_idx += 1;
+            continue;
+        }
+        if arr_1[loop_idx] == 9 {
+            break;
+        }
+        writeln!(out, "Val: {}", arr_1[loop_idx])?;
+        loop_idx += 1;
+    }
+
+    let mut loop_index = 0;
+    while loop_index < arr_1.len() {
+        writeln!(out, "Element: {}", arr_1[loop_index])?;
+        loop_index += 1;
+    }
+
+    for val in arr_1.iter() {
+        writeln!(out, "Val: {}", val)?;
+    }
+    Ok(())
+}
+
+// Class 9 - Mutable arrays
+pub fn mutable_arrays(out: &mut dyn Write) -> io::Result<()> {
+    // Let's replace all the elements that can be divided by 2, to 0
+    // Here we are defining a mutable array, because we want to change some elements
+    let mut arr_1: [i32; 9] = [1, 2, 3, 4, 5, 6, 7, 8, 9];
+
+    // For the loop, we will need to can the method iter_mut(), which returns every loop, a &mut(mutable borrow reference)
+    for val in arr_1.iter_mut() {
+        if (val.abs() % 2 == 0) {
+            // for changing its value, we will need to dereference it
+            // Remember that this is different from pointers in another language, in Rust we can only borrow a mutable reference to
+            // another single variable, in another languages we can create as many pointers we want, here we can only borrow one at a time,
+            // like a real object, we can only borrow a car to another person, if we haven't borrowed it to anyone.
+            *val = 0;
+        }
+    }
+    writeln!(out, "Array: {:?}", arr_1)?;
+    Ok(())
+}
+
+// Class 10 - Tuples
+pub fn basic_tuples(out: &mut dyn Write) -> io::Result<()> {
+    // Tuples are sequential data structures, that can hold different types of data, and it's immutable,
+    // off course you can turn a tuple to a mutable tuple, but like arrays, you can't remove/append new elements
+    let my_tuple: (u8, String, f64) = (47, "Derek".to_string(), 50_000.00);
+    writeln!(out, "Name: {}", my_tuple.1)?;
+
+    let (v1, v2, v3) = my_tuple;
+    writeln!(out, "Age: {}", my_tuple.0)?;
+    Ok(())
+}
+
+// Class 14 - Vectors
+// As we talked on Arrays class, vectors are defined on memory as sequential memory addresses,
+// and different from arrays, that are fixed in length, here we can assign or remove elements. What Rust does in the
+// background, which is done for many different programming languages, is assigning a sequential number of addresses in memory,
+// when the size is exceeded, it doubles the current size, and makes a copy of the array to a new sequence.
+pub fn vectors(out: &mut dyn Write) -> io::Result<()> {
+    let vec1: Vec<i32> = Vec::new();
+    let mut vec2: Vec<i32> = vec![1, 2, 3, 4];
+    vec2.push(5);
+    writeln!(out, "1st: {}", vec2[0])?;
+
+    match vec2.get(1) {
+        Some(second) => writeln!(out, "2nd: {}", second)?,
+        None => writeln!(out, "No second value")?
+    }
+
+    for i in &mut vec2 {
+        *i *= 2;
+    }
+    for i in &vec2 {
+        writeln!(out, "{}", i)?;
+    }
+    Ok(())
+}
+
+// Class 18 - HashMaps
+pub fn hash_maps(out: &mut dyn Write) -> io::Result<()> {
+    let mut heroes = HashMap::new();
+    heroes.insert("Superman", "Clark Kent");
+    heroes.insert("Batman", "Bruce Wayne");
+    heroes.insert("Flash", "Barry Allen");
+
+
+    // A HashMap doesn't keep any order, every execution can iterate it in a different order,
+    // so we sort the entries before printing them
+    let mut entries: Vec<(&&str, &&str)> = heroes.iter().collect();
+    entries.sort();
+    for (k, v) in entries {
+        writeln!(out, "{} = {}", k, v)?;
+    }
+
+    if heroes.contains_key("Batman") {
+        let the_batman: Option<&&str> = heroes.get(&"Batman");
+        match the_batman {
+            Some(x) => writeln!(out, "Batman is a hero")?,
+            None => writeln!(out, "Batman is not a hero")?
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,48 @@
+use std::cmp::Ordering;
+use std::io::{self, Write};
+
+// Class 6 - If clauses
+pub fn if_clauses(out: &mut dyn Write) -> io::Result<()> {
+
+    // Normal IF
+    let age: i32 = 5;
+    if (age >= 1) && (age <= 18) {
+        writeln!(out, "Important Birthday")?;
+    } else if (age == 21) || (age == 50) {
+        writeln!(out, "Important Birthday")?;
+    } else if age >= 65 {
+        writeln!(out, "Important Birthday")?;
+    } else {
+        writeln!(out, "Not an Important Birthday")?;
+    }
+
+    // Inline IF
+    let mut my_age: i32 = 47;
+    let can_vote: bool = if my_age > 18 { true } else { false };
+    write!(out, "Can vote: {}", can_vote)?;
+
+
+    // Using Match
+    let age2: i32 = 8;
+    match age2 {
+        1..=18 => writeln!(out, "Important Birthday")?,
+        21 | 50 => writeln!(out, "Important Birthday")?,
+        65..=i32::MAX => writeln!(out, "Important Birthday")?,
+        _ => writeln!(out, "Not an Important Birthday")?
+    }
+    Ok(())
+}
+
+// Class 7 - Comparison Operator
+pub fn comp_operator(out: &mut dyn Write) -> io::Result<()> {
+    let my_age: i32 = 18;
+    let voting_age: i32 = 18;
+
+    // Here we are sending the voting_age with borrow operator &, because we are just borrowing this value to the function
+    match my_age.cmp(&voting_age) {
+        Ordering::Less => writeln!(out, "Can't vote")?,
+        Ordering::Equal => writeln!(out, "You gained the right to vote")?,
+        Ordering::Greater => writeln!(out, "Can vote")?
+    }
+    Ok(())
+}
@@ -0,0 +1,35 @@
+use std::io::{self, Write};
+
+// Class 13 - Enums
+pub enum Days {
+    Monday,
+    Tuesday,
+    Wednesday,
+    Thursday,
+    Friday,
+    Saturday,
+    Sunday,
+}
+
+impl Days {
+    pub fn is_weekend(&self) -> bool {
+        match self {
+            Days::Saturday | Days::Sunday => true,
+            _ => false
+        }
+    }
+}
+
+pub fn enums(out: &mut dyn Write) -> io::Result<()> {
+    let today: Days = Days::Monday;
+    match today {
+        Days::Monday => writeln!(out, "Everybody hates Monday")?,
+        Days::Tuesday => writeln!(out, "Donut day")?,
+        Days::Wednesday => writeln!(out, "Hump day")?,
+        Days::Thursday => writeln!(out, "Pay day")?,
+        Days::Friday => writeln!(out, "Almost weekend")?,
+        Days::Saturday => writeln!(out, "Weekend")?,
+        Days::Sunday => writeln!(out, "Weekend")?,
+    }
+    Ok(())
+}
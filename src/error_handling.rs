@@ -0,0 +1,50 @@
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Write};
+use std::num::ParseIntError;
+
+// Class 22 - Error Handling
+pub fn error_handling(out: &mut dyn Write) -> io::Result<()> {
+    let path: &str = "lines.txt";
+    let output = File::create(path);
+    let mut output = match output {
+        Ok(file) => file,
+        Err(err) => panic!("Problem creating file: {:?}", err)
+    };
+
+    output.write("Just some\nRandom words".as_ref()).expect("Failed to write to file");
+    // write!(output, "Just some\nRandom words").expect("Failed to write to file");
+    //
+    let input = File::open(path).unwrap();
+    let buffered = BufReader::new(input);
+    for line in buffered.lines() {
+        writeln!(out, "{}", line.unwrap())?;
+    }
+    Ok(())
+}
+
+// Class 23 - Question Marker Operator
+// Result is a enum from std, that represents either success or a failure it returns an Ok(T)
+// or an Err(E), you can use that on the return of the function to handle errors and valid results
+// when you use the question marker ? you are basically evaluating the result, it will assign the T generic type
+// to the variable, or it will return an error immediately like an throw on Java language
+pub fn try_to_parse() -> Result<i32, ParseIntError> {
+    let x: i32 = "123".parse()?; // x = 123
+    let y: i32 = "24a".parse()?; // returns an Err() immediately
+    Ok(x + y)                    // Doesn't run.
+}
+
+pub fn question_marker(out: &mut dyn Write) -> io::Result<()> {
+    match try_to_parse() {
+        Ok(sum) => writeln!(out, "Sum: {}", sum)?,
+        Err(err) => writeln!(out, "Failed to parse: {}", err)?
+    }
+    Ok(())
+}
+
+// Class 24 - HTTP Request
+
+// fn http_request() -> Result<(), Box<dyn Err>> {
+//     let resp = reqwest::blocking::get("https://httpbin.org/ip")?.text()?;
+//     println!("{:#?}", resp);
+//     Ok(())
+// }
@@ -0,0 +1,18 @@
+// Class 15 - Functions
+// Implement the functions below without looking at Class 15 in src/functions.rs, replacing the todo!() calls.
+// When you are done, run `cargo run -- check 15` to see if they pass the tests.
+
+// Returns the sum of x and y. Remember, we don't need the return statement for the last expression.
+pub fn get_sum(x: i32, y: i32) -> i32 {
+    todo!("return the sum of x and y")
+}
+
+// Returns both values at once
+pub fn return_multiple(x: i32, y: i32) -> (i32, i32) {
+    todo!("return x and y together")
+}
+
+// Doubles every value of the vector, the caller must see the change
+pub fn change_vector(vec1: &mut Vec<i32>) {
+    todo!("multiply every value of vec1 by 2")
+}
@@ -0,0 +1,143 @@
+// Exercise track
+// Each file in this folder has stubbed versions of the functions of a class, for the learner to implement.
+// The tests live here, away from the stubs, and `rust_tutorial check <class>` runs them, reporting
+// pass/fail for each one, with a hint when it fails.
+
+use std::fmt::Debug;
+use std::io::{self, Write};
+use std::panic;
+
+// The stub of change_vector doesn't use the Vec yet, so clippy would ask for a slice
+#[allow(clippy::ptr_arg)]
+pub mod class15;
+pub mod class16;
+
+struct Check {
+    description: &'static str,
+    hint: &'static str,
+    run: fn() -> Result<(), String>,
+}
+
+pub struct Exercise {
+    pub class: u32,
+    file: &'static str,
+    checks: &'static [Check],
+}
+
+fn expect_eq<T: PartialEq + Debug>(expected: T, actual: T) -> Result<(), String> {
+    if expected == actual {
+        Ok(())
+    } else {
+        Err(format!("expected {:?}, got {:?}", expected, actual))
+    }
+}
+
+pub static EXERCISES: &[Exercise] = &[
+    Exercise {
+        class: 15,
+        file: "src/exercises/class15.rs",
+        checks: &[
+            Check {
+                description: "get_sum adds two numbers",
+                hint: "the last expression of the function is the return value, x + y without a ;",
+                run: || expect_eq(10, class15::get_sum(5, 5)),
+            },
+            Check {
+                description: "get_sum works with negative numbers",
+                hint: "i32 is signed, -3 + 3 is 0",
+                run: || expect_eq(0, class15::get_sum(-3, 3)),
+            },
+            Check {
+                description: "return_multiple returns both values in order",
+                hint: "a tuple is written as (x, y)",
+                run: || expect_eq((1, 2), class15::return_multiple(1, 2)),
+            },
+            Check {
+                description: "change_vector doubles every value",
+                hint: "iterate over the &mut Vec and dereference each value: *value *= 2",
+                run: || {
+                    let mut values = vec![1, 2, 3];
+                    class15::change_vector(&mut values);
+                    expect_eq(vec![2, 4, 6], values)
+                },
+            },
+            Check {
+                description: "change_vector keeps an empty vector empty",
+                hint: "don't push new values, only change the existing ones",
+                run: || {
+                    let mut values: Vec<i32> = Vec::new();
+                    class15::change_vector(&mut values);
+                    expect_eq(Vec::<i32>::new(), values)
+                },
+            },
+        ],
+    },
+    Exercise {
+        class: 16,
+        file: "src/exercises/class16.rs",
+        checks: &[
+            Check {
+                description: "get_sum_gen adds integers",
+                hint: "the Add trait bound lets you use the + operator with T",
+                run: || expect_eq(9, class16::get_sum_gen(5, 4)),
+            },
+            Check {
+                description: "get_sum_gen adds floats",
+                hint: "the same function must work for any T: Add<Output = T>",
+                run: || expect_eq(9.5, class16::get_sum_gen(5.25, 4.25)),
+            },
+            Check {
+                description: "get_sum_gen works with big unsigned numbers",
+                hint: "don't convert the values to another type, use T all the way",
+                run: || expect_eq(u64::MAX, class16::get_sum_gen(u64::MAX - 1, 1)),
+            },
+        ],
+    },
+];
+
+pub fn find(class: u32) -> Option<&'static Exercise> {
+    EXERCISES.iter().find(|exercise| exercise.class == class)
+}
+
+fn panic_message(payload: &(dyn std::any::Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        String::from("panicked")
+    }
+}
+
+impl Exercise {
+    // Runs every check and returns true when all of them pass
+    pub fn check(&self, out: &mut dyn Write) -> io::Result<bool> {
+        writeln!(out, "Checking Class {} ({})", self.class, self.file)?;
+
+        // The stubs panic with todo!(), we report that ourselves, so we silence the default panic message
+        let default_hook = panic::take_hook();
+        panic::set_hook(Box::new(|_| {}));
+
+        let mut passed = 0;
+        for check in self.checks {
+            match panic::catch_unwind(check.run) {
+                Ok(Ok(())) => {
+                    passed += 1;
+                    writeln!(out, "  PASS  {}", check.description)?;
+                }
+                Ok(Err(problem)) => {
+                    writeln!(out, "  FAIL  {}: {}", check.description, problem)?;
+                    writeln!(out, "        hint: {}", check.hint)?;
+                }
+                Err(payload) => {
+                    writeln!(out, "  FAIL  {}: {}", check.description, panic_message(payload.as_ref()))?;
+                    writeln!(out, "        hint: {}", check.hint)?;
+                }
+            }
+        }
+
+        panic::set_hook(default_hook);
+        writeln!(out, "{}/{} tests passed", passed, self.checks.len())?;
+        Ok(passed == self.checks.len())
+    }
+}
@@ -0,0 +1,29 @@
+use std::io::{self, Write};
+
+// Class 15 - Functions
+// In Rust, you don't need to specify the return statement, the last line of a function will be
+// automatically considered the return statement
+pub fn get_sum(x: i32, y: i32) -> i32 {
+    x + y
+}
+
+// Functions can return more than one value
+pub fn return_multiple(x: i32, y: i32) -> (i32, i32) {
+    (x, y)
+}
+
+// As everything is immutable in Rust, if we make a mutable vector, and want to change it on a function
+// we would need to send a mutable reference of this guy
+pub fn change_vector(vec1: &mut Vec<i32>) {
+    for value in vec1 {
+        *value *= 2;
+    }
+}
+
+pub fn functions(out: &mut dyn Write) -> io::Result<()> {
+    let mut vec1: Vec<i32> = vec![1, 2, 3];
+    change_vector(&mut vec1);
+    writeln!(out, "the sum is: {}", get_sum(5, 5))?;
+    writeln!(out, "the vector is: {:?}", vec1)?;
+    Ok(())
+}
@@ -0,0 +1,21 @@
+use std::io::{self, Write};
+use std::ops::Add;
+
+// Class 16 - Generics
+// For working with Generics, we can't do some operations like sum or subtraction, we will need to use
+// a Trait, for being able to execute this operation, in this case the std::ops::Add Trait.
+// A trait defines functionality a particular type has and can share with other types.
+// We can use traits to define shared behavior in an abstract way. We can use trait bounds to specify
+// that a generic type can be any type that has certain behavior.
+// Traits are like interfaces, where you define a method that can be implemented by different Types, or
+// by Generics
+pub fn get_sum_gen<T: Add<Output=T>>(x: T, y: T) -> T {
+    x + y
+}
+
+pub fn generics(out: &mut dyn Write) -> io::Result<()> {
+    // The same function works for any type that implements Add
+    writeln!(out, "5 + 4 = {}", get_sum_gen(5, 4))?;
+    writeln!(out, "5.2 + 4.6 = {}", get_sum_gen(5.2, 4.6))?;
+    Ok(())
+}
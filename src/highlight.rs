@@ -0,0 +1,118 @@
+// Terminal syntax highlighting for the lesson sources
+// It's not a real Rust parser, just enough to make comments, strings, keywords, numbers and macros
+// stand out, with ANSI escape codes, line by line.
+
+const RESET: &str = "\x1b[0m";
+const COMMENT: &str = "\x1b[32m";
+const STRING: &str = "\x1b[33m";
+const KEYWORD: &str = "\x1b[35m";
+const NUMBER: &str = "\x1b[36m";
+const MACRO: &str = "\x1b[34m";
+const TYPE: &str = "\x1b[96m";
+
+const KEYWORDS: &[&str] = &[
+    "as", "break", "const", "continue", "crate", "dyn", "else", "enum", "fn", "for", "if", "impl", "in", "let", "loop",
+    "match", "mod", "move", "mut", "pub", "ref", "return", "self", "Self", "static", "struct", "super", "trait",
+    "type", "use", "where", "while", "true", "false",
+];
+
+fn paint(out: &mut String, color: &str, text: &str) {
+    out.push_str(color);
+    out.push_str(text);
+    out.push_str(RESET);
+}
+
+// Length in chars of a char literal starting at `start` ('a', '\n'), or None when it's a lifetime ('a)
+fn char_literal_len(chars: &[char], start: usize) -> Option<usize> {
+    match &chars[start + 1..] {
+        ['\\', _, '\'', ..] => Some(4),
+        [_, '\'', ..] => Some(3),
+        _ => None,
+    }
+}
+
+pub fn highlight_line(line: &str) -> String {
+    let chars: Vec<char> = line.chars().collect();
+    let mut out = String::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+
+        if c == '/' && chars.get(i + 1) == Some(&'/') {
+            let rest: String = chars[i..].iter().collect();
+            paint(&mut out, COMMENT, &rest);
+            break;
+        }
+
+        if c == '"' {
+            let mut end = i + 1;
+            while end < chars.len() && chars[end] != '"' {
+                if chars[end] == '\\' {
+                    end += 1;
+                }
+                end += 1;
+            }
+            let end = (end + 1).min(chars.len());
+            let literal: String = chars[i..end].iter().collect();
+            paint(&mut out, STRING, &literal);
+            i = end;
+            continue;
+        }
+
+        if c == '\'' {
+            if let Some(len) = char_literal_len(&chars, i) {
+                let literal: String = chars[i..i + len].iter().collect();
+                paint(&mut out, STRING, &literal);
+                i += len;
+                continue;
+            }
+        }
+
+        if c.is_ascii_digit() {
+            let mut end = i;
+            while end < chars.len() && (chars[end].is_ascii_alphanumeric() || chars[end] == '_' || chars[end] == '.') {
+                end += 1;
+            }
+            let number: String = chars[i..end].iter().collect();
+            paint(&mut out, NUMBER, &number);
+            i = end;
+            continue;
+        }
+
+        if c.is_alphabetic() || c == '_' {
+            let mut end = i;
+            while end < chars.len() && (chars[end].is_alphanumeric() || chars[end] == '_') {
+                end += 1;
+            }
+            let word: String = chars[i..end].iter().collect();
+            if chars.get(end) == Some(&'!') {
+                paint(&mut out, MACRO, &format!("{}!", word));
+                i = end + 1;
+                continue;
+            }
+            if KEYWORDS.contains(&word.as_str()) {
+                paint(&mut out, KEYWORD, &word);
+            } else if word.starts_with(char::is_uppercase) {
+                paint(&mut out, TYPE, &word);
+            } else {
+                out.push_str(&word);
+            }
+            i = end;
+            continue;
+        }
+
+        out.push(c);
+        i += 1;
+    }
+
+    out
+}
+
+pub fn highlight(source: &str) -> String {
+    source
+        .lines()
+        .map(highlight_line)
+        .collect::<Vec<String>>()
+        .join("\n")
+}
@@ -0,0 +1,15 @@
+use std::io::{self, Write};
+
+// Class 24 - Iterators
+
+pub fn iterators(out: &mut dyn Write) -> io::Result<()> {
+    let mut arr_it = [1, 2, 3, 4];
+    // when you use .iter(), you are basically borrowing the values like using &
+    for val in arr_it.iter() {
+        writeln!(out, "{}", val)?;
+    }
+
+    let mut iter1 = arr_it.iter();
+    writeln!(out, "1st: {:?}", iter1.next())?;
+    Ok(())
+}
@@ -0,0 +1,315 @@
+// Lesson registry
+// Every class of the tutorial is listed here, so the runner can find a lesson by its class number ("18"),
+// by the name of one of its functions ("hash_maps") or by its title ("Class 26 - Smart Pointers"),
+// instead of someone editing main() and recompiling every time.
+
+use std::fmt;
+use std::io::{self, BufRead, Write};
+
+// Lessons write to the sink they receive instead of calling println!, so the output can be captured.
+// Only io_example reads the input, the others just ignore it.
+pub type LessonFn = fn(&mut dyn BufRead, &mut dyn Write) -> io::Result<()>;
+
+// A single runnable function inside a class
+pub struct Example {
+    pub name: &'static str,
+    pub run: LessonFn,
+}
+
+// A class, some of them have more than one example, like Class 27 with all the re-borrow functions
+pub struct Lesson {
+    pub number: u32,
+    pub title: &'static str,
+    // Source of the module where the class lives, which can have more than one class
+    pub source: &'static str,
+    pub examples: &'static [Example],
+}
+
+impl Lesson {
+    // The same heading used in the comments of the lesson modules, like "Class 26 - Smart Pointers"
+    pub fn heading(&self) -> String {
+        format!("Class {} - {}", self.number, self.title)
+    }
+
+    // The comments and code of this class only, from its "// Class N - Title" heading in the module
+    // until the heading of the next class
+    pub fn source_code(&self) -> Option<&'static str> {
+        let marker = format!("// {}\n", self.heading());
+        let start = self.source.find(&marker)?;
+        let after_heading = start + marker.len();
+        let end = self.source[after_heading..]
+            .find("\n// Class ")
+            .map_or(self.source.len(), |position| after_heading + position + 1);
+        Some(self.source[start..end].trim_end())
+    }
+
+    // An id that finds this class and no other: its number, or its heading when another class has
+    // the same number
+    pub fn selector(&self) -> String {
+        if LESSONS.iter().filter(|lesson| lesson.number == self.number).count() > 1 {
+            self.heading()
+        } else {
+            self.number.to_string()
+        }
+    }
+
+    fn matches_title(&self, id: &str) -> bool {
+        self.heading().eq_ignore_ascii_case(id) || self.title.eq_ignore_ascii_case(id)
+    }
+}
+
+#[derive(Debug)]
+pub enum LessonError {
+    Unknown(String),
+}
+
+impl fmt::Display for LessonError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LessonError::Unknown(id) => write!(
+                f,
+                "unknown lesson `{}`, run `rust_tutorial list` to see the available lessons",
+                id
+            ),
+        }
+    }
+}
+
+impl std::error::Error for LessonError {}
+
+macro_rules! examples {
+    ($($module:ident::$name:ident),+ $(,)?) => {
+        &[$(Example { name: stringify!($name), run: |_, out| crate::$module::$name(out) }),+]
+    };
+}
+
+// The source of every lesson module, embedded at build time, so `show` works without the repository
+mod sources {
+    pub const BASICS: &str = include_str!("basics.rs");
+    pub const STRINGS: &str = include_str!("strings.rs");
+    pub const CONTROL_FLOW: &str = include_str!("control_flow.rs");
+    pub const COLLECTIONS: &str = include_str!("collections.rs");
+    pub const ENUMS: &str = include_str!("enums.rs");
+    pub const FUNCTIONS: &str = include_str!("functions.rs");
+    pub const GENERICS: &str = include_str!("generics.rs");
+    pub const OWNERSHIP: &str = include_str!("ownership.rs");
+    pub const STRUCTS: &str = include_str!("structs.rs");
+    pub const TRAITS: &str = include_str!("traits.rs");
+    pub const MODULES: &str = include_str!("modules.rs");
+    pub const ERROR_HANDLING: &str = include_str!("error_handling.rs");
+    pub const ITERATORS: &str = include_str!("iterators.rs");
+    pub const CLOSURES: &str = include_str!("closures.rs");
+    pub const SMART_POINTERS: &str = include_str!("smart_pointers.rs");
+}
+
+pub static LESSONS: &[Lesson] = &[
+    Lesson {
+        number: 1,
+        title: "Hello World",
+        source: sources::BASICS,
+        examples: examples![basics::hello_world],
+    },
+    Lesson {
+        number: 2,
+        title: "Strings",
+        source: sources::STRINGS,
+        examples: examples![strings::string_example],
+    },
+    Lesson {
+        number: 3,
+        title: "IO",
+        source: sources::BASICS,
+        examples: &[Example { name: "io_example", run: crate::basics::io_example }],
+    },
+    Lesson {
+        number: 4,
+        title: "Constants and Shadowing",
+        source: sources::BASICS,
+        examples: examples![basics::constants_shadow],
+    },
+    Lesson {
+        number: 5,
+        title: "Number Data types",
+        source: sources::BASICS,
+        examples: examples![basics::data_types],
+    },
+    Lesson {
+        number: 6,
+        title: "If clauses",
+        source: sources::CONTROL_FLOW,
+        examples: examples![control_flow::if_clauses],
+    },
+    Lesson {
+        number: 7,
+        title: "Comparison Operator",
+        source: sources::CONTROL_FLOW,
+        examples: examples![control_flow::comp_operator],
+    },
+    Lesson {
+        number: 8,
+        title: "Arrays and Loops",
+        source: sources::COLLECTIONS,
+        examples: examples![collections::basic_arrays],
+    },
+    Lesson {
+        number: 9,
+        title: "Mutable arrays",
+        source: sources::COLLECTIONS,
+        examples: examples![collections::mutable_arrays],
+    },
+    Lesson {
+        number: 10,
+        title: "Tuples",
+        source: sources::COLLECTIONS,
+        examples: examples![collections::basic_tuples],
+    },
+    Lesson {
+        number: 11,
+        title: "Strings Part 2",
+        source: sources::STRINGS,
+        examples: examples![strings::strings_second_example],
+    },
+    Lesson {
+        number: 12,
+        title: "Casting",
+        source: sources::BASICS,
+        examples: examples![basics::casting],
+    },
+    Lesson {
+        number: 13,
+        title: "Enums",
+        source: sources::ENUMS,
+        examples: examples![enums::enums],
+    },
+    Lesson {
+        number: 14,
+        title: "Vectors",
+        source: sources::COLLECTIONS,
+        examples: examples![collections::vectors],
+    },
+    Lesson {
+        number: 15,
+        title: "Functions",
+        source: sources::FUNCTIONS,
+        examples: examples![functions::functions],
+    },
+    Lesson {
+        number: 16,
+        title: "Generics",
+        source: sources::GENERICS,
+        examples: examples![generics::generics],
+    },
+    Lesson {
+        number: 17,
+        title: "Ownership",
+        source: sources::OWNERSHIP,
+        examples: examples![ownership::ownership],
+    },
+    Lesson {
+        number: 18,
+        title: "HashMaps",
+        source: sources::COLLECTIONS,
+        examples: examples![collections::hash_maps],
+    },
+    Lesson {
+        number: 19,
+        title: "Structs",
+        source: sources::STRUCTS,
+        examples: examples![structs::structs],
+    },
+    Lesson {
+        number: 20,
+        title: "Traits",
+        source: sources::TRAITS,
+        examples: examples![traits::traits],
+    },
+    Lesson {
+        number: 21,
+        title: "Modules",
+        source: sources::MODULES,
+        examples: examples![modules::modules],
+    },
+    Lesson {
+        number: 22,
+        title: "Error Handling",
+        source: sources::ERROR_HANDLING,
+        examples: examples![error_handling::error_handling],
+    },
+    Lesson {
+        number: 22,
+        title: "References part 2",
+        source: sources::OWNERSHIP,
+        examples: examples![ownership::immutable_reference, ownership::mutable_reference],
+    },
+    Lesson {
+        number: 23,
+        title: "Question Marker Operator",
+        source: sources::ERROR_HANDLING,
+        examples: examples![error_handling::question_marker],
+    },
+    Lesson {
+        number: 24,
+        title: "Iterators",
+        source: sources::ITERATORS,
+        examples: examples![iterators::iterators],
+    },
+    Lesson {
+        number: 25,
+        title: "Closures",
+        source: sources::CLOSURES,
+        examples: examples![closures::closures, closures::closures2],
+    },
+    Lesson {
+        number: 26,
+        title: "Smart Pointers",
+        source: sources::SMART_POINTERS,
+        examples: examples![smart_pointers::creating_boxes, smart_pointers::binary_tree_box],
+    },
+    Lesson {
+        number: 27,
+        title: "Advanced Ownership (Re-Borrowing)",
+        source: sources::OWNERSHIP,
+        examples: examples![
+            ownership::reborrow,
+            ownership::second_reborrow,
+            ownership::reborrow_thread,
+            ownership::reborrow_error,
+            ownership::reborrow_chain,
+            ownership::reborrow_hard,
+        ],
+    },
+];
+
+// Finds what should be executed for an id. A class number can match more than one lesson
+// (we have two Class 22), so we always return a list of (lesson, example) pairs.
+pub fn find(id: &str) -> Result<Vec<(&'static Lesson, &'static Example)>, LessonError> {
+    let id = id.trim();
+    let mut found = Vec::new();
+
+    if let Ok(number) = id.parse::<u32>() {
+        for lesson in LESSONS.iter().filter(|lesson| lesson.number == number) {
+            found.extend(lesson.examples.iter().map(|example| (lesson, example)));
+        }
+    } else if let Some(lesson) = LESSONS.iter().find(|lesson| lesson.matches_title(id)) {
+        found.extend(lesson.examples.iter().map(|example| (lesson, example)));
+    } else {
+        for lesson in LESSONS {
+            if let Some(example) = lesson.examples.iter().find(|example| example.name == id) {
+                found.push((lesson, example));
+            }
+        }
+    }
+
+    if found.is_empty() {
+        return Err(LessonError::Unknown(id.to_string()));
+    }
+    Ok(found)
+}
+
+// Every example of every class, in the order they are taught
+pub fn all() -> Vec<(&'static Lesson, &'static Example)> {
+    LESSONS
+        .iter()
+        .flat_map(|lesson| lesson.examples.iter().map(move |example| (lesson, example)))
+        .collect()
+}
@@ -0,0 +1,56 @@
+#![allow(unused)]
+// The lessons show the long way of writing things on purpose, so we don't want clippy to shorten them
+#![allow(
+    clippy::assign_op_pattern,
+    clippy::disallowed_names,
+    clippy::if_same_then_else,
+    clippy::manual_range_contains,
+    clippy::match_like_matches_macro,
+    clippy::needless_bool,
+    clippy::unnecessary_cast,
+    clippy::unused_io_amount
+)]
+
+// Every class of the tutorial lives in one of these modules, the comments of each function
+// are the explanation of the class.
+
+// Class 1 - Hello World, Class 3 - IO, Class 4 - Constants and Shadowing, Class 5 - Number Data types, Class 12 - Casting
+pub mod basics;
+// Class 2 - Strings, Class 11 - Strings Part 2
+pub mod strings;
+// Class 6 - If clauses, Class 7 - Comparison Operator
+pub mod control_flow;
+// Class 8 - Arrays and Loops, Class 9 - Mutable arrays, Class 10 - Tuples, Class 14 - Vectors, Class 18 - HashMaps
+pub mod collections;
+// Class 13 - Enums
+pub mod enums;
+// Class 15 - Functions
+pub mod functions;
+// Class 16 - Generics
+pub mod generics;
+// Class 17 - Ownership, Class 22 - References part 2, Class 27 - Advanced Ownership (Re-Borrowing)
+pub mod ownership;
+// Class 19 - Structs
+pub mod structs;
+// Class 20 - Traits
+pub mod traits;
+// Class 21 - Modules
+pub mod modules;
+pub mod restaurant;
+// Class 22 - Error Handling, Class 23 - Question Marker Operator, Class 24 - HTTP Request
+pub mod error_handling;
+// Class 24 - Iterators
+pub mod iterators;
+// Class 25 - Closures
+pub mod closures;
+// Class 26 - Smart Pointers
+pub mod smart_pointers;
+
+// The runner: lesson registry, interactive menu, quiz, progress, exercises and the book export
+pub mod book;
+pub mod exercises;
+pub mod highlight;
+pub mod lessons;
+pub mod menu;
+pub mod progress;
+pub mod quiz;
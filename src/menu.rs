@@ -0,0 +1,135 @@
+// Interactive lesson menu
+// It reads the commands the same way we did on Class 3 (io_example), with io::stdin().read_line,
+// and keeps the last lesson in memory, so "next", "previous" and "repeat" know where we are.
+
+use std::io;
+
+use crate::lessons::{self, Lesson, LESSONS};
+use crate::progress;
+
+enum Command {
+    Run(usize),
+    Next,
+    Previous,
+    Repeat,
+    List,
+    Quit,
+}
+
+struct Menu {
+    // Position in LESSONS of the last lesson we executed in this session
+    current: Option<usize>,
+}
+
+impl Menu {
+    fn parse(&self, input: &str) -> Result<Command, String> {
+        match input.to_lowercase().as_str() {
+            "n" | "next" => return Ok(Command::Next),
+            "p" | "previous" => return Ok(Command::Previous),
+            "r" | "repeat" => return Ok(Command::Repeat),
+            "l" | "list" => return Ok(Command::List),
+            "q" | "quit" => return Ok(Command::Quit),
+            _ => {}
+        }
+
+        // Anything else is a lesson id, the same ones accepted by `rust_tutorial run`. A number can
+        // be two classes (we have two Class 22), then the title says which one
+        let found = lessons::find(input).map_err(|err| err.to_string())?;
+        let mut classes: Vec<&Lesson> = Vec::new();
+        for (lesson, _) in found {
+            if !classes.iter().any(|class| std::ptr::eq(*class, lesson)) {
+                classes.push(lesson);
+            }
+        }
+        if classes.len() > 1 {
+            let headings: Vec<String> = classes.iter().map(|class| class.heading()).collect();
+            return Err(format!("`{}` is more than one class, type its title: {}", input, headings.join(" or ")));
+        }
+        position(classes[0])
+            .map(Command::Run)
+            .ok_or_else(|| format!("`{}` is not in the menu", input))
+    }
+
+    fn run(&mut self, index: usize) {
+        let lesson = &LESSONS[index];
+        println!("== {} ==", lesson.heading());
+        for example in lesson.examples {
+            println!("-- {} --", example.name);
+            if let Err(err) = (example.run)(&mut io::stdin().lock(), &mut io::stdout().lock()) {
+                println!("{} failed: {}", example.name, err);
+            }
+            println!();
+        }
+        self.current = Some(index);
+        progress::update(|progress| progress.record_run(lesson));
+    }
+
+    // Returns false when the user wants to leave the menu
+    fn execute(&mut self, command: Command) -> bool {
+        match command {
+            Command::Run(index) => self.run(index),
+            Command::Next => match self.current {
+                Some(index) if index + 1 < LESSONS.len() => self.run(index + 1),
+                Some(_) => println!("You are already in the last class"),
+                None => self.run(0),
+            },
+            Command::Previous => match self.current {
+                Some(index) if index > 0 => self.run(index - 1),
+                Some(_) => println!("You are already in the first class"),
+                None => println!("No class was executed yet, pick one by number"),
+            },
+            Command::Repeat => match self.current {
+                Some(index) => self.run(index),
+                None => println!("No class was executed yet, pick one by number"),
+            },
+            Command::List => print_menu(),
+            Command::Quit => return false,
+        }
+        true
+    }
+}
+
+fn position(lesson: &Lesson) -> Option<usize> {
+    LESSONS.iter().position(|candidate| std::ptr::eq(candidate, lesson))
+}
+
+fn print_menu() {
+    for lesson in LESSONS {
+        println!("  {}", lesson.heading());
+    }
+    println!("Type a class number or title, or: next, previous, repeat, list, quit");
+}
+
+pub fn interactive() {
+    let mut menu = Menu { current: None };
+    print_menu();
+
+    loop {
+        match menu.current {
+            Some(index) => print!("[{}] > ", LESSONS[index].heading()),
+            None => print!("> "),
+        }
+        io::Write::flush(&mut io::stdout()).expect("Failed to flush stdout");
+
+        let mut input: String = String::new();
+        // read_line returns 0 when stdin is closed (Ctrl+D), so we leave like a quit
+        let read = io::stdin().read_line(&mut input).expect("Didn't receive input");
+        if read == 0 {
+            break;
+        }
+
+        let input = input.trim();
+        if input.is_empty() {
+            continue;
+        }
+
+        match menu.parse(input) {
+            Ok(command) => {
+                if !menu.execute(command) {
+                    break;
+                }
+            }
+            Err(err) => println!("{}", err),
+        }
+    }
+}
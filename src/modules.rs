@@ -0,0 +1,13 @@
+use std::io::{self, Write};
+
+use crate::restaurant;
+
+// Class 21 - Modules
+// Crates: Modules that produce a library or executable
+// Modules: Organize and handle privacy
+// Packages: Build, test and share crates
+// Paths: A way of naming an item such as a struct, function
+pub fn modules(out: &mut dyn Write) -> io::Result<()> {
+    restaurant::order_food(out)?;
+    Ok(())
+}
@@ -0,0 +1,267 @@
+use std::io::{self, Write};
+use std::thread;
+
+// Class 17 - Ownership
+// Stack: Stores values in a last in first out format
+// Data on stack must have a defined fixed size
+
+// Heap: When putting data on the heap you request a certain amount of space. The OS finds space available
+// and returns an address for that space called a pointer.
+
+// RULES
+// 1 - Each value has a variable that is called its owner
+// 2 - There is only one owner at a time
+// 3 - When the owner goes out of the scope, the value disappears, as we don't have garbage collector in Rust
+// that is the way the language free memory, making sure that the application won't allocate more memory than needed
+pub fn ownership(out: &mut dyn Write) -> io::Result<()> {
+    let str1: String = String::from("World");
+    let str2: String = str1;
+
+    // If you try to execute the following print statement, it will return an error, because you moved
+    // the value from str1 to str2, and now str2 is the owner of the value, so you can't use it.
+    // println!("Hello {}", str1)
+
+    // In this case I'm using the & borrow operator, and I can still use vec1, vec2 and vec3, I can
+    // create as many borrow references as I want, and use all the variables, remembering that borrow
+    // references, you are only allowing the variable which is borrowing the value, to read that value,
+    // but if can't modify it.
+    let vec1: Vec<i32> = vec![1, 2, 3];
+    let vec2 = &vec1;
+    let vec3 = &vec1;
+    writeln!(out, "{:?}", vec2)?;
+    writeln!(out, "{:?}", vec3)?;
+
+    // Mutable references
+    // Different from borrow operator(&), we can have mutable borrow operator(&mut), which grants borrower
+    // the ability to change the value, this works like a pointer, but different from another languages, since
+    // Rust is memory safe, you can only borrow a mutable reference at a time, in the next case if you try to print
+    // str4, it will generate an error, because the owner of the mutable reference is currently str5.
+    // In another languages that have pointers, like Go and C, you can have as many mutable references as you like,
+    // but this is not memory safe. We also have unsafe pointers in Rust, but their use, is not recommended.
+    let mut str3: String = String::from("Hello");
+    let str4 = &mut str3;
+    let str5 = &mut str3;
+    let str6 = &str3;
+    // println!("{}", str4)
+    Ok(())
+}
+
+// Class 22 - References part 2
+
+// In Rust we have different types of pointers, they are:
+// - Smart pointers
+// - Raw pointers
+// - References
+
+// We have two types of references, the immutable reference(&), and the mutable reference (&mut)
+
+// In that example, we can se we are using immutable reference(&), which basically borrows a value
+// from a variable, and the variable that receives the reference, is borrowing it, so it can only read
+// but can't change the value
+// you can have as many immutable references as you like
+// in the last statement of the function we can se the print doesn't error, but if we do
+// let str1: String = String::from("ae");
+// let str2: String = str1
+// the print will fail, because the ownership moved from str1 to str2
+pub fn immutable_reference(out: &mut dyn Write) -> io::Result<()> {
+    let str1: String = String::from("ae");
+    let immut_ref: &String = &str1;
+    let immut_ref2: &String = &str1;
+
+    writeln!(out, "{}", str1)?;
+    Ok(())
+}
+
+// This is one thing that causes people to go mad about Rust, because the language itself is
+// type and memory safe, but how can you can ensure that it has safety, when you have two variables
+// sharing a reference?
+// There is a rule that changes everything, you can only have one mutable reference to a variable,
+// in languages like C or Golang, you can have multiple, in Rust, you can have only one, this helps
+// to keep the memory safe characteristic.
+// If you do this, you should get an error the last two lines
+// let mut str1: String = String::from("test");
+// let mut_ref: &mut String = &mut str1;
+// let mut_ref_second: &mut String = &mut str1;
+// let immut_ref: &String = &str1;
+//  println!("{}", mut_ref)
+pub fn mutable_reference(out: &mut dyn Write) -> io::Result<()> {
+    let mut str1: String = String::from("test");
+    let mut_ref: &mut String = &mut str1;
+    mut_ref.push_str("test 2");
+
+    writeln!(out, "{}", str1)?;
+    Ok(())
+}
+
+// For example, this function receives a mutable reference to a vector, with this, you would be able
+// to change a vector, changing also the original variable that declared the vector
+fn change_my_vector(my_list: &mut Vec<i32>) {
+    my_list.push(4);
+}
+
+// The rust borrow checker you always check the entire scope where the mutable reference was created,
+// so you can release the memory whenever the scope of the function ends, and that is why you can only
+// borrow a mutable reference once, because the borrow checker can count a single time, and check when
+// the memory can be released, so you can borrow it again to another variable
+// Also, take a look in the following code:
+//    let mut i:i32 = 1;
+//    let ref_i = &mut i;
+//    let another_ref_i = ref_i;
+// if you try to use ref_i, it will give an error, because the mutable reference can be only borrowed by
+// one variable at a time, so basically another_ref_i stole it from ref_i
+
+// Class 27 - Advanced Ownership (Re-Borrowing)
+// There is one important topic on Rust, that is not covered in the majority of documentations,
+// re-borrowing, which is an exception on mutable reference borrows.
+// As we know in Rust, we can only borrow one mutable reference at a time, for example, the following code would generate
+// an error
+// let a = 0;
+// let b = &mut a;
+// let c = &mut a;
+// println!("{b}"); // an error will be generated here, because the current borrower of the mutable reference is variable c.
+
+// This next example is also going to generate an error
+// let a = 0;
+// let b = &mut a;
+// let c = b;
+// println!("{b}"); Same case, we are using b, but we moved the borrow from b to c
+
+// Let's run reborrow() function, defined above, which in first instance we think that it will generate an error, but it works
+pub fn reborrow(out: &mut dyn Write) -> io::Result<()> {
+    let mut a = 7;
+    let b = &mut a;
+    let c = &mut *b; //  this is called re-borrow
+    *c = 1;
+
+    writeln!(out, "{b}")?;
+    // println!("{c}"); if we try to print c, it will generate an error, because the re-borrow already returned to b
+    Ok(())
+}
+// Why does it work ? When we use the syntax &mut *b, we are basically doing a re-borrow, which means we are temporally lending the mutable
+// reference to another, in this case, to c variable. When we use b again, the re-borrow ends, and we can't use c again.
+
+// Now the question is, why do we have the re-borrow mechanism, considering that Rust must be memory safe? One of the reasons
+// for re-borrowing, is that we might want to send a mutable reference to function call, and then use that mutable reference again when the
+// function returns. In Rust, everytime we pass a mutable reference to a function, Rust in the background forces a re-borrow.
+// Let's check this example:
+
+
+// Here, r is receiving a re-borrow of the mutable reference, and when the function ends, the re-borrow ends, and we still can use b variable.
+fn foo(r: &mut i32) {
+    *r += 1;
+}
+
+pub fn second_reborrow(out: &mut dyn Write) -> io::Result<()> {
+    let mut a = 7;
+    let b = &mut a; // first mutable reference borrow
+
+    foo(b); // here we are sending the mutable reference to the function, but what rust is doing behind the scenes is &mut *b
+    // this means we are re-borrowing the mutable reference that belongs, to b. We can't re-borrow from the owner, we can only re-borrow from the borrower.
+
+
+    writeln!(out, "{b}")?; // Expected error, `b` was moved out.
+    Ok(())
+}
+
+// This might sound really confusing, but let's think in the memory management side, on stack allocations. We know that all
+// function calls are stored in the stack, and that a stack is LIFO, so let's suppose that b borrowed a mutable reference from a.
+// This will be placed on the top of the stack, and on the next line I will re-borrow b to a function. This new function call will be
+// placed on the top of the stack, and Rust knows that there won't be any usages of b, until the new called function returns, because b it's defined
+// in the parent function scope. Therefore, the compiler knows that we can only re-use b, when the called function returns, so there won't be a concurrency,
+// which guarantees the application holds the memory safe attribute.
+
+// Furthermore, if you got this point clear, if we are in a multi-threading environment, does that still stands ?
+// Let's take a look in this example
+
+// fn start_thread(r: &mut i32) {
+//     thread::spawn( || {
+//         println!("{}", r);
+//     });
+// }
+
+pub fn reborrow_thread(out: &mut dyn Write) -> io::Result<()> {
+    let mut a = 7;
+    let b = &mut a;
+
+    // start_thread(b); // here we are re-borrowing, Rust is doing &mut *b in the background.
+    Ok(())
+}
+
+// If we uncomment the start_thread function and its call inside reborrow_thread, we will get the error
+// argument requires that `'1` must outlive `'static`. This means that Rust has the re-borrow mechanism, but doesn't
+// allow this value to escape to another thread, because this would require multiple references to a value, and since it's
+// not memory safe, this is not allowed.
+
+// The mechanism of re-borrow, also explains the &self arguments from implemented traits and functions,
+// when the function that has a &self is called, Rust in the background is re-borrowing its own value, to be used in the function.
+
+// Let's leave things a little bit more complicated, like mutable borrow, that we can only have one at a time, the re-borrow works the same way,
+// for example, this next function would cause an error
+pub fn reborrow_error(out: &mut dyn Write) -> io::Result<()> {
+    let mut a = 7;
+    let b = &mut a; // here we are passing the mutable reference to b
+    let c = &mut *b; // c is the re-borrow of b
+   // let d = &mut *b; // we moved the re-borrow of b to d
+    *c = 2; // as the re-borrow now it's with d, we can't use c, this will generate an error
+    Ok(())
+}
+
+// But, let's take a deeper look on the next function, which will work
+pub fn reborrow_chain(out: &mut dyn Write) -> io::Result<()> {
+    let mut a = 7;
+    let b = &mut a; // b receives the mutable borrow from a
+    let c = &mut *b; // c receives the re-borrow from b
+    let d = &mut *c; // d receives the re-borrow from c
+    *d = 2;
+    writeln!(out, "{c}")?;
+    writeln!(out, "{b}")?;
+    Ok(())
+}
+// This might blow your mind, why is this working ? Considering that we can only have a mutable borrow or re-borrow at a time.
+// But, if you take a deeper look, between this example, and the last one that doesn't work, this new one it's not moving the re-borrow ownership,
+// c is re-borrowing b, but d is re-borrowing c, and we may call this, re-borrowing chain. Remember, that we talked that when you execute a re-borrow,
+// once you use the variable that you re-borrowed from, the re-borrow ends? For illustrating this, let's imagine that re-borrow works like a LIFO stack,
+// so at the second re-borrow(let d = &mut *c), we will have a stack like this(don't confuse with the stack on the memory, it's metaphoric) ->
+//
+//
+//        | d re-borrow c      |    when we stop using d                             when we stop using c
+//        | c re-borrow b      |     or mention c again     | c re-borrow b      |    or mention b again
+//        | b mutable borrow a |          ====>             | b mutable borrow a |           ====>             | b mutable borrow a |
+//        | a definition       |                            | a definition       |                             | a definition       |
+
+// If we take a look in the example that generates an error
+//         | d re-borrow b      |     -> here on d variable, we are "stealing/moving" the re-borrow of b from c, if we try to use c again, it will generate an error
+//         | c re-borrow b      |
+//         | b mutable borrow a |
+//         | a definition       |
+
+// With this stack analogy, we can imagine how the compiler will think, in the sense that, I can do re-borrows in chain, because everytime I use the variable
+// I re-borrowed from, the re-borrow will end, and we will free memory allocated space, so in the workable example, if we use c again, the re-borrow that d take will end, and finally when we
+// use b, the re-borrow from c ends. Since this is a chain, if straight after doing all the re-borrows, if I use b, all the re-borrows will end.
+
+
+// I know you are tired from this, but let's do one last example:
+pub fn reborrow_hard(out: &mut dyn Write) -> io::Result<()> {
+    let mut foo = 1;
+    let mut borrower = &mut foo;
+
+    let rr = &mut borrower;
+    let borrower2 = &mut (**rr);
+
+    *borrower2 = 2;
+    *borrower = 3;
+
+    writeln!(out, "{}", foo)?;
+    Ok(())
+}
+// We gave this example just to push harder, even though you will probably never see a &mut &mut i32 variable
+// on a normal Rust code, but we are doing this just to complicate. When using  &mut (**rr), we are just doing the
+// normal re-borrow, and when we use borrower again, the re-borrow that was with borrower2 goes away.
+
+fn my_function(number: i32, out: &mut dyn Write) -> io::Result<()> {
+    writeln!(out, "{number}")
+
+    // If you try to execute the following print statement, it will return an error, because you moved
+    // the value from str1 to str2, and now str2 is the owner of the value, so you can't use it.
+    // println!("Hello {}", str1)
+}
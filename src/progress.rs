@@ -0,0 +1,128 @@
+// Learner progress
+// Every time a class is executed, a quiz is passed or an exercise is completed, we record it in
+// progress.json, inside the user's config directory, so a team lead can see where each new hire is.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::{self, ErrorKind, Write};
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+use crate::lessons::{Lesson, LESSONS};
+
+#[derive(Serialize, Deserialize, Default)]
+struct ClassProgress {
+    runs: u32,
+    quiz_passed: bool,
+    exercises_completed: bool,
+}
+
+#[derive(Serialize, Deserialize, Default)]
+pub struct Progress {
+    // Keyed by the lesson heading, like "Class 18 - HashMaps", because we have two Class 22
+    classes: BTreeMap<String, ClassProgress>,
+}
+
+// RUST_TUTORIAL_CONFIG_DIR wins, then the usual config directory of each OS
+pub fn config_dir() -> io::Result<PathBuf> {
+    if let Some(dir) = std::env::var_os("RUST_TUTORIAL_CONFIG_DIR") {
+        return Ok(PathBuf::from(dir));
+    }
+    let base = std::env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("APPDATA").map(PathBuf::from))
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))
+        .ok_or_else(|| io::Error::new(ErrorKind::NotFound, "could not find the config directory"))?;
+    Ok(base.join("rust_tutorial"))
+}
+
+impl Progress {
+    pub fn path() -> io::Result<PathBuf> {
+        Ok(config_dir()?.join("progress.json"))
+    }
+
+    // A learner that never ran anything doesn't have the file yet, so we start empty
+    pub fn load() -> io::Result<Progress> {
+        match fs::read_to_string(Progress::path()?) {
+            Ok(content) => serde_json::from_str(&content).map_err(|err| io::Error::new(ErrorKind::InvalidData, err)),
+            Err(err) if err.kind() == ErrorKind::NotFound => Ok(Progress::default()),
+            Err(err) => Err(err),
+        }
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        let path = Progress::path()?;
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let content = serde_json::to_string_pretty(self).map_err(|err| io::Error::new(ErrorKind::InvalidData, err))?;
+        fs::write(path, content)
+    }
+
+    fn class(&mut self, lesson: &Lesson) -> &mut ClassProgress {
+        self.classes.entry(lesson.heading()).or_default()
+    }
+
+    pub fn record_run(&mut self, lesson: &Lesson) {
+        self.class(lesson).runs += 1;
+    }
+
+    pub fn record_quiz(&mut self, lesson: &Lesson) {
+        self.class(lesson).quiz_passed = true;
+    }
+
+    pub fn record_exercises(&mut self, lesson: &Lesson) {
+        self.class(lesson).exercises_completed = true;
+    }
+
+    // The first class, in the order they are taught, that was never executed
+    pub fn next_lesson(&self) -> Option<&'static Lesson> {
+        LESSONS.iter().find(|lesson| {
+            self.classes
+                .get(&lesson.heading())
+                .is_none_or(|class| class.runs == 0)
+        })
+    }
+
+    pub fn print(&self, out: &mut dyn Write) -> io::Result<()> {
+        writeln!(out, "{:<45} {:>5}  {:<6}  Exercises", "Class", "Runs", "Quiz")?;
+        for lesson in LESSONS {
+            let (runs, quiz, exercises) = match self.classes.get(&lesson.heading()) {
+                Some(class) => (class.runs, class.quiz_passed, class.exercises_completed),
+                None => (0, false, false),
+            };
+            writeln!(
+                out,
+                "{:<45} {:>5}  {:<6}  {}",
+                lesson.heading(),
+                runs,
+                if quiz { "passed" } else { "-" },
+                if exercises { "done" } else { "-" }
+            )?;
+        }
+
+        writeln!(out)?;
+        match self.next_lesson() {
+            Some(lesson) => {
+                let selector = lesson.selector();
+                // A title has spaces, the shell needs it between quotes
+                let selector = if selector.contains(' ') { format!("\"{}\"", selector) } else { selector };
+                writeln!(out, "Next lesson: {} (rust_tutorial run {})", lesson.heading(), selector)
+            }
+            None => writeln!(out, "You have executed every class!"),
+        }
+    }
+}
+
+// Loads, changes and saves the progress. Progress is a nice to have, so if the file can't be
+// written we only warn, instead of failing the lesson the learner just ran.
+pub fn update(change: impl FnOnce(&mut Progress)) {
+    let result = Progress::load().and_then(|mut progress| {
+        change(&mut progress);
+        progress.save()
+    });
+    if let Err(err) = result {
+        eprintln!("warning: could not save the progress: {}", err);
+    }
+}
@@ -0,0 +1,200 @@
+// "Does it compile?" quiz for Class 27 - Advanced Ownership (Re-Borrowing)
+// Each question shows one of the re-borrow snippets, asks if it compiles and, when it does, what it prints.
+// The expected output is not written here, we run the lesson from the registry and capture what it writes.
+
+use std::io::{self, BufRead, Write};
+
+use crate::lessons::{self, Lesson};
+
+struct Question {
+    // Name of the lesson function in the registry, used to capture the expected output
+    lesson: &'static str,
+    snippet: &'static str,
+    compiles: bool,
+    explanation: &'static str,
+}
+
+static QUESTIONS: &[Question] = &[
+    Question {
+        lesson: "reborrow",
+        snippet: "\
+let mut a = 7;
+let b = &mut a;
+let c = &mut *b;
+*c = 1;
+
+println!(\"{b}\");",
+        compiles: true,
+        explanation: "\
+When we use the syntax &mut *b, we are doing a re-borrow, which means we are temporally lending the
+mutable reference to another variable, in this case to c. When we use b again, the re-borrow ends,
+and we can't use c again. c changed the value to 1 before that, so b prints 1.",
+    },
+    Question {
+        lesson: "second_reborrow",
+        snippet: "\
+fn foo(r: &mut i32) {
+    *r += 1;
+}
+
+let mut a = 7;
+let b = &mut a;
+
+foo(b);
+
+println!(\"{b}\");",
+        compiles: true,
+        explanation: "\
+Everytime we pass a mutable reference to a function, Rust in the background forces a re-borrow,
+so foo(b) is really foo(&mut *b). The function call is on top of the stack, and b can't be used until
+it returns, when the re-borrow ends. b was not moved, so we can still print it: 7 + 1 = 8.",
+    },
+    Question {
+        lesson: "reborrow_error",
+        snippet: "\
+let mut a = 7;
+let b = &mut a;
+let c = &mut *b;
+let d = &mut *b;
+*c = 2;",
+        compiles: false,
+        explanation: "\
+c is the re-borrow of b, and then d re-borrows b again, \"stealing\" the re-borrow from c.
+Like the mutable borrow, we can only have one re-borrow at a time, so using c after that
+is an error: cannot borrow `*b` as mutable more than once at a time (E0499).",
+    },
+    Question {
+        lesson: "reborrow_chain",
+        snippet: "\
+let mut a = 7;
+let b = &mut a;
+let c = &mut *b;
+let d = &mut *c;
+*d = 2;
+println!(\"{c}\");
+println!(\"{b}\");",
+        compiles: true,
+        explanation: "\
+This one is not moving the re-borrow, c re-borrows b, and d re-borrows c, a re-borrowing chain.
+Imagine the re-borrows as a LIFO stack: when we mention c again, the re-borrow of d ends, and when we
+mention b again, the re-borrow of c ends. d changed the value to 2, so both lines print 2.",
+    },
+    Question {
+        lesson: "reborrow_hard",
+        snippet: "\
+let mut foo = 1;
+let mut borrower = &mut foo;
+
+let rr = &mut borrower;
+let borrower2 = &mut (**rr);
+
+*borrower2 = 2;
+*borrower = 3;
+
+println!(\"{}\", foo);",
+        compiles: true,
+        explanation: "\
+You will probably never see a &mut &mut i32 on normal Rust code, but &mut (**rr) is just the normal
+re-borrow. When we use borrower again, the re-borrow that was with borrower2 goes away, and the last
+value written is 3.",
+    },
+];
+
+pub struct Score {
+    pub points: u32,
+    pub total: u32,
+}
+
+impl Score {
+    // Every question answered, and every answer right
+    pub fn passed(&self) -> bool {
+        let possible: u32 = QUESTIONS.iter().map(|question| if question.compiles { 2 } else { 1 }).sum();
+        self.points == possible
+    }
+}
+
+// The class the quiz is about, so the progress can record it
+pub fn lesson() -> &'static Lesson {
+    let found = lessons::find(QUESTIONS[0].lesson).expect("the quiz questions are in the registry");
+    found[0].0
+}
+
+fn expected_output(lesson: &str) -> io::Result<String> {
+    let found = lessons::find(lesson).map_err(|err| io::Error::new(io::ErrorKind::NotFound, err))?;
+    let mut output: Vec<u8> = Vec::new();
+    for (_, example) in found {
+        (example.run)(&mut io::empty(), &mut output)?;
+    }
+    Ok(String::from_utf8_lossy(&output).into_owned())
+}
+
+// The printed lines can be answered one per line or in a single line separated by spaces or commas
+fn normalize(text: &str) -> Vec<&str> {
+    text.split(|c: char| c.is_whitespace() || c == ',')
+        .filter(|word| !word.is_empty())
+        .collect()
+}
+
+fn ask(input: &mut dyn BufRead, out: &mut dyn Write, prompt: &str) -> io::Result<Option<String>> {
+    write!(out, "{} ", prompt)?;
+    out.flush()?;
+    let mut answer: String = String::new();
+    if input.read_line(&mut answer)? == 0 {
+        return Ok(None);
+    }
+    Ok(Some(answer.trim().to_string()))
+}
+
+pub fn run(input: &mut dyn BufRead, out: &mut dyn Write) -> io::Result<Score> {
+    let mut score = Score { points: 0, total: 0 };
+
+    for (index, question) in QUESTIONS.iter().enumerate() {
+        writeln!(out, "Question {} of {} ({})", index + 1, QUESTIONS.len(), question.lesson)?;
+        writeln!(out)?;
+        for line in question.snippet.lines() {
+            if line.is_empty() {
+                writeln!(out)?;
+            } else {
+                writeln!(out, "    {}", line)?;
+            }
+        }
+        writeln!(out)?;
+
+        // read_line returns 0 when the input is closed, so we stop and show the score we have
+        let answer = match ask(input, out, "Does it compile? (y/n)")? {
+            Some(answer) => answer.to_lowercase(),
+            None => break,
+        };
+        score.total += 1;
+        let said_compiles = answer.starts_with('y');
+        if said_compiles == question.compiles {
+            score.points += 1;
+            writeln!(out, "Correct!")?;
+        } else {
+            let verdict = if question.compiles { "it compiles" } else { "it doesn't compile" };
+            writeln!(out, "Wrong, {}", verdict)?;
+        }
+
+        if question.compiles {
+            let expected = expected_output(question.lesson)?;
+            let answer = match ask(input, out, "What does it print?")? {
+                Some(answer) => answer,
+                None => break,
+            };
+            score.total += 1;
+            if normalize(&answer) == normalize(&expected) {
+                score.points += 1;
+                writeln!(out, "Correct!")?;
+            } else {
+                writeln!(out, "Wrong, it prints: {}", normalize(&expected).join(" "))?;
+            }
+        }
+
+        writeln!(out)?;
+        writeln!(out, "{}", question.explanation)?;
+        writeln!(out)?;
+    }
+
+    writeln!(out, "Score: {}/{}", score.points, score.total)?;
+    Ok(score)
+}
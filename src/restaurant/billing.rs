@@ -0,0 +1,301 @@
+// Billing
+// Money is always an integer number of cents, 0.1 + 0.2 is not 0.3 with floats, and a bill that is
+// one cent off is a bug. Taxes and tips are Rates in basis points (1% is 100 basis points), and every
+// time a rate or a split leaves a fraction of a cent we round it explicitly, so the parts always add up.
+
+use std::error::Error;
+use std::fmt;
+use std::io::{self, Write};
+use std::iter::Sum;
+use std::ops::{Add, AddAssign, Neg, Sub, SubAssign};
+
+use serde::{Deserialize, Serialize};
+
+use crate::restaurant::order::Order;
+use crate::restaurant::pizza_order::menu::Menu;
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Default, Serialize, Deserialize)]
+pub struct Money(i64);
+
+impl Money {
+    pub const ZERO: Money = Money(0);
+
+    pub fn cents(cents: i64) -> Money {
+        Money(cents)
+    }
+
+    pub fn dollars(dollars: i64) -> Money {
+        Money(dollars * 100)
+    }
+
+    pub fn as_cents(&self) -> i64 {
+        self.0
+    }
+
+    pub fn times(&self, quantity: i64) -> Money {
+        Money(self.0 * quantity)
+    }
+
+    // The rate of this amount, rounded to the nearest cent, half a cent rounds away from zero
+    pub fn apply(&self, rate: Rate) -> Money {
+        let scaled = self.0 * rate.basis_points as i64;
+        let rounded = (scaled.abs() + 5_000) / 10_000;
+        Money(if scaled < 0 { -rounded } else { rounded })
+    }
+}
+
+// "$12.50", "-$1.05"
+impl fmt::Display for Money {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let sign = if self.0 < 0 { "-" } else { "" };
+        let text = format!("{}${}.{:02}", sign, self.0.abs() / 100, self.0.abs() % 100);
+        // So tables can align the amounts with {:>10}
+        f.pad(&text)
+    }
+}
+
+impl Add for Money {
+    type Output = Money;
+
+    fn add(self, other: Money) -> Money {
+        Money(self.0 + other.0)
+    }
+}
+
+impl Sub for Money {
+    type Output = Money;
+
+    fn sub(self, other: Money) -> Money {
+        Money(self.0 - other.0)
+    }
+}
+
+impl AddAssign for Money {
+    fn add_assign(&mut self, other: Money) {
+        self.0 += other.0;
+    }
+}
+
+impl SubAssign for Money {
+    fn sub_assign(&mut self, other: Money) {
+        self.0 -= other.0;
+    }
+}
+
+impl Neg for Money {
+    type Output = Money;
+
+    fn neg(self) -> Money {
+        Money(-self.0)
+    }
+}
+
+impl Sum for Money {
+    fn sum<I: Iterator<Item = Money>>(iter: I) -> Money {
+        iter.fold(Money::ZERO, |total, money| total + money)
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default, Serialize, Deserialize)]
+pub struct Rate {
+    pub basis_points: u32,
+}
+
+impl Rate {
+    pub fn percent(percent: u32) -> Rate {
+        Rate { basis_points: percent * 100 }
+    }
+
+    // 8.25% is Rate::basis_points(825)
+    pub fn basis_points(basis_points: u32) -> Rate {
+        Rate { basis_points }
+    }
+}
+
+// "15%", "8.25%"
+impl fmt::Display for Rate {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let (whole, fraction) = (self.basis_points / 100, self.basis_points % 100);
+        if fraction == 0 {
+            write!(f, "{}%", whole)
+        } else {
+            let fraction = format!("{:02}", fraction);
+            write!(f, "{}.{}%", whole, fraction.trim_end_matches('0'))
+        }
+    }
+}
+
+// Splits `total` in parts proportional to `weights`. Each part is rounded down, and the cents left
+// go one by one to the parts that lost the biggest fraction, the first ones when it's a tie
+pub fn allocate(total: Money, weights: &[i64]) -> Vec<Money> {
+    let weight_sum: i64 = weights.iter().sum();
+    if weights.is_empty() {
+        return Vec::new();
+    }
+    if weight_sum == 0 {
+        return allocate(total, &vec![1; weights.len()]);
+    }
+    if total.0 < 0 {
+        return allocate(-total, weights).into_iter().map(|part| -part).collect();
+    }
+
+    let mut parts: Vec<Money> = Vec::new();
+    let mut fractions: Vec<(i64, usize)> = Vec::new();
+    for (index, weight) in weights.iter().enumerate() {
+        let exact = total.0 * weight;
+        parts.push(Money(exact / weight_sum));
+        fractions.push((exact % weight_sum, index));
+    }
+    let left = total.0 - parts.iter().map(|part| part.0).sum::<i64>();
+    fractions.sort_by(|a, b| b.0.cmp(&a.0).then(a.1.cmp(&b.1)));
+    for (_, index) in fractions.iter().take(left as usize) {
+        parts[*index] += Money(1);
+    }
+    parts
+}
+
+#[derive(Debug, PartialEq)]
+pub enum BillingError {
+    NobodyToPay,
+    // split_by_line needs the name of who pays each line of the bill
+    OwnersDontMatch { lines: usize, owners: usize },
+}
+
+impl fmt::Display for BillingError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BillingError::NobodyToPay => write!(f, "a bill can't be split between nobody"),
+            BillingError::OwnersDontMatch { lines, owners } => {
+                write!(f, "the bill has {} lines but {} people were given", lines, owners)
+            }
+        }
+    }
+}
+
+impl Error for BillingError {}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct BillLine {
+    pub description: String,
+    pub amount: Money,
+}
+
+// Taken off the food, `description` says which promotion and why
+#[derive(Clone, PartialEq, Debug)]
+pub struct Discount {
+    pub description: String,
+    pub amount: Money,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct Bill {
+    pub customer: String,
+    pub lines: Vec<BillLine>,
+    pub discounts: Vec<Discount>,
+    pub tax_rate: Rate,
+    pub tip_rate: Rate,
+}
+
+impl Bill {
+    // One line per pizza, at the prices of `menu`, and no tip until the customer leaves one
+    pub fn for_order(order: &Order, menu: &Menu, tax_rate: Rate) -> Bill {
+        let lines = order
+            .pizzas
+            .iter()
+            .map(|pizza| BillLine {
+                description: pizza.to_string(),
+                amount: menu.price_of(pizza),
+            })
+            .collect();
+        Bill {
+            customer: order.customer.clone(),
+            lines,
+            discounts: Vec::new(),
+            tax_rate,
+            tip_rate: Rate::default(),
+        }
+    }
+
+    pub fn with_tip(mut self, tip_rate: Rate) -> Bill {
+        self.tip_rate = tip_rate;
+        self
+    }
+
+    pub fn with_discounts(mut self, discounts: Vec<Discount>) -> Bill {
+        self.discounts = discounts;
+        self
+    }
+
+    pub fn subtotal(&self) -> Money {
+        self.lines.iter().map(|line| line.amount).sum()
+    }
+
+    // Never more than the food, a bill can't pay the customer
+    pub fn discount(&self) -> Money {
+        let discount: Money = self.discounts.iter().map(|discount| discount.amount).sum();
+        discount.min(self.subtotal())
+    }
+
+    // The tax is on what the customer pays for the food, after the discounts
+    pub fn tax(&self) -> Money {
+        (self.subtotal() - self.discount()).apply(self.tax_rate)
+    }
+
+    // The tip is on the food, before the discounts and the tax, the waiter served all of it
+    pub fn tip(&self) -> Money {
+        self.subtotal().apply(self.tip_rate)
+    }
+
+    pub fn total(&self) -> Money {
+        self.subtotal() - self.discount() + self.tax() + self.tip()
+    }
+
+    pub fn split_evenly(&self, people: usize) -> Result<Vec<Money>, BillingError> {
+        if people == 0 {
+            return Err(BillingError::NobodyToPay);
+        }
+        Ok(allocate(self.total(), &vec![1; people]))
+    }
+
+    // Everybody pays their own lines, plus their part of the tax and the tip. `owners` has who
+    // pays each line, in the order of the lines, and the result is in the order people first appear
+    pub fn split_by_line(&self, owners: &[&str]) -> Result<Vec<(String, Money)>, BillingError> {
+        if owners.len() != self.lines.len() {
+            return Err(BillingError::OwnersDontMatch {
+                lines: self.lines.len(),
+                owners: owners.len(),
+            });
+        }
+        if owners.is_empty() {
+            return Err(BillingError::NobodyToPay);
+        }
+
+        let mut people: Vec<(String, i64)> = Vec::new();
+        for (owner, line) in owners.iter().zip(&self.lines) {
+            match people.iter_mut().find(|(name, _)| name == owner) {
+                Some((_, spent)) => *spent += line.amount.0,
+                None => people.push((owner.to_string(), line.amount.0)),
+            }
+        }
+        let weights: Vec<i64> = people.iter().map(|(_, spent)| *spent).collect();
+        let parts = allocate(self.total(), &weights);
+        Ok(people.into_iter().map(|(name, _)| name).zip(parts).collect())
+    }
+
+    pub fn print_receipt(&self, out: &mut dyn Write) -> io::Result<()> {
+        writeln!(out, "Receipt for {}", self.customer)?;
+        for line in &self.lines {
+            writeln!(out, "{:<60} {:>10}", line.description, line.amount)?;
+        }
+        writeln!(out, "{}", "-".repeat(71))?;
+        writeln!(out, "{:<60} {:>10}", "Subtotal", self.subtotal())?;
+        for discount in &self.discounts {
+            writeln!(out, "{:<60} {:>10}", discount.description, -discount.amount)?;
+        }
+        writeln!(out, "{:<60} {:>10}", format!("Tax {}", self.tax_rate), self.tax())?;
+        if self.tip_rate.basis_points > 0 {
+            writeln!(out, "{:<60} {:>10}", format!("Tip {}", self.tip_rate), self.tip())?;
+        }
+        writeln!(out, "{:<60} {:>10}", "Total", self.total())
+    }
+}
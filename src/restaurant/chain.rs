@@ -0,0 +1,603 @@
+// The chain
+// The restaurant grew into a chain, its branches are declared in a JSON file, chain.json next to this
+// module is an example. Every branch starts from the menu of the chain and only says what it changes:
+// the prices of the sizes and toppings, the toppings it adds and the ones it doesn't make. It has its
+// own opening hours, its own tables and its own inventory, stocked for its menu with the `stock` it
+// declares, and it keeps the orders it sold, in orders-<branch>.jsonl once the chain has a directory.
+// An online order goes to the nearest branch that is open and can make it, and the report of the
+// chain sums up the day of every branch, each one at its own prices.
+//
+// The branches are in the same city, `utc_offset_minutes` in chain.json says how far its clocks are
+// from UTC. The opening hours are on those clocks, and the report counts the days and hours with them.
+//
+// The city is a grid of blocks. A branch is `east` and `north` blocks away from the center, negative
+// is west and south, and the distance is the blocks we walk, not a straight line.
+
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::io::{self, ErrorKind, Write};
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::restaurant::clock::{SimulatedClock, Timestamp, UtcOffset};
+use crate::restaurant::counter::Counter;
+use crate::restaurant::inventory::{Inventory, StockLevels};
+use crate::restaurant::order::{Order, OrderError};
+use crate::restaurant::pizza_order::menu::{Menu, Size, Topping};
+use crate::restaurant::pizza_order::dietary::DietaryNeeds;
+use crate::restaurant::pizza_order::help_customer::{self, Refusal};
+use crate::restaurant::pizza_order::{Pizza, PizzaBuilder};
+use crate::restaurant::report::DailyReport;
+use crate::restaurant::seating::{Restaurant, Table};
+use crate::restaurant::store::{OrderFilter, OrderStore, StoredOrder};
+
+const STANDARD_CHAIN: &str = include_str!("chain.json");
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default, Serialize, Deserialize)]
+pub struct Location {
+    pub east: i64,
+    pub north: i64,
+}
+
+impl Location {
+    pub fn new(east: i64, north: i64) -> Location {
+        Location { east, north }
+    }
+
+    pub fn blocks_to(&self, other: Location) -> u64 {
+        self.east.abs_diff(other.east) + self.north.abs_diff(other.north)
+    }
+}
+
+// "3 east, 2 south"
+impl fmt::Display for Location {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let east = if self.east < 0 { "west" } else { "east" };
+        let north = if self.north < 0 { "south" } else { "north" };
+        write!(f, "{} {}, {} {}", self.east.abs(), east, self.north.abs(), north)
+    }
+}
+
+// Hours on the clocks of the city, `to_hour` is not included, and 17 to 2 closes past midnight
+#[derive(Clone, Copy, PartialEq, Debug, Serialize, Deserialize)]
+pub struct OpeningHours {
+    pub from_hour: u64,
+    pub to_hour: u64,
+}
+
+impl OpeningHours {
+    // `offset` is the one of the city
+    pub fn is_open(&self, at: Timestamp, offset: UtcOffset) -> bool {
+        let hour = at.local(offset).hour();
+        if self.from_hour <= self.to_hour {
+            self.from_hour <= hour && hour < self.to_hour
+        } else {
+            self.from_hour <= hour || hour < self.to_hour
+        }
+    }
+}
+
+// "17:00-02:00"
+impl fmt::Display for OpeningHours {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:02}:00-{:02}:00", self.from_hour, self.to_hour)
+    }
+}
+
+// What a branch changes of the menu of the chain, nothing by default
+#[derive(Clone, PartialEq, Debug, Default, Serialize, Deserialize)]
+pub struct MenuOverrides {
+    // The price of the pizza before the toppings, in cents, the sizes that are not here keep theirs
+    #[serde(default)]
+    pub sizes: BTreeMap<Size, u32>,
+    #[serde(default)]
+    pub topping_prices: BTreeMap<String, u32>,
+    #[serde(default)]
+    pub extra_toppings: Vec<Topping>,
+    #[serde(default)]
+    pub removed_toppings: Vec<String>,
+}
+
+impl MenuOverrides {
+    pub fn apply(&self, menu: &Menu) -> Menu {
+        let mut menu = menu.clone();
+        menu.sizes.extend(self.sizes.iter().map(|(size, cents)| (*size, *cents)));
+        menu.toppings.retain(|topping| !self.removed_toppings.contains(&topping.name));
+        // A topping the menu already has is replaced, so the branch can change its allergens or diets
+        for extra in &self.extra_toppings {
+            match menu.toppings.iter_mut().find(|topping| topping.name == extra.name) {
+                Some(topping) => *topping = extra.clone(),
+                None => menu.toppings.push(extra.clone()),
+            }
+        }
+        for topping in &mut menu.toppings {
+            if let Some(cents) = self.topping_prices.get(&topping.name) {
+                topping.price_cents = *cents;
+            }
+        }
+        menu
+    }
+}
+
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct BranchConfig {
+    pub name: String,
+    pub location: Location,
+    pub hours: OpeningHours,
+    #[serde(default)]
+    pub menu: MenuOverrides,
+    // The seats of each table, no tables is the floor of Restaurant::standard
+    #[serde(default)]
+    pub tables: Vec<u32>,
+    #[serde(default)]
+    pub stock: StockLevels,
+}
+
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct ChainConfig {
+    #[serde(default, rename = "utc_offset_minutes")]
+    pub utc_offset: UtcOffset,
+    pub branches: Vec<BranchConfig>,
+}
+
+impl ChainConfig {
+    // The chain.json that comes with the tutorial
+    pub fn standard() -> ChainConfig {
+        ChainConfig::from_json(STANDARD_CHAIN).expect("chain.json has valid branches")
+    }
+
+    pub fn from_json(json: &str) -> io::Result<ChainConfig> {
+        serde_json::from_str(json).map_err(|err| io::Error::new(ErrorKind::InvalidData, err))
+    }
+
+    pub fn load(path: &Path) -> io::Result<ChainConfig> {
+        ChainConfig::from_json(&fs::read_to_string(path)?)
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum ChainError {
+    UnknownBranch(String),
+    // The branch, and the id that is not in its history
+    UnknownOrder(String, u64),
+    AllClosed(Timestamp),
+    // No open branch has every topping of the order on its menu
+    NoBranchCanMake,
+    Order(OrderError),
+    // The branch cancelled the order, it's in its history
+    Refused(Refusal),
+    // The message of an io::Error, which can't be compared
+    Io(String),
+}
+
+impl fmt::Display for ChainError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ChainError::UnknownBranch(name) => write!(f, "there is no branch {}", name),
+            ChainError::UnknownOrder(branch, id) => write!(f, "{} has no order {}", branch, id),
+            ChainError::AllClosed(at) => write!(f, "every branch is closed at {}", at),
+            ChainError::NoBranchCanMake => write!(f, "no open branch can make that order"),
+            ChainError::Order(err) => write!(f, "{}", err),
+            ChainError::Refused(refusal) => write!(f, "{}", refusal),
+            ChainError::Io(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+impl Error for ChainError {}
+
+impl From<OrderError> for ChainError {
+    fn from(err: OrderError) -> ChainError {
+        ChainError::Order(err)
+    }
+}
+
+impl From<io::Error> for ChainError {
+    fn from(err: io::Error) -> ChainError {
+        ChainError::Io(err.to_string())
+    }
+}
+
+impl From<ChainError> for io::Error {
+    fn from(err: ChainError) -> io::Error {
+        io::Error::other(err)
+    }
+}
+
+pub struct Branch {
+    config: BranchConfig,
+    menu: Menu,
+    restaurant: Restaurant,
+    counter: Counter,
+    utc_offset: UtcOffset,
+    orders: Vec<StoredOrder>,
+    // Where the orders are saved, they are only in memory without it
+    store: Option<OrderStore>,
+}
+
+impl Branch {
+    pub fn new(config: BranchConfig, chain_menu: &Menu) -> Branch {
+        let menu = config.menu.apply(chain_menu);
+        let restaurant = if config.tables.is_empty() {
+            Restaurant::standard()
+        } else {
+            let tables = config
+                .tables
+                .iter()
+                .zip(1..)
+                .map(|(capacity, number)| Table { number, capacity: *capacity })
+                .collect();
+            Restaurant::new(tables, 45 * 60)
+        };
+        Branch {
+            restaurant,
+            counter: Counter::new(Inventory::stocked_with(&menu, &config.stock)),
+            menu,
+            config,
+            utc_offset: UtcOffset::default(),
+            orders: Vec::new(),
+            store: None,
+        }
+    }
+
+    // The branch starts with the orders of the store and saves the new ones in it
+    pub fn with_store(mut self, store: OrderStore) -> Branch {
+        self.orders = store.orders().to_vec();
+        self.store = Some(store);
+        self
+    }
+
+    // The clocks of the branch, UTC until the chain says otherwise
+    pub fn with_utc_offset(mut self, offset: UtcOffset) -> Branch {
+        self.utc_offset = offset;
+        self
+    }
+
+    pub fn name(&self) -> &str {
+        &self.config.name
+    }
+
+    pub fn location(&self) -> Location {
+        self.config.location
+    }
+
+    pub fn hours(&self) -> OpeningHours {
+        self.config.hours
+    }
+
+    pub fn utc_offset(&self) -> UtcOffset {
+        self.utc_offset
+    }
+
+    pub fn is_open(&self, at: Timestamp) -> bool {
+        self.config.hours.is_open(at, self.utc_offset)
+    }
+
+    // The menu of the chain with the overrides of the branch
+    pub fn menu(&self) -> &Menu {
+        &self.menu
+    }
+
+    pub fn restaurant(&self) -> &Restaurant {
+        &self.restaurant
+    }
+
+    pub fn restaurant_mut(&mut self) -> &mut Restaurant {
+        &mut self.restaurant
+    }
+
+    pub fn counter(&self) -> &Counter {
+        &self.counter
+    }
+
+    pub fn counter_mut(&mut self) -> &mut Counter {
+        &mut self.counter
+    }
+
+    // Every topping of the pizzas is on the menu of the branch
+    pub fn has_on_menu(&self, pizzas: &[Pizza]) -> bool {
+        pizzas
+            .iter()
+            .flat_map(|pizza| &pizza.toppings)
+            .all(|topping| self.menu.topping(&topping.name).is_some())
+    }
+
+    // The toppings are on the menu, and the kitchen has the ingredients for all the pizzas
+    pub fn can_make(&self, pizzas: &[Pizza]) -> bool {
+        self.has_on_menu(pizzas) && self.counter.inventory().check(pizzas).is_ok()
+    }
+
+    pub fn orders(&self) -> &[StoredOrder] {
+        &self.orders
+    }
+
+    pub fn store(&self) -> Option<&OrderStore> {
+        self.store.as_ref()
+    }
+
+    // Keeps the order in the history of the branch, the first one is 1
+    pub fn record(&mut self, order: Order) -> io::Result<u64> {
+        let id = match &mut self.store {
+            Some(store) => store.insert(&order)?,
+            None => self.orders.len() as u64 + 1,
+        };
+        self.orders.push(StoredOrder { id, order });
+        Ok(id)
+    }
+
+    // Changes the order only in memory, update_order saves the change in the store too
+    pub fn order_mut(&mut self, id: u64) -> Option<&mut Order> {
+        self.orders.iter_mut().find(|stored| stored.id == id).map(|stored| &mut stored.order)
+    }
+
+    // The order is only changed when `change` succeeds, and then saved
+    pub fn update_order(
+        &mut self,
+        id: u64,
+        change: impl FnOnce(&mut Order) -> Result<(), OrderError>,
+    ) -> Result<(), ChainError> {
+        let stored = self
+            .orders
+            .iter_mut()
+            .find(|stored| stored.id == id)
+            .ok_or_else(|| ChainError::UnknownOrder(String::from(self.config.name.as_str()), id))?;
+        let mut order = stored.order.clone();
+        change(&mut order)?;
+        if let Some(store) = &mut self.store {
+            store.update(id, &order)?;
+        }
+        stored.order = order;
+        Ok(())
+    }
+
+    // The orders of the parties seated on `date` of the local calendar, "2024-03-15"
+    pub fn on_date(&self, date: &str) -> Vec<&StoredOrder> {
+        let filter = OrderFilter {
+            date: Some(String::from(date)),
+            utc_offset: self.utc_offset,
+            ..OrderFilter::default()
+        };
+        self.orders.iter().filter(|stored| filter.matches(stored)).collect()
+    }
+}
+
+// The same pizza, built again from the menu of the branch
+fn builder<'a>(menu: &'a Menu, pizza: &Pizza) -> PizzaBuilder<'a> {
+    let mut builder = PizzaBuilder::new(menu).size(pizza.size).dough(pizza.dough).cheese(pizza.cheese);
+    for topping in &pizza.toppings {
+        builder = builder.topping(&topping.name);
+    }
+    builder
+}
+
+// Where an online order went
+#[derive(Clone, PartialEq, Debug)]
+pub struct Routed {
+    pub branch: String,
+    // The id of the order in the branch
+    pub id: u64,
+    pub blocks: u64,
+}
+
+pub struct Chain {
+    menu: Menu,
+    utc_offset: UtcOffset,
+    branches: Vec<Branch>,
+}
+
+impl Chain {
+    pub fn new(config: ChainConfig, menu: Menu) -> Chain {
+        let utc_offset = config.utc_offset;
+        let branches = config
+            .branches
+            .into_iter()
+            .map(|branch| Branch::new(branch, &menu).with_utc_offset(utc_offset))
+            .collect();
+        Chain { menu, utc_offset, branches }
+    }
+
+    // The branches of chain.json, with the standard menu
+    pub fn standard() -> Chain {
+        Chain::new(ChainConfig::standard(), Menu::standard())
+    }
+
+    // Every branch keeps its orders in `dir`, in orders-<branch>.jsonl, and starts with the ones
+    // that are already there
+    pub fn with_stores(mut self, dir: &Path) -> io::Result<Chain> {
+        self.branches = self
+            .branches
+            .into_iter()
+            .map(|branch| {
+                let store = OrderStore::open(&Chain::store_path(dir, branch.name()))?;
+                Ok(branch.with_store(store))
+            })
+            .collect::<io::Result<Vec<Branch>>>()?;
+        Ok(self)
+    }
+
+    // "Harbor" is orders-harbor.jsonl
+    pub fn store_path(dir: &Path, branch: &str) -> PathBuf {
+        let name: String = branch
+            .to_lowercase()
+            .chars()
+            .map(|c| if c.is_alphanumeric() { c } else { '-' })
+            .collect();
+        dir.join(format!("orders-{}.jsonl", name))
+    }
+
+    pub fn menu(&self) -> &Menu {
+        &self.menu
+    }
+
+    pub fn utc_offset(&self) -> UtcOffset {
+        self.utc_offset
+    }
+
+    pub fn branches(&self) -> &[Branch] {
+        &self.branches
+    }
+
+    // Branch names are compared ignoring case
+    pub fn branch(&self, name: &str) -> Result<&Branch, ChainError> {
+        self.branches
+            .iter()
+            .find(|branch| branch.name().eq_ignore_ascii_case(name))
+            .ok_or_else(|| ChainError::UnknownBranch(String::from(name)))
+    }
+
+    pub fn branch_mut(&mut self, name: &str) -> Result<&mut Branch, ChainError> {
+        self.branches
+            .iter_mut()
+            .find(|branch| branch.name().eq_ignore_ascii_case(name))
+            .ok_or_else(|| ChainError::UnknownBranch(String::from(name)))
+    }
+
+    pub fn open_at(&self, at: Timestamp) -> Vec<&Branch> {
+        self.branches.iter().filter(|branch| branch.is_open(at)).collect()
+    }
+
+    // The position of the nearest branch that is open at `at` and can make the pizzas, when two are
+    // as near the first one of chain.json gets the order. When every open branch ran out of something,
+    // the nearest one with the toppings on its menu gets it and swaps what it ran out of
+    fn nearest(&self, from: Location, pizzas: &[Pizza], at: Timestamp) -> Result<usize, ChainError> {
+        let open: Vec<usize> = (0..self.branches.len()).filter(|&index| self.branches[index].is_open(at)).collect();
+        if open.is_empty() {
+            return Err(ChainError::AllClosed(at));
+        }
+        let nearest = |can: &dyn Fn(&Branch) -> bool| {
+            open.iter()
+                .copied()
+                .filter(|&index| can(&self.branches[index]))
+                .min_by_key(|&index| (from.blocks_to(self.branches[index].location()), index))
+        };
+        nearest(&|branch| branch.can_make(pizzas))
+            .or_else(|| nearest(&|branch| branch.has_on_menu(pizzas)))
+            .ok_or(ChainError::NoBranchCanMake)
+    }
+
+    pub fn route(&self, from: Location, pizzas: &[Pizza], at: Timestamp) -> Result<&Branch, ChainError> {
+        Ok(&self.branches[self.nearest(from, pizzas, at)?])
+    }
+
+    // An order of a customer without dietary needs
+    pub fn order_online(&mut self, customer: &str, from: Location, pizzas: Vec<Pizza>, at: Timestamp) -> Result<Routed, ChainError> {
+        self.order_online_with_needs(customer, &DietaryNeeds::default(), from, pizzas, at)
+    }
+
+    // The order goes to the branch route chooses, and its counter takes it like the waiter does: a
+    // topping the branch ran out of is swapped for the closest one, and a pizza with an allergen of
+    // the customer is refused. The branch keeps a refused order too, cancelled. An online order has
+    // no table
+    pub fn order_online_with_needs(
+        &mut self,
+        customer: &str,
+        needs: &DietaryNeeds,
+        from: Location,
+        pizzas: Vec<Pizza>,
+        at: Timestamp,
+    ) -> Result<Routed, ChainError> {
+        let index = self.nearest(from, &pizzas, at)?;
+        let branch = &mut self.branches[index];
+
+        let mut order = Order::seat(customer, at);
+        let choices = pizzas.iter().map(|pizza| builder(&branch.menu, pizza)).collect();
+        let clock = SimulatedClock::starting_at(at);
+        let refusal = help_customer::order_pizzas(&mut order, choices, needs, branch.counter.inventory_mut(), &clock, &mut io::sink())?;
+        if refusal.is_none() {
+            order.send_to_kitchen(at)?;
+        }
+        let id = branch.record(order)?;
+        match refusal {
+            Some(refusal) => Err(ChainError::Refused(refusal)),
+            None => Ok(Routed {
+                branch: String::from(branch.name()),
+                id,
+                blocks: from.blocks_to(branch.location()),
+            }),
+        }
+    }
+
+    pub fn print(&self, at: Timestamp, out: &mut dyn Write) -> io::Result<()> {
+        writeln!(out, "{:<10} {:<18} {:<11}  {:<6}  {:>6}  Menu", "Branch", "Location", "Hours", "Now", "Tables")?;
+        for branch in &self.branches {
+            let now = if branch.is_open(at) { "open" } else { "closed" };
+            let mut changes: Vec<String> = branch.config.menu.extra_toppings.iter().map(|topping| format!("+{}", topping.name)).collect();
+            changes.extend(branch.config.menu.removed_toppings.iter().map(|name| format!("-{}", name)));
+            if !branch.config.menu.sizes.is_empty() || !branch.config.menu.topping_prices.is_empty() {
+                changes.push(String::from("own prices"));
+            }
+            writeln!(
+                out,
+                "{:<10} {:<18} {:<11}  {:<6}  {:>6}  {}",
+                branch.name(),
+                branch.location().to_string(),
+                branch.hours().to_string(),
+                now,
+                branch.restaurant.tables().len(),
+                changes.join(", ")
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone, PartialEq, Debug, Serialize)]
+pub struct BranchReport {
+    pub branch: String,
+    pub report: DailyReport,
+}
+
+// The day of every branch, and of the whole chain
+#[derive(Clone, PartialEq, Debug, Serialize)]
+pub struct ChainReport {
+    pub date: String,
+    pub branches: Vec<BranchReport>,
+    pub chain: DailyReport,
+}
+
+impl ChainReport {
+    pub fn for_date(chain: &Chain, date: &str) -> ChainReport {
+        let mut priced: Vec<(&StoredOrder, &Menu)> = Vec::new();
+        let mut branches = Vec::new();
+        for branch in &chain.branches {
+            let orders = branch.on_date(date);
+            priced.extend(orders.iter().map(|stored| (*stored, &branch.menu)));
+            branches.push(BranchReport {
+                branch: String::from(branch.name()),
+                report: DailyReport::from_orders(date, &orders, &branch.menu, branch.utc_offset),
+            });
+        }
+        ChainReport {
+            date: String::from(date),
+            branches,
+            chain: DailyReport::from_priced(date, &priced, chain.utc_offset),
+        }
+    }
+
+    // A row per branch and one for the chain, then the toppings and customers of the whole chain
+    pub fn print(&self, out: &mut dyn Write) -> io::Result<()> {
+        writeln!(out, "Branches on {}", self.date)?;
+        writeln!(out, "{:<20} {:>8} {:>8} {:>8} {:>12} {:>12}", "Branch", "Orders", "Paid", "Pizzas", "Revenue", "Average")?;
+        let mut row = |name: &str, report: &DailyReport| -> io::Result<()> {
+            writeln!(
+                out,
+                "{:<20} {:>8} {:>8} {:>8} {:>12} {:>12}",
+                name, report.orders, report.paid_orders, report.pizzas_sold, report.revenue, report.average_ticket
+            )
+        };
+        for branch in &self.branches {
+            row(&branch.branch, &branch.report)?;
+        }
+        row("Chain", &self.chain)?;
+        writeln!(out)?;
+        self.chain.print(out)
+    }
+
+    pub fn write_json(&self, out: &mut dyn Write) -> io::Result<()> {
+        serde_json::to_writer_pretty(&mut *out, self).map_err(|err| io::Error::new(ErrorKind::InvalidData, err))?;
+        writeln!(out)
+    }
+}
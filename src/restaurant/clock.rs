@@ -0,0 +1,155 @@
+// Time in the restaurant
+// A Timestamp is the number of seconds since 1970-01-01 00:00:00 UTC, like the unix time.
+// The restaurant asks a Clock what time it is, instead of asking the system directly.
+// Opening hours, happy hours and the reports go by the clocks on the wall of the restaurant, a
+// UtcOffset says how far those are from UTC.
+
+use std::fmt;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Serialize, Deserialize)]
+pub struct Timestamp(pub u64);
+
+impl Timestamp {
+    pub fn seconds(&self) -> u64 {
+        self.0
+    }
+
+    pub fn plus(&self, seconds: u64) -> Timestamp {
+        Timestamp(self.0 + seconds)
+    }
+
+    // Seconds from `earlier` until this timestamp, 0 if `earlier` is actually later
+    pub fn since(&self, earlier: Timestamp) -> u64 {
+        self.0.saturating_sub(earlier.0)
+    }
+
+    // "2024-03-15"
+    pub fn date(&self) -> String {
+        let (year, month, day) = civil_from_days(self.0 / 86_400);
+        format!("{:04}-{:02}-{:02}", year, month, day)
+    }
+
+    // From 0 to 23
+    pub fn hour(&self) -> u64 {
+        self.0 % 86_400 / 3600
+    }
+
+    // The same day at `hour`:00:00
+    pub fn at_hour(&self, hour: u64) -> Timestamp {
+        Timestamp(self.0 - self.0 % 86_400 + hour * 3600)
+    }
+
+    // What the clocks `offset` from UTC say, its date() and hour() are the local ones
+    pub fn local(&self, offset: UtcOffset) -> Timestamp {
+        Timestamp(self.0.saturating_add_signed(offset.seconds()))
+    }
+
+    // The same local day at `hour`:00:00 local time, back in UTC
+    pub fn at_local_hour(&self, hour: u64, offset: UtcOffset) -> Timestamp {
+        let local = self.local(offset).at_hour(hour);
+        Timestamp(local.0.saturating_add_signed(-offset.seconds()))
+    }
+}
+
+// Minutes ahead of UTC, 60 in Paris in winter and -300 in New York, and 0 by default
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default, Serialize, Deserialize)]
+pub struct UtcOffset(pub i64);
+
+impl UtcOffset {
+    pub fn hours(hours: i64) -> UtcOffset {
+        UtcOffset(hours * 60)
+    }
+
+    pub fn seconds(&self) -> i64 {
+        self.0 * 60
+    }
+}
+
+// "2024-03-15 12:30:00", always in UTC
+impl fmt::Display for Timestamp {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let seconds_of_day = self.0 % 86_400;
+        write!(
+            f,
+            "{} {:02}:{:02}:{:02}",
+            self.date(),
+            seconds_of_day / 3600,
+            seconds_of_day % 3600 / 60,
+            seconds_of_day % 60
+        )
+    }
+}
+
+// Year, month and day of the number of days since 1970-01-01, the algorithm is from
+// http://howardhinnant.github.io/date_algorithms.html, it counts the years from March so
+// February and its leap day are the last month of the year
+fn civil_from_days(days: u64) -> (u64, u64, u64) {
+    let days = days + 719_468;
+    let era = days / 146_097;
+    let day_of_era = days % 146_097;
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_from_march = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_from_march + 2) / 5 + 1;
+    let month = if month_from_march < 10 { month_from_march + 3 } else { month_from_march - 9 };
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year, month, day)
+}
+
+pub trait Clock {
+    fn now(&self) -> Timestamp;
+}
+
+// The real time of the computer
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> Timestamp {
+        let elapsed = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default();
+        Timestamp(elapsed.as_secs())
+    }
+}
+
+// A clock that only moves when we say so, the simulations and the tests use it so the same
+// orders always happen at the same times
+pub struct SimulatedClock {
+    now: AtomicU64,
+}
+
+impl SimulatedClock {
+    pub fn starting_at(start: Timestamp) -> SimulatedClock {
+        SimulatedClock {
+            now: AtomicU64::new(start.0),
+        }
+    }
+
+    pub fn advance(&self, seconds: u64) {
+        self.now.fetch_add(seconds, Ordering::SeqCst);
+    }
+
+    // Time never goes back, setting an earlier time leaves the clock where it was
+    pub fn advance_to(&self, time: Timestamp) {
+        self.now.fetch_max(time.0, Ordering::SeqCst);
+    }
+}
+
+impl Clock for SimulatedClock {
+    fn now(&self) -> Timestamp {
+        Timestamp(self.now.load(Ordering::SeqCst))
+    }
+}
+
+// 810 seconds are "13m 30s", an hour or more is "1h 05m"
+pub fn format_duration(seconds: u64) -> String {
+    if seconds >= 3600 {
+        format!("{}h {:02}m", seconds / 3600, seconds % 3600 / 60)
+    } else if seconds >= 60 {
+        format!("{}m {:02}s", seconds / 60, seconds % 60)
+    } else {
+        format!("{}s", seconds)
+    }
+}
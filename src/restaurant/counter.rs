@@ -0,0 +1,110 @@
+// The counter
+// Where the orders are taken and paid. The Counter has the Inventory of the kitchen, so the waiter
+// knows which pizzas we can still make, the Promotions that come off the bills and the loyalty
+// points the customers earned. The dining room doesn't need any of it to seat people, and a delivery
+// or an online order never sees a table but still goes through the counter.
+//
+// The counter of the learner keeps its files in the config directory, next to orders.jsonl:
+// promotions.json, only when they want promotions of their own, and loyalty.json with the points,
+// saved every time a customer earns or redeems some.
+
+use std::fs;
+use std::io::{self, ErrorKind};
+use std::path::{Path, PathBuf};
+
+use crate::restaurant::inventory::Inventory;
+use crate::restaurant::promotions::{LoyaltyAccounts, Promotions};
+
+pub struct Counter {
+    inventory: Inventory,
+    promotions: Promotions,
+    loyalty: LoyaltyAccounts,
+    // Where the points are saved, a counter made with new only keeps them in memory
+    loyalty_path: Option<PathBuf>,
+}
+
+impl Counter {
+    // There are no promotions until with_promotions declares them, and nobody has points yet
+    pub fn new(inventory: Inventory) -> Counter {
+        Counter {
+            inventory,
+            promotions: Promotions::default(),
+            loyalty: LoyaltyAccounts::default(),
+            loyalty_path: None,
+        }
+    }
+
+    // The standard inventory, without promotions
+    pub fn standard() -> Counter {
+        Counter::new(Inventory::standard())
+    }
+
+    // The counter of the config directory `dir`: the promotions of its promotions.json, or the ones
+    // that come with the tutorial when there is none, and the points of loyalty.json
+    pub fn open(dir: &Path, inventory: Inventory) -> io::Result<Counter> {
+        let promotions = Promotions::open(dir)?;
+        let loyalty_path = dir.join("loyalty.json");
+        let loyalty = match fs::read_to_string(&loyalty_path) {
+            Ok(content) => serde_json::from_str(&content).map_err(|err| io::Error::new(ErrorKind::InvalidData, err))?,
+            Err(err) if err.kind() == ErrorKind::NotFound => LoyaltyAccounts::default(),
+            Err(err) => return Err(err),
+        };
+        Ok(Counter {
+            inventory,
+            promotions,
+            loyalty,
+            loyalty_path: Some(loyalty_path),
+        })
+    }
+
+    pub fn with_promotions(mut self, promotions: Promotions) -> Counter {
+        self.promotions = promotions;
+        self
+    }
+
+    pub fn with_loyalty(mut self, loyalty: LoyaltyAccounts) -> Counter {
+        self.loyalty = loyalty;
+        self
+    }
+
+    pub fn inventory(&self) -> &Inventory {
+        &self.inventory
+    }
+
+    pub fn inventory_mut(&mut self) -> &mut Inventory {
+        &mut self.inventory
+    }
+
+    pub fn promotions(&self) -> &Promotions {
+        &self.promotions
+    }
+
+    pub fn loyalty(&self) -> &LoyaltyAccounts {
+        &self.loyalty
+    }
+
+    // Both return the new balance of the customer
+    pub fn earn(&mut self, customer: &str, points: u32) -> io::Result<u32> {
+        let balance = self.loyalty.earn(customer, points);
+        self.save_loyalty()?;
+        Ok(balance)
+    }
+
+    pub fn redeem(&mut self, customer: &str, points: u32) -> io::Result<u32> {
+        let balance = self.loyalty.redeem(customer, points)?;
+        self.save_loyalty()?;
+        Ok(balance)
+    }
+
+    fn save_loyalty(&self) -> io::Result<()> {
+        let path = match &self.loyalty_path {
+            Some(path) => path,
+            None => return Ok(()),
+        };
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let content = serde_json::to_string_pretty(&self.loyalty).map_err(|err| io::Error::new(ErrorKind::InvalidData, err))?;
+        fs::write(path, content)
+    }
+}
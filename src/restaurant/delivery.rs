@@ -0,0 +1,369 @@
+// Delivery
+// A delivery order doesn't sit at a table, it goes to an address. The DeliveryMap says in which zone
+// each street is and how long a courier takes to get there. The kitchen cooks delivery orders like
+// any other, on the same board of the head chef as the orders of the tables, so a delivery ordered
+// during the rush waits for the pizzas before it. The board says when it will be ready, and a pool of
+// couriers takes them: a courier leaving for a zone waits a little for the other orders going to the
+// same zone, and takes up to `max_batch` of them in one trip, one stop after the other.
+//
+// Like the kitchen, nobody sleeps: the plan of the couriers is computed from the timestamps, so the
+// ETA of an order is the same every time, and run_until moves the plan forward to a given time.
+
+use std::error::Error;
+use std::fmt;
+use std::io::{self, Write};
+
+use crate::restaurant::clock::{format_duration, Timestamp};
+use crate::restaurant::kitchen::{Board, KitchenConfig};
+use crate::restaurant::order::{Order, OrderError};
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Address {
+    pub street: String,
+    pub number: u32,
+}
+
+impl Address {
+    pub fn new(street: &str, number: u32) -> Address {
+        Address {
+            street: String::from(street),
+            number,
+        }
+    }
+}
+
+// "12 Main Street"
+impl fmt::Display for Address {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {}", self.number, self.street)
+    }
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct Zone {
+    pub name: String,
+    pub streets: Vec<String>,
+    // From the restaurant to the zone, the way back takes the same
+    pub travel_seconds: u64,
+}
+
+impl Zone {
+    pub fn new(name: &str, streets: &[&str], travel_seconds: u64) -> Zone {
+        Zone {
+            name: String::from(name),
+            streets: streets.iter().map(|street| String::from(*street)).collect(),
+            travel_seconds,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum DeliveryError {
+    OutsideDeliveryArea(Address),
+    Order(OrderError),
+}
+
+impl fmt::Display for DeliveryError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DeliveryError::OutsideDeliveryArea(address) => write!(f, "we don't deliver to {}", address),
+            DeliveryError::Order(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+impl Error for DeliveryError {}
+
+impl From<OrderError> for DeliveryError {
+    fn from(err: OrderError) -> DeliveryError {
+        DeliveryError::Order(err)
+    }
+}
+
+impl From<DeliveryError> for io::Error {
+    fn from(err: DeliveryError) -> io::Error {
+        io::Error::other(err)
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct DeliveryMap {
+    zones: Vec<Zone>,
+}
+
+impl DeliveryMap {
+    pub fn new(zones: Vec<Zone>) -> DeliveryMap {
+        DeliveryMap { zones }
+    }
+
+    // The streets around the restaurant, 6, 12 and 20 minutes away
+    pub fn standard() -> DeliveryMap {
+        DeliveryMap::new(vec![
+            Zone::new("Center", &["Main Street", "Market Street", "Church Street"], 6 * 60),
+            Zone::new("North", &["Hill Road", "Park Avenue"], 12 * 60),
+            Zone::new("Riverside", &["River Road", "Mill Lane", "Harbor Street"], 20 * 60),
+        ])
+    }
+
+    pub fn zones(&self) -> &[Zone] {
+        &self.zones
+    }
+
+    // Street names are compared ignoring case
+    pub fn zone_of(&self, address: &Address) -> Result<&Zone, DeliveryError> {
+        self.zones
+            .iter()
+            .find(|zone| zone.streets.iter().any(|street| street.eq_ignore_ascii_case(&address.street)))
+            .ok_or_else(|| DeliveryError::OutsideDeliveryArea(address.clone()))
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct DeliveryConfig {
+    pub couriers: usize,
+    // How long a courier waits after an order is ready for other orders to the same zone
+    pub batch_window_seconds: u64,
+    pub max_batch: usize,
+    // From one door to the next one in the same zone
+    pub stop_seconds: u64,
+}
+
+impl Default for DeliveryConfig {
+    fn default() -> DeliveryConfig {
+        DeliveryConfig {
+            couriers: 2,
+            batch_window_seconds: 5 * 60,
+            max_batch: 3,
+            stop_seconds: 2 * 60,
+        }
+    }
+}
+
+// What the customer is told when they order
+#[derive(Clone, PartialEq, Debug)]
+pub struct Quote {
+    // The number of the delivery, the first one is 1
+    pub id: u64,
+    pub zone: String,
+    pub ready_at: Timestamp,
+    pub eta: Timestamp,
+}
+
+#[derive(Clone, Debug)]
+pub struct Delivery {
+    pub id: u64,
+    pub order: Order,
+    pub address: Address,
+    pub zone: String,
+    // The first courier is 1
+    pub courier: usize,
+    pub ready_at: Timestamp,
+    pub departs_at: Timestamp,
+    pub delivered_at: Timestamp,
+}
+
+// A courier going to a zone and back, with the deliveries in the order of the stops
+#[derive(Clone, PartialEq, Debug)]
+pub struct Trip {
+    pub courier: usize,
+    pub zone: String,
+    pub departs_at: Timestamp,
+    pub back_at: Timestamp,
+    pub deliveries: Vec<u64>,
+}
+
+struct Pending {
+    id: u64,
+    order: Order,
+    address: Address,
+    zone: Zone,
+    ready_at: Timestamp,
+}
+
+// A trip that was planned, and when each of its deliveries arrives
+struct PlannedTrip {
+    trip: Trip,
+    arrivals: Vec<Timestamp>,
+}
+
+pub struct DeliveryService {
+    map: DeliveryMap,
+    config: DeliveryConfig,
+    kitchen: KitchenConfig,
+    // When each courier is back at the restaurant
+    couriers: Vec<Timestamp>,
+    pending: Vec<Pending>,
+    on_the_road: Vec<Delivery>,
+    trips: Vec<Trip>,
+    next_id: u64,
+}
+
+impl DeliveryService {
+    // There is always at least one courier
+    pub fn new(map: DeliveryMap, config: DeliveryConfig, kitchen: KitchenConfig) -> DeliveryService {
+        DeliveryService {
+            couriers: vec![Timestamp(0); config.couriers.max(1)],
+            map,
+            config,
+            kitchen,
+            pending: Vec::new(),
+            on_the_road: Vec::new(),
+            trips: Vec::new(),
+            next_id: 1,
+        }
+    }
+
+    pub fn map(&self) -> &DeliveryMap {
+        &self.map
+    }
+
+    // The trips the couriers already left for
+    pub fn trips(&self) -> &[Trip] {
+        &self.trips
+    }
+
+    // Sends an Ordered order to the kitchen, to be delivered to `address` when it's ready. `board` is
+    // the board of the kitchen that cooks it, the one the tables send their orders to
+    pub fn order(&mut self, mut order: Order, address: Address, board: &mut Board, at: Timestamp) -> Result<Quote, DeliveryError> {
+        let zone = self.map.zone_of(&address)?.clone();
+        order.send_to_kitchen(at)?;
+        let assignment = board.assign(at, self.kitchen.cooking_time(&order));
+
+        let id = self.next_id;
+        self.next_id += 1;
+        self.pending.push(Pending {
+            id,
+            order,
+            address,
+            zone: zone.clone(),
+            ready_at: assignment.ready_at,
+        });
+        Ok(Quote {
+            id,
+            zone: zone.name,
+            ready_at: assignment.ready_at,
+            eta: self.estimate(id).expect("the order was just added"),
+        })
+    }
+
+    // When the delivery should arrive, with the orders we know of now. A later order for the same
+    // zone can join the trip and make it a stop longer
+    pub fn estimate(&self, id: u64) -> Option<Timestamp> {
+        if let Some(delivery) = self.on_the_road.iter().find(|delivery| delivery.id == id) {
+            return Some(delivery.delivered_at);
+        }
+        self.plan().into_iter().find_map(|planned| {
+            let stop = planned.trip.deliveries.iter().position(|delivery| *delivery == id)?;
+            Some(planned.arrivals[stop])
+        })
+    }
+
+    // The trips of the pending orders, the order that is ready first leaves first, with the courier
+    // that is back first
+    fn plan(&self) -> Vec<PlannedTrip> {
+        let mut couriers = self.couriers.clone();
+        let mut waiting: Vec<&Pending> = self.pending.iter().collect();
+        waiting.sort_by_key(|pending| (pending.ready_at, pending.id));
+
+        let mut trips = Vec::new();
+        while let Some(first) = waiting.first() {
+            let courier = (0..couriers.len()).min_by_key(|&courier| couriers[courier]).unwrap_or(0);
+            let start = first.ready_at.max(couriers[courier]);
+            let zone = first.zone.clone();
+            let batch: Vec<&Pending> = waiting
+                .iter()
+                .filter(|pending| pending.zone.name == zone.name)
+                .filter(|pending| pending.ready_at <= start.plus(self.config.batch_window_seconds))
+                .take(self.config.max_batch.max(1))
+                .copied()
+                .collect();
+
+            let departs_at = batch.iter().map(|pending| pending.ready_at).max().unwrap_or(start).max(start);
+            let arrivals: Vec<Timestamp> = (0..batch.len() as u64)
+                .map(|stop| departs_at.plus(zone.travel_seconds + stop * self.config.stop_seconds))
+                .collect();
+            let back_at = arrivals.last().copied().unwrap_or(departs_at).plus(zone.travel_seconds);
+            couriers[courier] = back_at;
+
+            let ids: Vec<u64> = batch.iter().map(|pending| pending.id).collect();
+            waiting.retain(|pending| !ids.contains(&pending.id));
+            trips.push(PlannedTrip {
+                trip: Trip {
+                    courier: courier + 1,
+                    zone: zone.name,
+                    departs_at,
+                    back_at,
+                    deliveries: ids,
+                },
+                arrivals,
+            });
+        }
+        trips
+    }
+
+    // The couriers leave for every trip planned to start by `now`, and the deliveries that arrived by
+    // `now` are returned, served and paid, in the order they arrived. The plan goes by when the orders
+    // are ready, a trip waiting for a late order can leave after the next one, so every trip is checked
+    pub fn run_until(&mut self, now: Timestamp) -> Result<Vec<Delivery>, DeliveryError> {
+        for planned in self.plan() {
+            if planned.trip.departs_at > now {
+                continue;
+            }
+            for (id, delivered_at) in planned.trip.deliveries.iter().zip(&planned.arrivals) {
+                let index = self
+                    .pending
+                    .iter()
+                    .position(|pending| pending.id == *id)
+                    .expect("a planned delivery is pending");
+                let pending = self.pending.remove(index);
+                let mut order = pending.order;
+                order.mark_ready(pending.ready_at)?;
+                self.on_the_road.push(Delivery {
+                    id: pending.id,
+                    order,
+                    address: pending.address,
+                    zone: pending.zone.name,
+                    courier: planned.trip.courier,
+                    ready_at: pending.ready_at,
+                    departs_at: planned.trip.departs_at,
+                    delivered_at: *delivered_at,
+                });
+            }
+            self.couriers[planned.trip.courier - 1] = planned.trip.back_at;
+            self.trips.push(planned.trip);
+        }
+
+        let mut delivered = Vec::new();
+        let mut index = 0;
+        while index < self.on_the_road.len() {
+            if self.on_the_road[index].delivered_at <= now {
+                let mut delivery = self.on_the_road.remove(index);
+                // Delivery orders are paid to the courier at the door
+                delivery.order.serve(delivery.delivered_at)?;
+                delivery.order.pay(delivery.delivered_at)?;
+                delivered.push(delivery);
+            } else {
+                index += 1;
+            }
+        }
+        delivered.sort_by_key(|delivery| (delivery.delivered_at, delivery.id));
+        Ok(delivered)
+    }
+
+    pub fn print_trips(&self, out: &mut dyn Write) -> io::Result<()> {
+        writeln!(out, "{:>7}  {:<10} {:<19}  {:>8}  Deliveries", "Courier", "Zone", "Departs", "Away")?;
+        for trip in &self.trips {
+            let deliveries: Vec<String> = trip.deliveries.iter().map(|id| id.to_string()).collect();
+            writeln!(
+                out,
+                "{:>7}  {:<10} {:<19}  {:>8}  {}",
+                trip.courier,
+                trip.zone,
+                trip.departs_at.to_string(),
+                format_duration(trip.back_at.since(trip.departs_at)),
+                deliveries.join(", ")
+            )?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,652 @@
+// Front of house
+// A full screen view of the restaurant for the staff: the tables, the open orders and the pizzas
+// waiting to be served, with keys to seat parties, build their pizzas, serve them and charge them,
+// with the coupon they give us.
+// Everything the staff does goes through the same help_customer functions the Class 21 script
+// uses, this screen only decides when to call them, that's the point of splitting a program in
+// modules. The clock is simulated, `t` moves it 5 minutes forward so the kitchen gets the pizzas ready.
+// Every order that is paid or cancelled goes to the order history, like the orders of the script.
+//
+// The times on the screen are the ones of the clocks of the restaurant, the utc_offset of its
+// promotions, so the staff sees the same hour the happy hour goes by.
+//
+// FrontOfHouse is only state: handle reacts to a key, render returns the lines of the screen. The
+// terminal part, run, reads the keys with crossterm and draws those lines, so the tests can drive
+// the screen without a terminal.
+
+use std::io::{self, Write};
+
+use crossterm::cursor::{Hide, MoveTo, Show};
+use crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
+use crossterm::style::Print;
+use crossterm::terminal::{self, Clear, ClearType, EnterAlternateScreen, LeaveAlternateScreen};
+use crossterm::{execute, queue};
+
+use crate::restaurant::billing::{Bill, Money, Rate};
+use crate::restaurant::clock::{Clock, SimulatedClock, Timestamp, UtcOffset};
+use crate::restaurant::counter::Counter;
+use crate::restaurant::kitchen::{Board, KitchenConfig};
+use crate::restaurant::order::{Order, OrderStatus};
+use crate::restaurant::pizza_order::dietary::DietaryNeeds;
+use crate::restaurant::pizza_order::help_customer;
+use crate::restaurant::pizza_order::menu::{Menu, Size};
+use crate::restaurant::pizza_order::PizzaBuilder;
+use crate::restaurant::seating::{Party, Restaurant};
+use crate::restaurant::store::OrderStore;
+
+// How many of the last messages the screen shows
+const MESSAGES: usize = 6;
+// What `t` moves the clock
+const TICK_SECONDS: u64 = 5 * 60;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Key {
+    Char(char),
+    Enter,
+    Esc,
+    Backspace,
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+// An order on the screen, the number is what the staff calls it
+struct Ticket {
+    number: u32,
+    order: Order,
+    ready_at: Option<Timestamp>,
+    coupon: Option<String>,
+}
+
+// The pizza being built, the dough and the cheese are positions in the lists of the menu
+struct Draft {
+    size: Size,
+    dough: usize,
+    cheese: usize,
+    toppings: Vec<String>,
+    cursor: usize,
+}
+
+enum Mode {
+    Floor,
+    // What was typed so far, "Ana 4"
+    Seating(String),
+    Coupon(String),
+    Building(Draft),
+}
+
+pub struct FrontOfHouse {
+    restaurant: Restaurant,
+    counter: Counter,
+    // Where the finished orders go, nowhere until with_store gives one
+    store: Option<OrderStore>,
+    menu: Menu,
+    kitchen: KitchenConfig,
+    board: Board,
+    clock: SimulatedClock,
+    tickets: Vec<Ticket>,
+    selected: usize,
+    mode: Mode,
+    messages: Vec<String>,
+    next_ticket: u32,
+}
+
+impl FrontOfHouse {
+    pub fn new(restaurant: Restaurant, counter: Counter, menu: Menu, kitchen: KitchenConfig, start: Timestamp) -> FrontOfHouse {
+        FrontOfHouse {
+            restaurant,
+            counter,
+            store: None,
+            menu,
+            board: Board::new(kitchen.cooks),
+            kitchen,
+            clock: SimulatedClock::starting_at(start),
+            tickets: Vec::new(),
+            selected: 0,
+            mode: Mode::Floor,
+            messages: vec![String::from("Press s to seat the first party")],
+            next_ticket: 1,
+        }
+    }
+
+    pub fn with_store(mut self, store: OrderStore) -> FrontOfHouse {
+        self.store = Some(store);
+        self
+    }
+
+    pub fn restaurant(&self) -> &Restaurant {
+        &self.restaurant
+    }
+
+    pub fn counter(&self) -> &Counter {
+        &self.counter
+    }
+
+    pub fn now(&self) -> Timestamp {
+        self.clock.now()
+    }
+
+    fn utc_offset(&self) -> UtcOffset {
+        self.counter.promotions().utc_offset
+    }
+
+    // The orders on the screen, in the order they were opened
+    pub fn orders(&self) -> Vec<&Order> {
+        self.tickets.iter().map(|ticket| &ticket.order).collect()
+    }
+
+    // Every line the help_customer functions wrote becomes a message
+    fn say(&mut self, out: Vec<u8>) {
+        let out = String::from_utf8_lossy(&out).into_owned();
+        self.messages.extend(out.lines().map(String::from));
+    }
+
+    fn open_ticket(&mut self, order: Order) {
+        let number = self.next_ticket;
+        self.next_ticket += 1;
+        self.tickets.push(Ticket {
+            number,
+            order,
+            ready_at: None,
+            coupon: None,
+        });
+    }
+
+    // Returns false when the staff leaves the screen
+    pub fn handle(&mut self, key: Key) -> bool {
+        let result = match &self.mode {
+            Mode::Floor => return self.handle_floor(key),
+            Mode::Seating(_) => self.handle_seating(key),
+            Mode::Coupon(_) => self.handle_coupon(key),
+            Mode::Building(_) => self.handle_building(key),
+        };
+        if let Err(err) = result {
+            self.messages.push(format!("error: {}", err));
+        }
+        true
+    }
+
+    fn handle_floor(&mut self, key: Key) -> bool {
+        let result = match key {
+            Key::Char('q') | Key::Esc => return false,
+            Key::Up => {
+                self.selected = self.selected.saturating_sub(1);
+                Ok(())
+            }
+            Key::Down => {
+                self.selected = (self.selected + 1).min(self.tickets.len().saturating_sub(1));
+                Ok(())
+            }
+            Key::Char('s') => {
+                self.mode = Mode::Seating(String::new());
+                Ok(())
+            }
+            Key::Char('o') => self.start_pizza(),
+            Key::Char('c') if !self.tickets.is_empty() => {
+                self.mode = Mode::Coupon(String::new());
+                Ok(())
+            }
+            Key::Char('t') => self.tick(),
+            Key::Char('v') => self.serve(),
+            Key::Char('p') => self.charge(),
+            Key::Char('x') => self.leave(),
+            _ => Ok(()),
+        };
+        if let Err(err) = result {
+            self.messages.push(format!("error: {}", err));
+        }
+        true
+    }
+
+    fn handle_seating(&mut self, key: Key) -> io::Result<()> {
+        let typed = match &mut self.mode {
+            Mode::Seating(typed) => typed,
+            _ => return Ok(()),
+        };
+        match key {
+            Key::Char(c) => typed.push(c),
+            Key::Backspace => {
+                typed.pop();
+            }
+            Key::Esc => self.mode = Mode::Floor,
+            Key::Enter => {
+                let party = parse_party(typed);
+                self.mode = Mode::Floor;
+                match party {
+                    Some(party) => self.seat(party)?,
+                    None => self.messages.push(String::from("Type the name and the size of the party, like: Ana 4")),
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    // The coupon is checked when the party pays, an empty one takes it back
+    fn handle_coupon(&mut self, key: Key) -> io::Result<()> {
+        let typed = match &mut self.mode {
+            Mode::Coupon(typed) => typed,
+            _ => return Ok(()),
+        };
+        match key {
+            Key::Char(c) => typed.push(c),
+            Key::Backspace => {
+                typed.pop();
+            }
+            Key::Esc => self.mode = Mode::Floor,
+            Key::Enter => {
+                let code = typed.trim().to_string();
+                self.mode = Mode::Floor;
+                if let Some(ticket) = self.tickets.get_mut(self.selected) {
+                    let message = if code.is_empty() {
+                        format!("Ticket {} has no coupon", ticket.number)
+                    } else {
+                        format!("Ticket {} pays with the coupon {}", ticket.number, code)
+                    };
+                    ticket.coupon = if code.is_empty() { None } else { Some(code) };
+                    self.messages.push(message);
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn seat(&mut self, party: Party) -> io::Result<()> {
+        let mut out = Vec::new();
+        let seated = help_customer::seat_at_table(&mut self.restaurant, &party, &self.clock, &mut out)?;
+        self.say(out);
+        if let Some(order) = seated {
+            self.open_ticket(order);
+            self.selected = self.tickets.len() - 1;
+        }
+        Ok(())
+    }
+
+    fn selected_ticket(&mut self) -> Option<&mut Ticket> {
+        self.tickets.get_mut(self.selected)
+    }
+
+    fn start_pizza(&mut self) -> io::Result<()> {
+        let now = self.clock.now();
+        let ticket = match self.selected_ticket() {
+            Some(ticket) => ticket,
+            None => return Ok(()),
+        };
+        match ticket.order.status() {
+            OrderStatus::Seated => {}
+            // The party is still at the table, they can try another pizza. The refused order goes to
+            // the history first, like the ones of the Class 21 script
+            OrderStatus::Cancelled => {
+                self.record(self.selected)?;
+                let ticket = &mut self.tickets[self.selected];
+                let table = ticket.order.table;
+                ticket.order = Order::seat(&ticket.order.customer, now);
+                ticket.order.table = table;
+            }
+            status => {
+                let message = format!("Ticket {} is {}, only seated parties can order", ticket.number, status);
+                self.messages.push(message);
+                return Ok(());
+            }
+        }
+        self.mode = Mode::Building(Draft {
+            size: Size::Medium,
+            dough: 0,
+            cheese: 0,
+            toppings: Vec::new(),
+            cursor: 0,
+        });
+        Ok(())
+    }
+
+    fn handle_building(&mut self, key: Key) -> io::Result<()> {
+        let menu = &self.menu;
+        let draft = match &mut self.mode {
+            Mode::Building(draft) => draft,
+            _ => return Ok(()),
+        };
+        let sizes: Vec<Size> = menu.sizes.keys().copied().collect();
+        let size = sizes.iter().position(|size| *size == draft.size).unwrap_or(0);
+        match key {
+            Key::Left if !sizes.is_empty() => draft.size = sizes[(size + sizes.len() - 1) % sizes.len()],
+            Key::Right if !sizes.is_empty() => draft.size = sizes[(size + 1) % sizes.len()],
+            Key::Char('d') => draft.dough = (draft.dough + 1) % menu.doughs.len().max(1),
+            Key::Char('c') => draft.cheese = (draft.cheese + 1) % menu.cheeses.len().max(1),
+            Key::Up => draft.cursor = draft.cursor.saturating_sub(1),
+            Key::Down => draft.cursor = (draft.cursor + 1).min(menu.toppings.len().saturating_sub(1)),
+            Key::Char(' ') => {
+                if let Some(topping) = menu.toppings.get(draft.cursor) {
+                    match draft.toppings.iter().position(|name| *name == topping.name) {
+                        Some(index) => {
+                            draft.toppings.remove(index);
+                        }
+                        None => draft.toppings.push(topping.name.clone()),
+                    }
+                }
+            }
+            Key::Esc => self.mode = Mode::Floor,
+            Key::Enter => return self.place(),
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn builder<'a>(menu: &'a Menu, draft: &Draft) -> PizzaBuilder<'a> {
+        let mut builder = PizzaBuilder::new(menu).size(draft.size);
+        if let Some(dough) = menu.doughs.get(draft.dough) {
+            builder = builder.dough(*dough);
+        }
+        if let Some(cheese) = menu.cheeses.get(draft.cheese) {
+            builder = builder.cheese(*cheese);
+        }
+        for topping in &draft.toppings {
+            builder = builder.topping(topping);
+        }
+        builder
+    }
+
+    // Orders the pizza of the draft, and sends it to the kitchen when the order was placed
+    fn place(&mut self) -> io::Result<()> {
+        let draft = match std::mem::replace(&mut self.mode, Mode::Floor) {
+            Mode::Building(draft) => draft,
+            _ => return Ok(()),
+        };
+        let now = self.clock.now();
+        let ticket = match self.tickets.get_mut(self.selected) {
+            Some(ticket) => ticket,
+            None => return Ok(()),
+        };
+        let needs = ticket
+            .order
+            .table
+            .and_then(|table| self.restaurant.occupant(table))
+            .map(|party| party.needs.clone())
+            .unwrap_or_else(DietaryNeeds::default);
+
+        let offset = self.counter.promotions().utc_offset;
+        let mut out = Vec::new();
+        let choice = FrontOfHouse::builder(&self.menu, &draft);
+        help_customer::order_pizza(&mut ticket.order, choice, &needs, self.counter.inventory_mut(), &self.clock, &mut out)?;
+        if ticket.order.status() == OrderStatus::Ordered {
+            ticket.order.send_to_kitchen(now)?;
+            let assignment = self.board.assign(now, self.kitchen.cooking_time(&ticket.order));
+            ticket.ready_at = Some(assignment.ready_at);
+            writeln!(out, "Ticket {} goes to cook {}, ready at {}", ticket.number, assignment.cook, time_of_day(assignment.ready_at, offset))?;
+        }
+        self.say(out);
+        Ok(())
+    }
+
+    // The clock moves on, and the orders the kitchen finished are ready to be served
+    fn tick(&mut self) -> io::Result<()> {
+        self.clock.advance(TICK_SECONDS);
+        let now = self.clock.now();
+        let mut out = Vec::new();
+        for ticket in &mut self.tickets {
+            match ticket.ready_at {
+                Some(ready_at) if ready_at <= now && ticket.order.status() == OrderStatus::InKitchen => {
+                    ticket.order.mark_ready(ready_at)?;
+                    writeln!(out, "Ticket {} for {} is ready to be served", ticket.number, ticket.order.customer)?;
+                }
+                _ => {}
+            }
+        }
+        self.say(out);
+        Ok(())
+    }
+
+    fn serve(&mut self) -> io::Result<()> {
+        let clock = &self.clock;
+        let ticket = match self.tickets.get_mut(self.selected) {
+            Some(ticket) => ticket,
+            None => return Ok(()),
+        };
+        if ticket.order.status() != OrderStatus::Ready {
+            let message = format!("Ticket {} is {}, only ready orders can be served", ticket.number, ticket.order.status());
+            self.messages.push(message);
+            return Ok(());
+        }
+        let mut out = Vec::new();
+        help_customer::serve_customer(&mut ticket.order, clock, &mut out)?;
+        self.say(out);
+        Ok(())
+    }
+
+    // The party pays and leaves, and whoever was waiting for the table gets it. The ticket stays on
+    // the screen until the order is in the history, a paid ticket that couldn't be saved is saved
+    // again with the next `p`
+    fn charge(&mut self) -> io::Result<()> {
+        match self.tickets.get(self.selected) {
+            Some(ticket) if matches!(ticket.order.status(), OrderStatus::Served | OrderStatus::Paid) => {}
+            Some(ticket) => {
+                let message = format!("Ticket {} is {}, only served orders can pay", ticket.number, ticket.order.status());
+                self.messages.push(message);
+                return Ok(());
+            }
+            None => return Ok(()),
+        }
+        let ticket = &mut self.tickets[self.selected];
+        if ticket.order.status() == OrderStatus::Served {
+            let mut out = Vec::new();
+            let bill = Bill::for_order(&ticket.order, &self.menu, Rate::basis_points(825)).with_tip(Rate::percent(15));
+            let bill = help_customer::apply_promotions(&mut self.counter, &ticket.order, bill, ticket.coupon.as_deref(), &self.clock, &mut out)?;
+            help_customer::charge_customer(&mut self.counter, &mut ticket.order, &bill, &self.clock, &mut out)?;
+            writeln!(out, "Ticket {} paid {}", ticket.number, bill.total())?;
+            self.say(out);
+        }
+        self.record(self.selected)?;
+        let ticket = self.tickets.remove(self.selected);
+        self.clear_table(ticket.order, Vec::new())
+    }
+
+    // A party that didn't order, or whose pizza we couldn't make, leaves without paying
+    fn leave(&mut self) -> io::Result<()> {
+        match self.tickets.get(self.selected) {
+            Some(ticket) if matches!(ticket.order.status(), OrderStatus::Seated | OrderStatus::Cancelled) => {}
+            Some(ticket) => {
+                let message = format!("Ticket {} is {}, the party can't leave now", ticket.number, ticket.order.status());
+                self.messages.push(message);
+                return Ok(());
+            }
+            None => return Ok(()),
+        }
+        let ticket = &mut self.tickets[self.selected];
+        if ticket.order.status() == OrderStatus::Seated {
+            ticket.order.cancel(self.clock.now())?;
+        }
+        self.record(self.selected)?;
+        let ticket = self.tickets.remove(self.selected);
+        self.clear_table(ticket.order, Vec::new())
+    }
+
+    // Saves the order of the ticket at `index` in the history, when there is one
+    fn record(&mut self, index: usize) -> io::Result<()> {
+        if let Some(store) = &mut self.store {
+            store.insert(&self.tickets[index].order)?;
+        }
+        Ok(())
+    }
+
+    fn clear_table(&mut self, order: Order, mut out: Vec<u8>) -> io::Result<()> {
+        let seated = help_customer::clear_table(&mut self.restaurant, &order, &self.clock, &mut out)?;
+        self.say(out);
+        for order in seated {
+            self.open_ticket(order);
+        }
+        self.selected = self.selected.min(self.tickets.len().saturating_sub(1));
+        Ok(())
+    }
+
+    pub fn render(&self) -> Vec<String> {
+        let now = self.clock.now();
+        let mut lines = vec![format!("Class 21 pizzeria, {} (simulated)", now.local(self.utc_offset())), String::new()];
+
+        let mut floor = Vec::new();
+        // Writing to a Vec<u8> can't fail
+        let _ = self.restaurant.print(now, &mut floor);
+        lines.extend(String::from_utf8_lossy(&floor).lines().map(String::from));
+        lines.push(String::new());
+
+        match &self.mode {
+            Mode::Building(draft) => lines.extend(self.render_draft(draft)),
+            _ => lines.extend(self.render_orders(now)),
+        }
+
+        lines.push(String::new());
+        let first = self.messages.len().saturating_sub(MESSAGES);
+        lines.extend(self.messages[first..].iter().cloned());
+        lines.push(String::new());
+        lines.push(match &self.mode {
+            Mode::Floor => String::from("s seat  o order  t +5 min  v serve  c coupon  p pay  x leave  Up/Down select  q quit"),
+            Mode::Seating(typed) => format!("Party (name and size): {}_   Enter seat  Esc back", typed),
+            Mode::Coupon(typed) => format!("Coupon code: {}_   Enter keep  Esc back", typed),
+            Mode::Building(_) => String::from("Left/Right size  d dough  c cheese  Up/Down and Space toppings  Enter order  Esc back"),
+        });
+        lines
+    }
+
+    fn render_orders(&self, now: Timestamp) -> Vec<String> {
+        let mut lines = vec![format!("  {:>6}  {:<12} {:>5}  {:<10} {:<8}  Pizzas", "Ticket", "Customer", "Table", "Status", "Ready")];
+        if self.tickets.is_empty() {
+            lines.push(String::from("  No open orders"));
+        }
+        for (index, ticket) in self.tickets.iter().enumerate() {
+            let marker = if index == self.selected { ">" } else { " " };
+            let table = ticket.order.table.map(|table| table.to_string()).unwrap_or_default();
+            let ready = match ticket.ready_at {
+                Some(ready_at) if ticket.order.status() == OrderStatus::InKitchen => time_of_day(ready_at, self.utc_offset()),
+                _ => String::new(),
+            };
+            let pizzas: Vec<String> = ticket.order.pizzas.iter().map(|pizza| pizza.to_string()).collect();
+            lines.push(format!(
+                "{} {:>6}  {:<12} {:>5}  {:<10} {:<8}  {}",
+                marker,
+                ticket.number,
+                ticket.order.customer,
+                table,
+                ticket.order.status().to_string(),
+                ready,
+                pizzas.join("; ")
+            ));
+        }
+
+        let waiting: Vec<String> = self
+            .tickets
+            .iter()
+            .filter(|ticket| ticket.order.status() == OrderStatus::Ready)
+            .map(|ticket| {
+                let waited = ticket.ready_at.map(|ready_at| now.since(ready_at)).unwrap_or(0);
+                format!("ticket {} for {} ({} min)", ticket.number, ticket.order.customer, waited / 60)
+            })
+            .collect();
+        lines.push(String::new());
+        if waiting.is_empty() {
+            lines.push(String::from("No pizzas waiting to be served"));
+        } else {
+            lines.push(format!("Waiting to be served: {}", waiting.join(", ")));
+        }
+        lines
+    }
+
+    fn render_draft(&self, draft: &Draft) -> Vec<String> {
+        let customer = self
+            .tickets
+            .get(self.selected)
+            .map(|ticket| ticket.order.customer.as_str())
+            .unwrap_or_default();
+        let dough = self.menu.doughs.get(draft.dough).map(|dough| dough.to_string()).unwrap_or_default();
+        let cheese = self.menu.cheeses.get(draft.cheese).map(|cheese| cheese.to_string()).unwrap_or_default();
+        let mut lines = vec![
+            format!("Pizza for {}", customer),
+            format!("Size: < {} >   Dough: {}   Cheese: {}", draft.size, dough, cheese),
+        ];
+        for (index, topping) in self.menu.toppings.iter().enumerate() {
+            let marker = if index == draft.cursor { ">" } else { " " };
+            let check = if draft.toppings.contains(&topping.name) { "x" } else { " " };
+            let price = Money::cents(topping.price_cents as i64);
+            lines.push(format!("{} [{}] {:<14} {:>6}", marker, check, topping.name, price));
+        }
+        lines.push(match FrontOfHouse::builder(&self.menu, draft).build() {
+            Ok(pizza) => format!("Price: {}", self.menu.price_of(&pizza)),
+            Err(err) => format!("Can't be made: {}", err),
+        });
+        lines
+    }
+}
+
+// "12:30:00" on the clocks `offset` from UTC
+fn time_of_day(at: Timestamp, offset: UtcOffset) -> String {
+    let time = at.local(offset).to_string();
+    time.split(' ').nth(1).map(String::from).unwrap_or(time)
+}
+
+// "Ana 4" is Ana, a party of 4, "Ana" alone is a party of 1
+fn parse_party(typed: &str) -> Option<Party> {
+    let mut words: Vec<&str> = typed.split_whitespace().collect();
+    let size = match words.last().map(|word| word.parse::<u32>()) {
+        Some(Ok(size)) => {
+            words.pop();
+            size
+        }
+        _ => 1,
+    };
+    if words.is_empty() || size == 0 {
+        return None;
+    }
+    Some(Party::new(&words.join(" "), size))
+}
+
+// Puts the terminal back the way it was, even when the screen panics
+struct RawMode;
+
+impl Drop for RawMode {
+    fn drop(&mut self) {
+        let _ = execute!(io::stdout(), LeaveAlternateScreen, Show);
+        let _ = terminal::disable_raw_mode();
+    }
+}
+
+fn draw(screen: &FrontOfHouse, out: &mut impl Write) -> io::Result<()> {
+    let (width, height) = terminal::size()?;
+    queue!(out, Clear(ClearType::All))?;
+    for (row, line) in screen.render().iter().take(height as usize).enumerate() {
+        let line: String = line.chars().take(width as usize).collect();
+        queue!(out, MoveTo(0, row as u16), Print(line))?;
+    }
+    out.flush()
+}
+
+// Runs the screen in the terminal until the staff quits with q, Esc or Ctrl+C
+pub fn run(mut screen: FrontOfHouse) -> io::Result<()> {
+    let mut stdout = io::stdout();
+    terminal::enable_raw_mode()?;
+    let _raw_mode = RawMode;
+    execute!(stdout, EnterAlternateScreen, Hide)?;
+
+    loop {
+        draw(&screen, &mut stdout)?;
+        let key = match event::read()? {
+            Event::Key(key) if key.kind == KeyEventKind::Press => key,
+            _ => continue,
+        };
+        if key.code == KeyCode::Char('c') && key.modifiers.contains(KeyModifiers::CONTROL) {
+            return Ok(());
+        }
+        let key = match key.code {
+            KeyCode::Char(c) => Key::Char(c),
+            KeyCode::Enter => Key::Enter,
+            KeyCode::Esc => Key::Esc,
+            KeyCode::Backspace => Key::Backspace,
+            KeyCode::Up => Key::Up,
+            KeyCode::Down => Key::Down,
+            KeyCode::Left => Key::Left,
+            KeyCode::Right => Key::Right,
+            _ => continue,
+        };
+        if !screen.handle(key) {
+            return Ok(());
+        }
+    }
+}
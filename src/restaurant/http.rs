@@ -0,0 +1,316 @@
+// The restaurant over HTTP
+// A small JSON API, only on localhost, so a frontend can try the ordering flow:
+//
+//   GET  /menu                the menu, like menu.json
+//   GET  /orders              every order
+//   POST /orders              {"customer": "Ana", "pizzas": [{"size": "large", "toppings": ["ham"]}]}
+//                             and optionally "diets": ["vegan"] and "allergies": ["fish"]
+//   GET  /orders/{id}         one order, with its status
+//   POST /orders/{id}/cancel  cancels it, if the pizzas were not served yet
+//
+// Online orders don't get a table, they go straight to the kitchen. They are taken like the orders of
+// the waiter, with help_customer::order_pizzas: the inventory of the counter can swap a topping we ran
+// out of, and an order we can't make is a 409 that says what we are out of. A refused order is kept in
+// the history, cancelled, like the waiter does. The kitchen works like in kitchen.rs, the board of the
+// head chef says when each order will be ready, and the status of the order changes to ready when
+// the clock gets there. Cancelling an order in the kitchen gives its ingredients back to the counter
+// and its time back to the cook, so the orders after it are ready sooner.
+
+use std::collections::HashMap;
+use std::io::{self, ErrorKind, Read};
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+
+use serde::Deserialize;
+use serde_json::{json, Value};
+use tiny_http::{Header, Request, Response, Server};
+
+use crate::restaurant::clock::{Clock, Timestamp};
+use crate::restaurant::counter::Counter;
+use crate::restaurant::inventory::Inventory;
+use crate::restaurant::kitchen::{Assignment, Board, KitchenConfig};
+use crate::restaurant::order::{Order, OrderError, OrderStatus};
+use crate::restaurant::pizza_order::dietary::{Allergen, Diet, DietaryNeeds};
+use crate::restaurant::pizza_order::help_customer::{self, Refusal};
+use crate::restaurant::pizza_order::menu::{Cheese, Dough, Menu, Size};
+use crate::restaurant::pizza_order::PizzaBuilder;
+use crate::restaurant::store::{OrderStore, StoredOrder};
+
+#[derive(Deserialize)]
+struct PizzaRequest {
+    size: Option<Size>,
+    dough: Option<Dough>,
+    cheese: Option<Cheese>,
+    #[serde(default)]
+    toppings: Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct OrderRequest {
+    customer: String,
+    pizzas: Vec<PizzaRequest>,
+    #[serde(default)]
+    diets: Vec<Diet>,
+    #[serde(default)]
+    allergies: Vec<Allergen>,
+}
+
+fn error(status: u16, message: &str) -> (u16, Value) {
+    (status, json!({ "error": message }))
+}
+
+fn builder<'a>(menu: &'a Menu, request: &PizzaRequest) -> PizzaBuilder<'a> {
+    let mut builder = PizzaBuilder::new(menu);
+    if let Some(size) = request.size {
+        builder = builder.size(size);
+    }
+    if let Some(dough) = request.dough {
+        builder = builder.dough(dough);
+    }
+    if let Some(cheese) = request.cheese {
+        builder = builder.cheese(cheese);
+    }
+    for topping in &request.toppings {
+        builder = builder.topping(topping);
+    }
+    builder
+}
+
+// Everything the API knows, the HTTP server only moves requests in and responses out
+pub struct PizzaApi {
+    menu: Menu,
+    kitchen: KitchenConfig,
+    board: Board,
+    counter: Counter,
+    store: OrderStore,
+    clock: Arc<dyn Clock + Send + Sync>,
+    // Who cooks each order and when it will be ready
+    assignments: HashMap<u64, Assignment>,
+}
+
+impl PizzaApi {
+    // The counter starts stocked for the menu, with_counter changes it
+    pub fn new(menu: Menu, kitchen: KitchenConfig, store: OrderStore, clock: Arc<dyn Clock + Send + Sync>) -> PizzaApi {
+        PizzaApi {
+            board: Board::new(kitchen.cooks),
+            counter: Counter::new(Inventory::stocked_for(&menu)),
+            menu,
+            kitchen,
+            store,
+            clock,
+            assignments: HashMap::new(),
+        }
+    }
+
+    pub fn with_counter(mut self, counter: Counter) -> PizzaApi {
+        self.counter = counter;
+        self
+    }
+
+    // Orders that were in the kitchen before a restart are ready after their cooking time
+    fn ready_time(&self, stored: &StoredOrder) -> Option<Timestamp> {
+        if let Some(assignment) = self.assignments.get(&stored.id) {
+            return Some(assignment.ready_at);
+        }
+        let in_kitchen = stored.order.time_of(OrderStatus::InKitchen)?;
+        Some(in_kitchen.plus(self.kitchen.cooking_time(&stored.order)))
+    }
+
+    // Moves the orders the kitchen already finished to Ready
+    fn refresh(&mut self, id: u64) -> io::Result<()> {
+        let now = self.clock.now();
+        let stored = match self.store.get(id) {
+            Some(stored) if stored.order.status() == OrderStatus::InKitchen => stored,
+            _ => return Ok(()),
+        };
+        match self.ready_time(stored) {
+            Some(ready_at) if ready_at <= now => {
+                let mut order = stored.order.clone();
+                order.mark_ready(ready_at)?;
+                self.store.update(id, &order)
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn order_json(&self, stored: &StoredOrder) -> Value {
+        let order = &stored.order;
+        let ready_at = match order.status() {
+            OrderStatus::InKitchen => self.ready_time(stored),
+            _ => order.time_of(OrderStatus::Ready),
+        };
+        let pizzas: Vec<String> = order.pizzas.iter().map(|pizza| pizza.to_string()).collect();
+        let price: i64 = order.pizzas.iter().map(|pizza| self.menu.price_of(pizza).as_cents()).sum();
+        json!({
+            "id": stored.id,
+            "customer": order.customer,
+            "status": order.status(),
+            "pizzas": pizzas,
+            "price_cents": price,
+            "ready_at": ready_at,
+        })
+    }
+
+    fn create_order(&mut self, body: &str) -> io::Result<(u16, Value)> {
+        let request: OrderRequest = match serde_json::from_str(body) {
+            Ok(request) => request,
+            Err(err) => return Ok(error(400, &format!("invalid order: {}", err))),
+        };
+        if request.pizzas.is_empty() {
+            return Ok(error(422, &OrderError::NoPizzas.to_string()));
+        }
+
+        let now = self.clock.now();
+        let mut order = Order::seat(&request.customer, now);
+        let choices = request.pizzas.iter().map(|pizza| builder(&self.menu, pizza)).collect();
+        let needs = DietaryNeeds {
+            diets: request.diets,
+            allergies: request.allergies,
+        };
+        // What the waiter would say goes nowhere, the answer is the order or why we refused it
+        let refusal = help_customer::order_pizzas(&mut order, choices, &needs, self.counter.inventory_mut(), &*self.clock, &mut io::sink())?;
+        if let Some(refusal) = refusal {
+            self.store.insert(&order)?;
+            return Ok(match refusal {
+                Refusal::CantMake(err) => error(422, &err.to_string()),
+                refusal @ Refusal::Allergens(_) => error(422, &refusal.to_string()),
+                Refusal::OutOfStock(err) => error(409, &err.to_string()),
+            });
+        }
+        order.send_to_kitchen(now)?;
+        let assignment = self.board.assign(now, self.kitchen.cooking_time(&order));
+        let id = self.store.insert(&order)?;
+        self.assignments.insert(id, assignment);
+
+        let stored = self.store.get(id).expect("the order was just inserted");
+        Ok((201, self.order_json(stored)))
+    }
+
+    fn list_orders(&mut self) -> io::Result<(u16, Value)> {
+        let ids: Vec<u64> = self.store.orders().iter().map(|stored| stored.id).collect();
+        for id in ids {
+            self.refresh(id)?;
+        }
+        let orders = self.store.orders().iter().map(|stored| self.order_json(stored)).collect();
+        Ok((200, Value::Array(orders)))
+    }
+
+    fn get_order(&mut self, id: &str) -> io::Result<(u16, Value)> {
+        let id = match id.parse::<u64>() {
+            Ok(id) => id,
+            Err(_) => return Ok(error(404, &format!("there is no order {}", id))),
+        };
+        self.refresh(id)?;
+        match self.store.get(id) {
+            Some(stored) => Ok((200, self.order_json(stored))),
+            None => Ok(error(404, &format!("there is no order {}", id))),
+        }
+    }
+
+    fn cancel_order(&mut self, id: &str) -> io::Result<(u16, Value)> {
+        let id = match id.parse::<u64>() {
+            Ok(id) => id,
+            Err(_) => return Ok(error(404, &format!("there is no order {}", id))),
+        };
+        self.refresh(id)?;
+        let mut order = match self.store.get(id) {
+            Some(stored) => stored.order.clone(),
+            None => return Ok(error(404, &format!("there is no order {}", id))),
+        };
+        let now = self.clock.now();
+        let in_kitchen = order.status() == OrderStatus::InKitchen;
+        if let Err(err) = order.cancel(now) {
+            return Ok(error(409, &err.to_string()));
+        }
+        self.store.update(id, &order)?;
+        if let Some(cancelled) = self.assignments.remove(&id) {
+            let freed = self.board.release(&cancelled, now);
+            for later in self.assignments.values_mut() {
+                if later.cook == cancelled.cook && later.started_at >= cancelled.ready_at {
+                    later.started_at = Timestamp(later.started_at.seconds() - freed);
+                    later.ready_at = Timestamp(later.ready_at.seconds() - freed);
+                }
+            }
+        }
+        if in_kitchen {
+            self.counter.inventory_mut().give_back(&order.pizzas);
+        }
+        let stored = self.store.get(id).expect("the order was just updated");
+        Ok((200, self.order_json(stored)))
+    }
+
+    // The status code and the JSON of the answer to a request
+    pub fn handle(&mut self, method: &str, path: &str, body: &str) -> (u16, Value) {
+        let segments: Vec<&str> = path.trim_matches('/').split('/').collect();
+        let result = match (method, segments.as_slice()) {
+            ("GET", ["menu"]) => Ok((200, json!(self.menu))),
+            ("GET", ["orders"]) => self.list_orders(),
+            ("POST", ["orders"]) => self.create_order(body),
+            ("GET", ["orders", id]) => self.get_order(id),
+            ("POST", ["orders", id, "cancel"]) => self.cancel_order(id),
+            _ => Ok(error(404, &format!("no route for {} {}", method, path))),
+        };
+        result.unwrap_or_else(|err| error(500, &err.to_string()))
+    }
+}
+
+fn respond(api: &mut PizzaApi, mut request: Request) -> io::Result<()> {
+    let mut body = String::new();
+    let (status, value) = match request.as_reader().read_to_string(&mut body) {
+        Ok(_) => {
+            let method = request.method().to_string();
+            let path = request.url().split('?').next().unwrap_or("").to_string();
+            api.handle(&method, &path, &body)
+        }
+        Err(err) => error(400, &err.to_string()),
+    };
+    let header = Header::from_bytes("Content-Type", "application/json").expect("a valid header");
+    let response = Response::from_string(value.to_string())
+        .with_status_code(status)
+        .with_header(header);
+    request.respond(response)
+}
+
+// The API answering requests in its own thread, until stop is called
+pub struct HttpServer {
+    server: Arc<Server>,
+    address: SocketAddr,
+    thread: JoinHandle<()>,
+}
+
+impl HttpServer {
+    // "127.0.0.1:0" picks any free port, address() says which one
+    pub fn start(address: &str, mut api: PizzaApi) -> io::Result<HttpServer> {
+        let server = Arc::new(Server::http(address).map_err(|err| io::Error::new(ErrorKind::AddrNotAvailable, err.to_string()))?);
+        let address = server
+            .server_addr()
+            .to_ip()
+            .ok_or_else(|| io::Error::new(ErrorKind::AddrNotAvailable, "the server is not on a TCP address"))?;
+
+        let receiver = Arc::clone(&server);
+        let thread = thread::spawn(move || {
+            // recv fails when the server is unblocked, that's the signal to stop
+            while let Ok(request) = receiver.recv() {
+                if let Err(err) = respond(&mut api, request) {
+                    eprintln!("warning: could not answer a request: {}", err);
+                }
+            }
+        });
+        Ok(HttpServer { server, address, thread })
+    }
+
+    pub fn address(&self) -> SocketAddr {
+        self.address
+    }
+
+    pub fn stop(self) {
+        self.server.unblock();
+        let _ = self.thread.join();
+    }
+
+    // Answers requests until the program is killed
+    pub fn wait(self) {
+        let _ = self.thread.join();
+    }
+}
@@ -0,0 +1,359 @@
+// The pantry
+// Every pizza uses up ingredients: a ball of dough, some grams of cheese and a portion of each
+// topping, more cheese and toppings for the bigger sizes. The recipes say how much, recipes.json next
+// to this module has the ones we use by default. The Inventory counts what is left, warns when an
+// ingredient is running low, and refuses pizzas we don't have the ingredients for. When only a
+// topping is missing we can offer another one instead, a different dough or cheese would be a
+// different pizza, so those orders are refused.
+
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt;
+use std::io::{self, ErrorKind, Write};
+
+use serde::{Deserialize, Serialize};
+
+use crate::restaurant::pizza_order::menu::{Cheese, Dough, Menu, Size, Topping};
+use crate::restaurant::pizza_order::Pizza;
+
+const STANDARD_RECIPES: &str = include_str!("recipes.json");
+
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub enum Ingredient {
+    // Counted in dough balls
+    Dough(Dough),
+    // Counted in grams
+    Cheese(Cheese),
+    // Counted in portions
+    Topping(String),
+}
+
+impl Ingredient {
+    pub fn topping(name: &str) -> Ingredient {
+        Ingredient::Topping(String::from(name))
+    }
+
+    // "3 dough balls", "800 g", "1 portion"
+    pub fn quantity(&self, quantity: u32) -> String {
+        let plural = if quantity == 1 { "" } else { "s" };
+        match self {
+            Ingredient::Dough(_) => format!("{} dough ball{}", quantity, plural),
+            Ingredient::Cheese(_) => format!("{} g", quantity),
+            Ingredient::Topping(_) => format!("{} portion{}", quantity, plural),
+        }
+    }
+}
+
+impl fmt::Display for Ingredient {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Ingredient::Dough(dough) => write!(f, "{}", dough),
+            Ingredient::Cheese(cheese) => write!(f, "{}", cheese),
+            Ingredient::Topping(name) => write!(f, "{}", name),
+        }
+    }
+}
+
+// What one pizza of a size uses
+#[derive(Clone, Copy, PartialEq, Debug, Serialize, Deserialize)]
+pub struct Recipe {
+    pub dough_balls: u32,
+    pub cheese_grams: u32,
+    // Of each topping on the pizza
+    pub topping_portions: u32,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Recipes(BTreeMap<Size, Recipe>);
+
+impl Recipes {
+    pub fn standard() -> Recipes {
+        Recipes::from_json(STANDARD_RECIPES).expect("recipes.json has valid recipes")
+    }
+
+    pub fn from_json(json: &str) -> io::Result<Recipes> {
+        serde_json::from_str(json).map_err(|err| io::Error::new(ErrorKind::InvalidData, err))
+    }
+
+    pub fn recipe(&self, size: Size) -> Option<Recipe> {
+        self.0.get(&size).copied()
+    }
+
+    // The ingredients of the pizza and how much of each, a size without a recipe uses nothing
+    pub fn ingredients(&self, pizza: &Pizza) -> Vec<(Ingredient, u32)> {
+        let recipe = match self.recipe(pizza.size) {
+            Some(recipe) => recipe,
+            None => return Vec::new(),
+        };
+        let mut ingredients = vec![(Ingredient::Dough(pizza.dough), recipe.dough_balls)];
+        if pizza.cheese != Cheese::NoCheese {
+            ingredients.push((Ingredient::Cheese(pizza.cheese), recipe.cheese_grams));
+        }
+        for topping in &pizza.toppings {
+            ingredients.push((Ingredient::topping(&topping.name), recipe.topping_portions));
+        }
+        ingredients
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum InventoryError {
+    OutOfStock { ingredient: Ingredient, needed: u32, left: u32 },
+}
+
+impl fmt::Display for InventoryError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            InventoryError::OutOfStock { ingredient, needed, left } => write!(
+                f,
+                "we ran out of {}, {} needed and {} left",
+                ingredient,
+                ingredient.quantity(*needed),
+                ingredient.quantity(*left)
+            ),
+        }
+    }
+}
+
+impl Error for InventoryError {}
+
+impl From<InventoryError> for io::Error {
+    fn from(err: InventoryError) -> io::Error {
+        io::Error::other(err)
+    }
+}
+
+// An ingredient at or below its alert level
+#[derive(Clone, PartialEq, Debug)]
+pub struct StockAlert {
+    pub ingredient: Ingredient,
+    pub left: u32,
+}
+
+// "mushrooms is running low, 3 portions left"
+impl fmt::Display for StockAlert {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} is running low, {} left", self.ingredient, self.ingredient.quantity(self.left))
+    }
+}
+
+// A topping we ran out of, and the one the pizza gets instead
+#[derive(Clone, PartialEq, Debug)]
+pub struct Swap {
+    pub missing: String,
+    pub replacement: String,
+}
+
+// How much of each ingredient of the menu a day of service starts with, what is not said is the
+// stock of Inventory::stocked_for. The alerts are at a quarter of it
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct StockLevels {
+    // Of each dough
+    pub dough_balls: u32,
+    // Of each cheese
+    pub cheese_grams: u32,
+    // Of each topping that is not in `toppings`
+    pub topping_portions: u32,
+    pub toppings: BTreeMap<String, u32>,
+}
+
+impl Default for StockLevels {
+    fn default() -> StockLevels {
+        StockLevels {
+            dough_balls: 40,
+            cheese_grams: 6_000,
+            topping_portions: 40,
+            toppings: BTreeMap::new(),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct Inventory {
+    recipes: Recipes,
+    stock: BTreeMap<Ingredient, u32>,
+    // We warn when what is left of an ingredient gets to its alert level
+    alert_at: BTreeMap<Ingredient, u32>,
+}
+
+impl Inventory {
+    // Nothing in stock yet, restock says what we have
+    pub fn new(recipes: Recipes) -> Inventory {
+        Inventory {
+            recipes,
+            stock: BTreeMap::new(),
+            alert_at: BTreeMap::new(),
+        }
+    }
+
+    // Everything on the standard menu, stocked for a day of service
+    pub fn standard() -> Inventory {
+        Inventory::stocked_for(&Menu::standard())
+    }
+
+    // 40 balls of each dough of the menu, 6 kg of each cheese and 40 portions of each topping, with
+    // alerts at a quarter of that
+    pub fn stocked_for(menu: &Menu) -> Inventory {
+        Inventory::stocked_with(menu, &StockLevels::default())
+    }
+
+    pub fn stocked_with(menu: &Menu, levels: &StockLevels) -> Inventory {
+        let mut inventory = Inventory::new(Recipes::standard());
+        let mut stock = |ingredient: Ingredient, quantity: u32| {
+            inventory.alert_at(ingredient.clone(), quantity / 4);
+            inventory.restock(ingredient, quantity);
+        };
+        for dough in &menu.doughs {
+            stock(Ingredient::Dough(*dough), levels.dough_balls);
+        }
+        for cheese in menu.cheeses.iter().filter(|cheese| **cheese != Cheese::NoCheese) {
+            stock(Ingredient::Cheese(*cheese), levels.cheese_grams);
+        }
+        for topping in &menu.toppings {
+            let portions = levels.toppings.get(&topping.name).copied().unwrap_or(levels.topping_portions);
+            stock(Ingredient::topping(&topping.name), portions);
+        }
+        inventory
+    }
+
+    pub fn recipes(&self) -> &Recipes {
+        &self.recipes
+    }
+
+    pub fn stock(&self, ingredient: &Ingredient) -> u32 {
+        self.stock.get(ingredient).copied().unwrap_or(0)
+    }
+
+    pub fn restock(&mut self, ingredient: Ingredient, quantity: u32) {
+        *self.stock.entry(ingredient).or_insert(0) += quantity;
+    }
+
+    pub fn alert_at(&mut self, ingredient: Ingredient, quantity: u32) {
+        self.alert_at.insert(ingredient, quantity);
+    }
+
+    // Everything the pizzas use together, so two pizzas can't both count on the last dough ball
+    fn needed(&self, pizzas: &[Pizza]) -> BTreeMap<Ingredient, u32> {
+        let mut needed: BTreeMap<Ingredient, u32> = BTreeMap::new();
+        for pizza in pizzas {
+            for (ingredient, quantity) in self.recipes.ingredients(pizza) {
+                *needed.entry(ingredient).or_insert(0) += quantity;
+            }
+        }
+        needed
+    }
+
+    // The first ingredient we don't have enough of
+    pub fn check(&self, pizzas: &[Pizza]) -> Result<(), InventoryError> {
+        for (ingredient, needed) in self.needed(pizzas) {
+            let left = self.stock(&ingredient);
+            if left < needed {
+                return Err(InventoryError::OutOfStock { ingredient, needed, left });
+            }
+        }
+        Ok(())
+    }
+
+    // Takes the ingredients of the pizzas, all of them or nothing, and returns the alerts of the
+    // ingredients that got low with these pizzas
+    pub fn consume(&mut self, pizzas: &[Pizza]) -> Result<Vec<StockAlert>, InventoryError> {
+        self.check(pizzas)?;
+        let mut alerts = Vec::new();
+        for (ingredient, needed) in self.needed(pizzas) {
+            let before = self.stock(&ingredient);
+            let left = before - needed;
+            self.stock.insert(ingredient.clone(), left);
+            match self.alert_at.get(&ingredient) {
+                Some(alert_at) if left <= *alert_at && before > *alert_at => alerts.push(StockAlert { ingredient, left }),
+                _ => {}
+            }
+        }
+        Ok(alerts)
+    }
+
+    // Puts back the ingredients of pizzas that were not cooked after all
+    pub fn give_back(&mut self, pizzas: &[Pizza]) {
+        for (ingredient, quantity) in self.needed(pizzas) {
+            self.restock(ingredient, quantity);
+        }
+    }
+
+    // Every ingredient at or below its alert level
+    pub fn low_stock(&self) -> Vec<StockAlert> {
+        self.alert_at
+            .iter()
+            .filter(|(ingredient, alert_at)| self.stock(ingredient) <= **alert_at)
+            .map(|(ingredient, _)| StockAlert {
+                ingredient: ingredient.clone(),
+                left: self.stock(ingredient),
+            })
+            .collect()
+    }
+
+    // The closest pizza we can make: every topping we ran out of is replaced by the topping of the
+    // menu with the closest price that we have, that is not on the pizza yet and goes with the others.
+    // The dough and the cheese are never replaced.
+    pub fn substitute(&self, pizza: &Pizza, menu: &Menu) -> Result<(Pizza, Vec<Swap>), InventoryError> {
+        let mut pizza = pizza.clone();
+        let mut swaps = Vec::new();
+        // Each swap puts a topping we have in place of one we don't, so this ends
+        loop {
+            match self.check(std::slice::from_ref(&pizza)) {
+                Ok(()) => return Ok((pizza, swaps)),
+                Err(InventoryError::OutOfStock { ingredient: Ingredient::Topping(missing), needed, left }) => {
+                    let position = pizza
+                        .toppings
+                        .iter()
+                        .position(|topping| topping.name == missing)
+                        .expect("a missing topping is on the pizza");
+                    let replacement = match self.replacement_for(&pizza, position, menu) {
+                        Some(replacement) => replacement,
+                        None => {
+                            let ingredient = Ingredient::Topping(missing);
+                            return Err(InventoryError::OutOfStock { ingredient, needed, left });
+                        }
+                    };
+                    swaps.push(Swap {
+                        missing,
+                        replacement: replacement.name.clone(),
+                    });
+                    pizza.toppings[position] = replacement;
+                }
+                Err(err) => return Err(err),
+            }
+        }
+    }
+
+    fn replacement_for(&self, pizza: &Pizza, position: usize, menu: &Menu) -> Option<Topping> {
+        let portions = self.recipes.recipe(pizza.size)?.topping_portions;
+        let price = pizza.toppings[position].price_cents;
+        let others: Vec<&Topping> = pizza
+            .toppings
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| *index != position)
+            .map(|(_, topping)| topping)
+            .collect();
+        menu.toppings
+            .iter()
+            .filter(|candidate| candidate.name != pizza.toppings[position].name)
+            .filter(|candidate| self.stock(&Ingredient::topping(&candidate.name)) >= portions)
+            .filter(|candidate| others.iter().all(|other| other.name != candidate.name))
+            .filter(|candidate| others.iter().all(|other| !menu.are_incompatible(&other.name, &candidate.name)))
+            .min_by_key(|candidate| candidate.price_cents.abs_diff(price))
+            .cloned()
+    }
+
+    pub fn print(&self, out: &mut dyn Write) -> io::Result<()> {
+        writeln!(out, "{:<20} {:>16}", "Ingredient", "Left")?;
+        for (ingredient, left) in &self.stock {
+            let low = match self.alert_at.get(ingredient) {
+                Some(alert_at) if left <= alert_at => "  low",
+                _ => "",
+            };
+            writeln!(out, "{:<20} {:>16}{}", ingredient.to_string(), ingredient.quantity(*left), low)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,311 @@
+// The kitchen
+// Every cook is a thread with its own channel of tickets. The head chef (the Kitchen) gives each
+// order to the cook that will be free first, and the cooks send the orders back, ready, over another
+// channel. Nobody sleeps: the time a pizza takes is simulated from the timestamps of the orders, so
+// the same orders always give the same report, no matter how the threads are scheduled.
+
+use std::io::{self, Write};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::thread::{self, JoinHandle};
+
+use crate::restaurant::clock::{format_duration, Timestamp};
+use crate::restaurant::order::{Order, OrderError};
+use crate::restaurant::pizza_order::menu::Dough;
+use crate::restaurant::pizza_order::Pizza;
+
+#[derive(Clone, Debug)]
+pub struct KitchenConfig {
+    pub cooks: usize,
+    // Preparing a pizza takes prep_seconds, plus topping_seconds for each topping
+    pub prep_seconds: u64,
+    pub topping_seconds: u64,
+    pub bake_seconds: u64,
+    // A thin crust is in and out of the oven faster
+    pub thin_crust_bake_seconds: u64,
+}
+
+impl Default for KitchenConfig {
+    fn default() -> KitchenConfig {
+        KitchenConfig {
+            cooks: 2,
+            prep_seconds: 120,
+            topping_seconds: 30,
+            bake_seconds: 600,
+            thin_crust_bake_seconds: 480,
+        }
+    }
+}
+
+impl KitchenConfig {
+    pub fn prep_time(&self, pizza: &Pizza) -> u64 {
+        self.prep_seconds + self.topping_seconds * pizza.toppings.len() as u64
+    }
+
+    pub fn bake_time(&self, pizza: &Pizza) -> u64 {
+        match pizza.dough {
+            Dough::ThinCrust => self.thin_crust_bake_seconds,
+            _ => self.bake_seconds,
+        }
+    }
+
+    // One cook makes all the pizzas of an order, one after the other
+    pub fn cooking_time(&self, order: &Order) -> u64 {
+        order
+            .pizzas
+            .iter()
+            .map(|pizza| self.prep_time(pizza) + self.bake_time(pizza))
+            .sum()
+    }
+}
+
+struct Ticket {
+    number: usize,
+    order: Order,
+    queued_at: Timestamp,
+}
+
+pub struct CookedOrder {
+    // The position of the order in the kitchen, the first order sent is ticket 1
+    pub ticket: usize,
+    pub cook: usize,
+    pub order: Order,
+    pub queued_at: Timestamp,
+    pub started_at: Timestamp,
+    pub ready_at: Timestamp,
+}
+
+impl CookedOrder {
+    // How long the order waited for a cook
+    pub fn wait_seconds(&self) -> u64 {
+        self.started_at.since(self.queued_at)
+    }
+
+    // How long from the kitchen receiving the order until it was ready
+    pub fn total_seconds(&self) -> u64 {
+        self.ready_at.since(self.queued_at)
+    }
+}
+
+fn cook(number: usize, config: KitchenConfig, tickets: Receiver<Ticket>, done: Sender<CookedOrder>) {
+    let mut free_at = Timestamp(0);
+    for ticket in tickets {
+        let started_at = free_at.max(ticket.queued_at);
+        let ready_at = started_at.plus(config.cooking_time(&ticket.order));
+        let mut order = ticket.order;
+        // The Kitchen only sends orders that are InKitchen, so they can always be Ready
+        order.mark_ready(ready_at).expect("the kitchen only cooks orders that are in the kitchen");
+        free_at = ready_at;
+
+        let cooked = CookedOrder {
+            ticket: ticket.number,
+            cook: number,
+            order,
+            queued_at: ticket.queued_at,
+            started_at,
+            ready_at,
+        };
+        if done.send(cooked).is_err() {
+            break;
+        }
+    }
+}
+
+// Who cooks what and when
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Assignment {
+    // The first cook is 1
+    pub cook: usize,
+    pub started_at: Timestamp,
+    pub ready_at: Timestamp,
+}
+
+// The board of the head chef: when each cook will be done with what they already have
+#[derive(Clone, Debug)]
+pub struct Board {
+    free_at: Vec<Timestamp>,
+}
+
+impl Board {
+    // There is always at least one cook
+    pub fn new(cooks: usize) -> Board {
+        Board {
+            free_at: vec![Timestamp(0); cooks.max(1)],
+        }
+    }
+
+    pub fn cooks(&self) -> usize {
+        self.free_at.len()
+    }
+
+    // Gives `seconds` of work that arrived `at` to the cook that is free first, when two cooks are
+    // free at the same time, the first one takes it
+    pub fn assign(&mut self, at: Timestamp, seconds: u64) -> Assignment {
+        let cook = (0..self.free_at.len())
+            .min_by_key(|&cook| self.free_at[cook].max(at))
+            .unwrap_or(0);
+        let started_at = self.free_at[cook].max(at);
+        let ready_at = started_at.plus(seconds);
+        self.free_at[cook] = ready_at;
+        Assignment {
+            cook: cook + 1,
+            started_at,
+            ready_at,
+        }
+    }
+
+    // Takes back the work of an assignment that was cancelled `at`, what the cook didn't do of it yet
+    // is free again. The work the cook had after it starts that many seconds earlier, which are
+    // returned
+    pub fn release(&mut self, assignment: &Assignment, at: Timestamp) -> u64 {
+        let freed = assignment.ready_at.since(assignment.started_at.max(at));
+        let free_at = &mut self.free_at[assignment.cook - 1];
+        *free_at = Timestamp(free_at.seconds() - freed);
+        freed
+    }
+}
+
+pub struct Kitchen {
+    config: KitchenConfig,
+    tickets: Vec<Sender<Ticket>>,
+    done: Receiver<CookedOrder>,
+    cooks: Vec<JoinHandle<()>>,
+    board: Board,
+    sent: usize,
+}
+
+impl Kitchen {
+    pub fn open(config: KitchenConfig) -> Kitchen {
+        let board = Board::new(config.cooks);
+        let (done_sender, done) = mpsc::channel();
+        let mut tickets = Vec::new();
+        let mut cooks = Vec::new();
+        for number in 1..=board.cooks() {
+            let (sender, receiver) = mpsc::channel();
+            let config = config.clone();
+            let done_sender = done_sender.clone();
+            cooks.push(thread::spawn(move || cook(number, config, receiver, done_sender)));
+            tickets.push(sender);
+        }
+
+        Kitchen {
+            board,
+            config,
+            tickets,
+            done,
+            cooks,
+            sent: 0,
+        }
+    }
+
+    // Moves the order to InKitchen at `at` and gives it to the cook that is free first
+    pub fn send(&mut self, mut order: Order, at: Timestamp) -> Result<(), OrderError> {
+        order.send_to_kitchen(at)?;
+
+        let assignment = self.board.assign(at, self.config.cooking_time(&order));
+
+        self.sent += 1;
+        let ticket = Ticket {
+            number: self.sent,
+            order,
+            queued_at: at,
+        };
+        self.tickets[assignment.cook - 1].send(ticket).expect("the cooks work until the kitchen closes");
+        Ok(())
+    }
+
+    // Waits for the cooks to finish every order, and reports how the service went
+    pub fn close(self) -> KitchenReport {
+        let cooks = self.cooks.len();
+        drop(self.tickets);
+        let mut orders: Vec<CookedOrder> = self.done.iter().collect();
+        for cook in self.cooks {
+            cook.join().expect("a cook panicked");
+        }
+        orders.sort_by_key(|cooked| cooked.ticket);
+        KitchenReport { cooks, orders }
+    }
+}
+
+pub struct KitchenReport {
+    pub cooks: usize,
+    // In the order they were sent to the kitchen
+    pub orders: Vec<CookedOrder>,
+}
+
+impl KitchenReport {
+    pub fn pizzas(&self) -> usize {
+        self.orders.iter().map(|cooked| cooked.order.pizzas.len()).sum()
+    }
+
+    // From the first order arriving until the last one is ready
+    pub fn service_seconds(&self) -> u64 {
+        let first = self.orders.iter().map(|cooked| cooked.queued_at).min();
+        let last = self.orders.iter().map(|cooked| cooked.ready_at).max();
+        match (first, last) {
+            (Some(first), Some(last)) => last.since(first),
+            _ => 0,
+        }
+    }
+
+    pub fn pizzas_per_hour(&self) -> f64 {
+        match self.service_seconds() {
+            0 => 0.0,
+            seconds => self.pizzas() as f64 * 3600.0 / seconds as f64,
+        }
+    }
+
+    // Orders waiting for a cook right after `cooked` arrived, counting itself if it had to wait
+    pub fn queue_length(&self, cooked: &CookedOrder) -> usize {
+        let at = cooked.queued_at;
+        self.orders
+            .iter()
+            .filter(|other| other.queued_at <= at && other.started_at > at)
+            .count()
+    }
+
+    pub fn longest_queue(&self) -> usize {
+        self.orders.iter().map(|cooked| self.queue_length(cooked)).max().unwrap_or(0)
+    }
+
+    pub fn average_wait_seconds(&self) -> u64 {
+        if self.orders.is_empty() {
+            return 0;
+        }
+        self.orders.iter().map(|cooked| cooked.wait_seconds()).sum::<u64>() / self.orders.len() as u64
+    }
+
+    pub fn print(&self, out: &mut dyn Write) -> io::Result<()> {
+        writeln!(
+            out,
+            "{:>6}  {:<12} {:>4}  {:>5}  {:>8}  {:>8}",
+            "Ticket", "Customer", "Cook", "Queue", "Wait", "Ready in"
+        )?;
+        for cooked in &self.orders {
+            writeln!(
+                out,
+                "{:>6}  {:<12} {:>4}  {:>5}  {:>8}  {:>8}",
+                cooked.ticket,
+                cooked.order.customer,
+                cooked.cook,
+                self.queue_length(cooked),
+                format_duration(cooked.wait_seconds()),
+                format_duration(cooked.total_seconds())
+            )?;
+        }
+        writeln!(out)?;
+        writeln!(
+            out,
+            "{} pizzas in {} with {} cooks: {:.1} pizzas per hour",
+            self.pizzas(),
+            format_duration(self.service_seconds()),
+            self.cooks,
+            self.pizzas_per_hour()
+        )?;
+        writeln!(
+            out,
+            "Longest queue: {} orders, average wait: {}",
+            self.longest_queue(),
+            format_duration(self.average_wait_seconds())
+        )
+    }
+}
@@ -1,41 +1,94 @@
-mod pizza_order {
-    pub struct Pizza {
-        pub dough: String,
-        pub cheese: String,
-        pub topping: String,
-    }
+// The restaurant of Class 21 - Modules
+// Each part of the restaurant is a module, and each module only knows its own job:
+//
+//   pizza_order   the pizzas, the menu and the help_customer functions the waiter uses
+//   order         follows each order from the table to the bill
+//   seating       the tables of the Restaurant and its waitlist
+//   counter       where orders are taken and paid, with the inventory, promotions and loyalty
+//   kitchen       the cooks, inventory counts the ingredients they use
+//   billing       the bill, promotions take discounts off it
+//   delivery      the couriers, for the orders that don't eat here
+//   store         the history of the orders, report sums up the sales of a day
+//   http          serves the orders as a JSON API, front_of_house puts them on a screen for the staff
+//   chain         runs several restaurants as the branches of one chain
+//   clock         tells everyone what time it is
 
-    impl Pizza {
-        pub fn lunch(topping: &str) -> Pizza {
-            Pizza {
-                dough: String::from("regular dough"),
-                cheese: String::from("mozzarella"),
-                topping: String::from(topping),
-            }
-        }
+pub mod billing;
+pub mod chain;
+pub mod clock;
+pub mod counter;
+pub mod delivery;
+pub mod front_of_house;
+pub mod http;
+pub mod inventory;
+pub mod kitchen;
+pub mod order;
+pub mod pizza_order;
+pub mod promotions;
+pub mod report;
+pub mod seating;
+pub mod store;
 
-        pub fn eat(&self) {
+use billing::{Bill, Rate};
+use clock::{Clock, SimulatedClock, SystemClock};
+use counter::Counter;
+use kitchen::{Kitchen, KitchenConfig};
+use order::{Order, OrderStatus};
+use pizza_order::help_customer;
+use pizza_order::menu::Menu;
+use pizza_order::PizzaBuilder;
+use promotions::Promotions;
+use seating::{Party, Restaurant};
+use store::OrderStore;
 
-        }
-    }
+// With the history of the learner, the customer pays at its counter and the orders go to its store
+pub fn order_food(out: &mut dyn std::io::Write) -> std::io::Result<()> {
+    store::using_history(|history| match history {
+        Some(history) => serve_lunch(&mut history.counter, Some(&mut history.store), out),
+        None => serve_lunch(&mut Counter::standard().with_promotions(Promotions::standard()), None, out),
+    })
+}
 
-    pub mod help_customer {
-        fn seat_at_table() {
-            println!("Customer seated at table");
+// Like the progress, the history is a nice to have, if an order can't be written we only warn
+fn record(store: &mut Option<&mut OrderStore>, order: &Order) {
+    if let Some(store) = store {
+        if let Err(err) = store.insert(order) {
+            eprintln!("warning: could not save the order: {}", err);
         }
+    }
+}
 
-        pub fn take_order() {
-            seat_at_table();
-            let cust_pizza: super::Pizza = super::Pizza::lunch("veggies");
-            serve_customer(cust_pizza);
+fn serve_lunch(counter: &mut Counter, mut store: Option<&mut OrderStore>, out: &mut dyn std::io::Write) -> std::io::Result<()> {
+    let menu = Menu::standard();
+    let choice = PizzaBuilder::new(&menu).topping("veggies");
+    let mut restaurant = Restaurant::standard();
+    // The kitchen works in simulated time. The customer of the class always comes for lunch today, on
+    // the clocks of the restaurant, so the happy hour of the afternoon doesn't change their bill
+    let clock = SimulatedClock::starting_at(SystemClock.now().at_local_hour(12, counter.promotions().utc_offset));
+    let order = match help_customer::take_order(&mut restaurant, counter, &Party::new("Customer", 1), choice, &clock, out)? {
+        Some(order) if order.status() == OrderStatus::Ordered => order,
+        // A pizza we couldn't make is a cancelled order, it's in the sales report too
+        Some(order) => {
+            record(&mut store, &order);
+            return Ok(());
         }
+        None => return Ok(()),
+    };
 
-        fn serve_customer(cust_pizza: super::Pizza) {
-            println!("The customer is served a regular pizza with: {}", cust_pizza.topping);
-        }
+    let mut kitchen = Kitchen::open(KitchenConfig::default());
+    kitchen.send(order, clock.now())?;
+    for cooked in kitchen.close().orders {
+        clock.advance_to(cooked.ready_at);
+        let mut order = cooked.order;
+        help_customer::serve_customer(&mut order, &clock, out)?;
+        help_customer::eat_pizzas(&order, 6, out)?;
+        let bill = Bill::for_order(&order, &menu, Rate::basis_points(825)).with_tip(Rate::percent(15));
+        let bill = help_customer::apply_promotions(counter, &order, bill, None, &clock, out)?;
+        help_customer::charge_customer(counter, &mut order, &bill, &clock, out)?;
+        let steps: Vec<String> = order.history().iter().map(|transition| transition.status.to_string()).collect();
+        writeln!(out, "Order of {}: {}", order.customer, steps.join(" -> "))?;
+        help_customer::clear_table(&mut restaurant, &order, &clock, out)?;
+        record(&mut store, &order);
     }
+    Ok(())
 }
-
-pub fn order_food() {
-    crate::restaurant::pizza_order::help_customer::take_order();
-}
\ No newline at end of file
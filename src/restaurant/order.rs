@@ -0,0 +1,245 @@
+// The life of an order
+// An order starts when the customer is seated and moves, one step at a time, through
+// Seated -> Ordered -> InKitchen -> Ready -> Served -> Paid. It can be Cancelled until the pizzas are
+// served. Any other move is an OrderError, and every move is recorded with the time it happened.
+// A paid order also keeps what it was charged, the prices of the menu may change after that.
+
+use std::error::Error;
+use std::fmt;
+use std::io;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use crate::restaurant::billing::Money;
+use crate::restaurant::clock::Timestamp;
+use crate::restaurant::pizza_order::Pizza;
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OrderStatus {
+    Seated,
+    Ordered,
+    InKitchen,
+    Ready,
+    Served,
+    Paid,
+    Cancelled,
+}
+
+impl OrderStatus {
+    // The state machine: every status and the statuses it can move to
+    pub fn can_move_to(&self, next: OrderStatus) -> bool {
+        match (self, next) {
+            (OrderStatus::Seated, OrderStatus::Ordered) => true,
+            (OrderStatus::Ordered, OrderStatus::InKitchen) => true,
+            (OrderStatus::InKitchen, OrderStatus::Ready) => true,
+            (OrderStatus::Ready, OrderStatus::Served) => true,
+            (OrderStatus::Served, OrderStatus::Paid) => true,
+            (OrderStatus::Seated, OrderStatus::Cancelled) => true,
+            (OrderStatus::Ordered, OrderStatus::Cancelled) => true,
+            (OrderStatus::InKitchen, OrderStatus::Cancelled) => true,
+            (OrderStatus::Ready, OrderStatus::Cancelled) => true,
+            _ => false,
+        }
+    }
+
+    // Paid and Cancelled orders are over, nothing else can happen to them
+    pub fn is_closed(&self) -> bool {
+        matches!(self, OrderStatus::Paid | OrderStatus::Cancelled)
+    }
+}
+
+impl fmt::Display for OrderStatus {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            OrderStatus::Seated => "seated",
+            OrderStatus::Ordered => "ordered",
+            OrderStatus::InKitchen => "in kitchen",
+            OrderStatus::Ready => "ready",
+            OrderStatus::Served => "served",
+            OrderStatus::Paid => "paid",
+            OrderStatus::Cancelled => "cancelled",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+// "in kitchen", "in_kitchen" and "InKitchen" are all InKitchen
+impl FromStr for OrderStatus {
+    type Err = String;
+
+    fn from_str(status: &str) -> Result<Self, Self::Err> {
+        let name: String = status.chars().filter(|c| c.is_alphabetic()).collect();
+        match name.to_lowercase().as_str() {
+            "seated" => Ok(OrderStatus::Seated),
+            "ordered" => Ok(OrderStatus::Ordered),
+            "inkitchen" => Ok(OrderStatus::InKitchen),
+            "ready" => Ok(OrderStatus::Ready),
+            "served" => Ok(OrderStatus::Served),
+            "paid" => Ok(OrderStatus::Paid),
+            "cancelled" => Ok(OrderStatus::Cancelled),
+            _ => Err(format!("unknown order status `{}`", status)),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum OrderError {
+    InvalidTransition { from: OrderStatus, to: OrderStatus },
+    // An order needs at least one pizza
+    NoPizzas,
+}
+
+impl fmt::Display for OrderError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            OrderError::InvalidTransition { from, to } => write!(f, "an order that is {} can't be {}", from, to),
+            OrderError::NoPizzas => write!(f, "the order has no pizzas"),
+        }
+    }
+}
+
+impl Error for OrderError {}
+
+// So the functions that write to the customer can use ? on the transitions too
+impl From<OrderError> for io::Error {
+    fn from(err: OrderError) -> io::Error {
+        io::Error::other(err)
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Debug, Serialize, Deserialize)]
+pub struct Transition {
+    pub status: OrderStatus,
+    pub at: Timestamp,
+}
+
+// The food of the bill the customer paid, taxes and tips are not ours
+#[derive(Clone, Copy, PartialEq, Debug, Serialize, Deserialize)]
+pub struct Charge {
+    #[serde(rename = "subtotal_cents")]
+    pub subtotal: Money,
+    #[serde(rename = "discount_cents")]
+    pub discount: Money,
+}
+
+impl Charge {
+    pub fn food(&self) -> Money {
+        self.subtotal - self.discount
+    }
+}
+
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+#[serde(try_from = "StoredFields")]
+pub struct Order {
+    pub customer: String,
+    // The table of the customer, if they are eating in the restaurant
+    #[serde(default)]
+    pub table: Option<u32>,
+    pub pizzas: Vec<Pizza>,
+    // Set by charge_customer, the orders saved before there was a charge don't have it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub charged: Option<Charge>,
+    // Never empty, the first transition is always Seated
+    history: Vec<Transition>,
+}
+
+// An order as it is read from a file, before we know its history is one an Order can have
+#[derive(Deserialize)]
+struct StoredFields {
+    customer: String,
+    #[serde(default)]
+    table: Option<u32>,
+    pizzas: Vec<Pizza>,
+    #[serde(default)]
+    charged: Option<Charge>,
+    history: Vec<Transition>,
+}
+
+impl TryFrom<StoredFields> for Order {
+    type Error = String;
+
+    fn try_from(fields: StoredFields) -> Result<Order, String> {
+        match fields.history.first() {
+            Some(first) if first.status == OrderStatus::Seated => Ok(Order {
+                customer: fields.customer,
+                table: fields.table,
+                pizzas: fields.pizzas,
+                charged: fields.charged,
+                history: fields.history,
+            }),
+            Some(first) => Err(format!("the history of the order starts {} instead of seated", first.status)),
+            None => Err(String::from("the order has no history")),
+        }
+    }
+}
+
+impl Order {
+    pub fn seat(customer: &str, at: Timestamp) -> Order {
+        Order {
+            customer: String::from(customer),
+            table: None,
+            pizzas: Vec::new(),
+            charged: None,
+            history: vec![Transition { status: OrderStatus::Seated, at }],
+        }
+    }
+
+    pub fn status(&self) -> OrderStatus {
+        self.history[self.history.len() - 1].status
+    }
+
+    pub fn history(&self) -> &[Transition] {
+        &self.history
+    }
+
+    // When the order reached `status`, if it did
+    pub fn time_of(&self, status: OrderStatus) -> Option<Timestamp> {
+        self.history
+            .iter()
+            .find(|transition| transition.status == status)
+            .map(|transition| transition.at)
+    }
+
+    fn move_to(&mut self, status: OrderStatus, at: Timestamp) -> Result<(), OrderError> {
+        let from = self.status();
+        if !from.can_move_to(status) {
+            return Err(OrderError::InvalidTransition { from, to: status });
+        }
+        self.history.push(Transition { status, at });
+        Ok(())
+    }
+
+    pub fn place(&mut self, pizzas: Vec<Pizza>, at: Timestamp) -> Result<(), OrderError> {
+        let from = self.status();
+        if !from.can_move_to(OrderStatus::Ordered) {
+            return Err(OrderError::InvalidTransition { from, to: OrderStatus::Ordered });
+        }
+        if pizzas.is_empty() {
+            return Err(OrderError::NoPizzas);
+        }
+        self.pizzas = pizzas;
+        self.move_to(OrderStatus::Ordered, at)
+    }
+
+    pub fn send_to_kitchen(&mut self, at: Timestamp) -> Result<(), OrderError> {
+        self.move_to(OrderStatus::InKitchen, at)
+    }
+
+    pub fn mark_ready(&mut self, at: Timestamp) -> Result<(), OrderError> {
+        self.move_to(OrderStatus::Ready, at)
+    }
+
+    pub fn serve(&mut self, at: Timestamp) -> Result<(), OrderError> {
+        self.move_to(OrderStatus::Served, at)
+    }
+
+    pub fn pay(&mut self, at: Timestamp) -> Result<(), OrderError> {
+        self.move_to(OrderStatus::Paid, at)
+    }
+
+    pub fn cancel(&mut self, at: Timestamp) -> Result<(), OrderError> {
+        self.move_to(OrderStatus::Cancelled, at)
+    }
+}
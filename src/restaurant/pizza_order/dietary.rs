@@ -0,0 +1,183 @@
+// Allergens and diets
+// Every dough, cheese and topping says which allergens it has and which diets it fits. The doughs
+// and cheeses are always the same, so their tags are here, the toppings come from the menu, so their
+// tags are in menu.json. A pizza has the allergens of all its ingredients, and fits a diet only if all
+// of them fit it: a topping the menu doesn't tag fits no diet, when we don't know we don't promise.
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use super::menu::{Cheese, Dough, Menu, Topping};
+use super::Pizza;
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Allergen {
+    Gluten,
+    Dairy,
+    Fish,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Diet {
+    Vegetarian,
+    Vegan,
+    GlutenFree,
+}
+
+impl fmt::Display for Allergen {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Allergen::Gluten => "gluten",
+            Allergen::Dairy => "dairy",
+            Allergen::Fish => "fish",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+impl fmt::Display for Diet {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Diet::Vegetarian => "vegetarian",
+            Diet::Vegan => "vegan",
+            Diet::GlutenFree => "gluten-free",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+impl Dough {
+    pub fn allergens(&self) -> Vec<Allergen> {
+        match self {
+            Dough::GlutenFree => Vec::new(),
+            _ => vec![Allergen::Gluten],
+        }
+    }
+
+    pub fn diets(&self) -> Vec<Diet> {
+        match self {
+            Dough::GlutenFree => vec![Diet::Vegetarian, Diet::Vegan, Diet::GlutenFree],
+            _ => vec![Diet::Vegetarian, Diet::Vegan],
+        }
+    }
+}
+
+impl Cheese {
+    pub fn allergens(&self) -> Vec<Allergen> {
+        match self {
+            Cheese::Vegan | Cheese::NoCheese => Vec::new(),
+            _ => vec![Allergen::Dairy],
+        }
+    }
+
+    // Parmesan is made with animal rennet, so it's not even vegetarian
+    pub fn diets(&self) -> Vec<Diet> {
+        match self {
+            Cheese::Mozzarella | Cheese::Cheddar => vec![Diet::Vegetarian, Diet::GlutenFree],
+            Cheese::Parmesan => vec![Diet::GlutenFree],
+            Cheese::Vegan | Cheese::NoCheese => vec![Diet::Vegetarian, Diet::Vegan, Diet::GlutenFree],
+        }
+    }
+}
+
+// What a customer can't or won't eat
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct DietaryNeeds {
+    pub diets: Vec<Diet>,
+    pub allergies: Vec<Allergen>,
+}
+
+impl DietaryNeeds {
+    pub fn diet(diet: Diet) -> DietaryNeeds {
+        DietaryNeeds {
+            diets: vec![diet],
+            allergies: Vec::new(),
+        }
+    }
+
+    pub fn allergy(allergen: Allergen) -> DietaryNeeds {
+        DietaryNeeds {
+            diets: Vec::new(),
+            allergies: vec![allergen],
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.diets.is_empty() && self.allergies.is_empty()
+    }
+
+    // Why an ingredient with these tags is not for this customer, nothing when it is
+    fn problems(&self, ingredient: &str, allergens: &[Allergen], diets: &[Diet]) -> Vec<String> {
+        let mut problems = Vec::new();
+        for allergen in self.allergies.iter().filter(|allergen| allergens.contains(allergen)) {
+            problems.push(format!("{} has {}", ingredient, allergen));
+        }
+        for diet in self.diets.iter().filter(|diet| !diets.contains(diet)) {
+            problems.push(format!("{} is not {}", ingredient, diet));
+        }
+        problems
+    }
+
+    pub fn allows(&self, topping: &Topping) -> bool {
+        self.problems(&topping.name, &topping.allergens, &topping.diets).is_empty()
+    }
+
+    // Everything on the pizza the customer can't or won't eat, "ham is not vegetarian"
+    pub fn problems_with(&self, pizza: &Pizza) -> Vec<String> {
+        let mut problems = self.problems(&pizza.dough.to_string(), &pizza.dough.allergens(), &pizza.dough.diets());
+        problems.extend(self.problems(&pizza.cheese.to_string(), &pizza.cheese.allergens(), &pizza.cheese.diets()));
+        for topping in &pizza.toppings {
+            problems.extend(self.problems(&topping.name, &topping.allergens, &topping.diets));
+        }
+        problems
+    }
+
+    // The pizza has one of the allergies, a diet that doesn't fit is only a preference
+    pub fn is_allergic_to(&self, pizza: &Pizza) -> bool {
+        pizza.allergens().iter().any(|allergen| self.allergies.contains(allergen))
+    }
+
+    // The menu without the toppings the customer can't or won't eat
+    pub fn filter(&self, menu: &Menu) -> Menu {
+        let mut filtered = menu.clone();
+        filtered.toppings.retain(|topping| self.allows(topping));
+        filtered
+    }
+}
+
+// "vegan, no gluten"
+impl fmt::Display for DietaryNeeds {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut needs: Vec<String> = self.diets.iter().map(|diet| diet.to_string()).collect();
+        needs.extend(self.allergies.iter().map(|allergen| format!("no {}", allergen)));
+        write!(f, "{}", needs.join(", "))
+    }
+}
+
+impl Pizza {
+    // Every allergen of its ingredients, once
+    pub fn allergens(&self) -> Vec<Allergen> {
+        let mut allergens = self.dough.allergens();
+        allergens.extend(self.cheese.allergens());
+        for topping in &self.toppings {
+            allergens.extend(topping.allergens.iter().copied());
+        }
+        allergens.sort();
+        allergens.dedup();
+        allergens
+    }
+
+    // The diets all of its ingredients fit
+    pub fn diets(&self) -> Vec<Diet> {
+        let cheese = self.cheese.diets();
+        self.dough
+            .diets()
+            .into_iter()
+            .filter(|diet| cheese.contains(diet))
+            .filter(|diet| self.toppings.iter().all(|topping| topping.diets.contains(diet)))
+            .collect()
+    }
+}
@@ -0,0 +1,288 @@
+use std::fmt;
+use std::io::{self, Write};
+
+use super::dietary::DietaryNeeds;
+use super::{Pizza, PizzaBuilder, PizzaError, TakeawayBox};
+use crate::restaurant::billing::Bill;
+use crate::restaurant::clock::{format_duration, Clock};
+use crate::restaurant::counter::Counter;
+use crate::restaurant::delivery::{Address, DeliveryService, Quote};
+use crate::restaurant::inventory::{Inventory, InventoryError};
+use crate::restaurant::kitchen::Board;
+use crate::restaurant::order::{Charge, Order, OrderStatus};
+use crate::restaurant::seating::{Party, Restaurant, Seating};
+
+// Finds a table for the party. When every table is taken the party goes to the waitlist, and
+// there is no order until they are seated.
+pub fn seat_at_table(restaurant: &mut Restaurant, party: &Party, clock: &dyn Clock, out: &mut dyn Write) -> io::Result<Option<Order>> {
+    match restaurant.arrive(party.clone(), clock.now()) {
+        Ok(Seating::Seated(table)) => {
+            writeln!(out, "{} seated at table {}", party.name, table)?;
+            let mut order = Order::seat(&party.name, clock.now());
+            order.table = Some(table);
+            Ok(Some(order))
+        }
+        Ok(Seating::Waitlisted { position, estimated_wait_seconds }) => {
+            writeln!(
+                out,
+                "{} is number {} on the waitlist, about {} of wait",
+                party.name,
+                position,
+                format_duration(estimated_wait_seconds)
+            )?;
+            Ok(None)
+        }
+        Err(err) => {
+            writeln!(out, "Sorry {}, {}", party.name, err)?;
+            Ok(None)
+        }
+    }
+}
+
+// Why the waiter cancelled an order instead of placing it
+#[derive(Debug, PartialEq)]
+pub enum Refusal {
+    CantMake(PizzaError),
+    // What in the pizza doesn't suit the customer, one of them is an allergen
+    Allergens(Vec<String>),
+    OutOfStock(InventoryError),
+}
+
+impl fmt::Display for Refusal {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Refusal::CantMake(err) => write!(f, "we can't make that pizza: {}", err),
+            Refusal::Allergens(problems) => write!(f, "we won't serve that pizza: {}", problems.join(", ")),
+            Refusal::OutOfStock(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+// The customer reads the menu of the builder and makes a choice, if the kitchen can't make
+// that pizza we tell them why and the order is cancelled, instead of serving something else.
+// A customer with dietary needs only sees the toppings that suit them.
+pub fn order_pizza(
+    order: &mut Order,
+    choice: PizzaBuilder,
+    needs: &DietaryNeeds,
+    inventory: &mut Inventory,
+    clock: &dyn Clock,
+    out: &mut dyn Write,
+) -> io::Result<()> {
+    let menu = needs.filter(choice.menu());
+    if needs.is_empty() {
+        writeln!(out, "Today's toppings: {}", menu.topping_names().join(", "))?;
+    } else {
+        writeln!(out, "Today's toppings for {}: {}", needs, menu.topping_names().join(", "))?;
+    }
+    order_pizzas(order, vec![choice], needs, inventory, clock, out)?;
+    Ok(())
+}
+
+// Places the order with every pizza of the choices, or cancels it and returns why. When we ran out of
+// a topping, the pizza gets the closest one we have and we tell the customer. If a pizza doesn't fit
+// the diet of the customer we warn them, if it has one of their allergens we refuse it. The online
+// orders come here too, with all their pizzas at once.
+pub fn order_pizzas(
+    order: &mut Order,
+    choices: Vec<PizzaBuilder>,
+    needs: &DietaryNeeds,
+    inventory: &mut Inventory,
+    clock: &dyn Clock,
+    out: &mut dyn Write,
+) -> io::Result<Option<Refusal>> {
+    match make_pizzas(choices, needs, inventory) {
+        Ok((pizzas, notes)) => {
+            for note in notes {
+                writeln!(out, "{}", note)?;
+            }
+            order.place(pizzas, clock.now())?;
+            for alert in inventory.consume(&order.pizzas)? {
+                writeln!(out, "Low stock: {}", alert)?;
+            }
+            Ok(None)
+        }
+        Err(refusal) => {
+            writeln!(out, "Sorry, {}", refusal)?;
+            order.cancel(clock.now())?;
+            Ok(Some(refusal))
+        }
+    }
+}
+
+// The pizzas of the choices, as we can make them, and what the customer should know about them
+fn make_pizzas(choices: Vec<PizzaBuilder>, needs: &DietaryNeeds, inventory: &Inventory) -> Result<(Vec<Pizza>, Vec<String>), Refusal> {
+    // What is left after the pizzas before, two pizzas can't both get the last portion of a topping
+    let mut left = inventory.clone();
+    let mut pizzas = Vec::new();
+    let mut notes = Vec::new();
+    for choice in choices {
+        // The replacement of a topping we ran out of suits the customer too
+        let menu = needs.filter(choice.menu());
+        let cust_pizza = choice.build().map_err(Refusal::CantMake)?;
+        let problems = needs.problems_with(&cust_pizza);
+        if needs.is_allergic_to(&cust_pizza) {
+            return Err(Refusal::Allergens(problems));
+        }
+        if !problems.is_empty() {
+            notes.push(format!("Careful, {}", problems.join(", ")));
+        }
+
+        let (cust_pizza, swaps) = left.substitute(&cust_pizza, &menu).map_err(Refusal::OutOfStock)?;
+        for swap in swaps {
+            notes.push(format!("We ran out of {}, your pizza comes with {} instead", swap.missing, swap.replacement));
+        }
+        left.consume(std::slice::from_ref(&cust_pizza)).map_err(Refusal::OutOfStock)?;
+        pizzas.push(cust_pizza);
+    }
+    Ok((pizzas, notes))
+}
+
+// Seats the party and takes their order at the counter. The order is returned Ordered, ready to be
+// sent to the kitchen, or Cancelled, and there is no order when the party had to wait for a table.
+pub fn take_order(
+    restaurant: &mut Restaurant,
+    counter: &mut Counter,
+    party: &Party,
+    choice: PizzaBuilder,
+    clock: &dyn Clock,
+    out: &mut dyn Write,
+) -> io::Result<Option<Order>> {
+    let mut order = match seat_at_table(restaurant, party, clock, out)? {
+        Some(order) => order,
+        None => return Ok(None),
+    };
+    order_pizza(&mut order, choice, &party.needs, counter.inventory_mut(), clock, out)?;
+    Ok(Some(order))
+}
+
+// Takes an order for delivery: there is no table, the customer is only "seated" to start the
+// order, and the pizza goes to their address when it's ready. `board` is the board of the kitchen,
+// the pizza is cooked after the ones already on it. Returns when it should arrive, or nothing when
+// we don't deliver there or can't make the pizza.
+#[allow(clippy::too_many_arguments)]
+pub fn take_delivery(
+    counter: &mut Counter,
+    delivery: &mut DeliveryService,
+    board: &mut Board,
+    party: &Party,
+    address: &Address,
+    choice: PizzaBuilder,
+    clock: &dyn Clock,
+    out: &mut dyn Write,
+) -> io::Result<Option<Quote>> {
+    if let Err(err) = delivery.map().zone_of(address) {
+        writeln!(out, "Sorry {}, {}", party.name, err)?;
+        return Ok(None);
+    }
+    let mut order = Order::seat(&party.name, clock.now());
+    order_pizza(&mut order, choice, &party.needs, counter.inventory_mut(), clock, out)?;
+    if order.status() != OrderStatus::Ordered {
+        return Ok(None);
+    }
+    let quote = delivery.order(order, address.clone(), board, clock.now())?;
+    writeln!(
+        out,
+        "{}'s pizza goes to {} ({}), in about {}",
+        party.name,
+        address,
+        quote.zone,
+        format_duration(quote.eta.since(clock.now()))
+    )?;
+    Ok(Some(quote))
+}
+
+// Takes the pizzas the kitchen made to the table
+pub fn serve_customer(order: &mut Order, clock: &dyn Clock, out: &mut dyn Write) -> io::Result<()> {
+    order.serve(clock.now())?;
+    for cust_pizza in &order.pizzas {
+        writeln!(out, "The customer is served a {}", cust_pizza)?;
+    }
+    Ok(())
+}
+
+// The customer eats up to `slices` slices of each pizza and takes the rest home. The order keeps the
+// pizzas as they were served, for the bill and the history, the customer eats their own.
+pub fn eat_pizzas(order: &Order, slices: u32, out: &mut dyn Write) -> io::Result<Vec<TakeawayBox>> {
+    let mut boxes = Vec::new();
+    for served in &order.pizzas {
+        let mut cust_pizza = served.clone();
+        let eating = slices.min(cust_pizza.slices_left());
+        cust_pizza.eat(eating)?;
+        writeln!(out, "The customer eats {} of {} slices", eating, cust_pizza.slices())?;
+        if !cust_pizza.is_finished() {
+            let leftovers = cust_pizza.box_leftovers()?;
+            writeln!(out, "The customer takes home a {}", leftovers)?;
+            boxes.push(leftovers);
+        }
+    }
+    Ok(boxes)
+}
+
+// Takes the promotions of the counter off the bill, and the loyalty points of the customer when
+// they have enough. A coupon we don't know is not a reason to refuse the order, the bill just
+// doesn't get it.
+pub fn apply_promotions(
+    counter: &mut Counter,
+    order: &Order,
+    bill: Bill,
+    coupon: Option<&str>,
+    clock: &dyn Clock,
+    out: &mut dyn Write,
+) -> io::Result<Bill> {
+    let ordered_at = order.time_of(OrderStatus::Ordered).unwrap_or_else(|| clock.now());
+    let points = counter.loyalty().balance(&order.customer);
+    let applied = match counter.promotions().apply(&bill, ordered_at, coupon, points) {
+        Ok(applied) => applied,
+        Err(err) => {
+            writeln!(out, "Sorry, {}", err)?;
+            counter.promotions().apply(&bill, ordered_at, None, points)?
+        }
+    };
+    if applied.points_redeemed > 0 {
+        counter.redeem(&order.customer, applied.points_redeemed)?;
+    }
+    Ok(bill.with_discounts(applied.discounts))
+}
+
+// Gives the receipt to the customer, who pays it and earns loyalty points for what they paid. The
+// order keeps the charge, that's what the sales report counts
+pub fn charge_customer(
+    counter: &mut Counter,
+    order: &mut Order,
+    bill: &Bill,
+    clock: &dyn Clock,
+    out: &mut dyn Write,
+) -> io::Result<()> {
+    bill.print_receipt(out)?;
+    order.pay(clock.now())?;
+    let charge = Charge {
+        subtotal: bill.subtotal(),
+        discount: bill.discount(),
+    };
+    order.charged = Some(charge);
+    let points = counter.promotions().loyalty.points_for(charge.food());
+    if points > 0 {
+        let balance = counter.earn(&order.customer, points)?;
+        writeln!(out, "{} earns {} loyalty points, {} in total", order.customer, points, balance)?;
+    }
+    Ok(())
+}
+
+// The customer leaves, and the table goes to whoever was waiting for it. Returns the orders of the
+// parties that were seated from the waitlist.
+pub fn clear_table(restaurant: &mut Restaurant, order: &Order, clock: &dyn Clock, out: &mut dyn Write) -> io::Result<Vec<Order>> {
+    let table = match order.table {
+        Some(table) => table,
+        None => return Ok(Vec::new()),
+    };
+    writeln!(out, "Table {} is free", table)?;
+    let mut orders = Vec::new();
+    for (party, table) in restaurant.leave(table, clock.now())? {
+        writeln!(out, "{} seated at table {}", party.name, table)?;
+        let mut order = Order::seat(&party.name, clock.now());
+        order.table = Some(table);
+        orders.push(order);
+    }
+    Ok(orders)
+}
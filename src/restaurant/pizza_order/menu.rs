@@ -0,0 +1,168 @@
+// The menu of the restaurant
+// What doughs, cheeses and sizes we offer, the price of each size and topping, and the rules the
+// PizzaBuilder checks: how many toppings fit on a pizza and which toppings don't go together.
+// Each topping also has its allergens and the diets it fits, see dietary.rs.
+// The menu is a JSON file, menu.json next to this module is the one we use by default.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::io::{self, ErrorKind};
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::restaurant::billing::Money;
+use crate::restaurant::pizza_order::dietary::{Allergen, Diet};
+use crate::restaurant::pizza_order::Pizza;
+
+const STANDARD_MENU: &str = include_str!("menu.json");
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Dough {
+    Regular,
+    ThinCrust,
+    WholeWheat,
+    GlutenFree,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Cheese {
+    Mozzarella,
+    Cheddar,
+    Parmesan,
+    Vegan,
+    NoCheese,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Size {
+    Small,
+    Medium,
+    Large,
+}
+
+impl fmt::Display for Dough {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Dough::Regular => "regular dough",
+            Dough::ThinCrust => "thin crust",
+            Dough::WholeWheat => "whole wheat dough",
+            Dough::GlutenFree => "gluten-free dough",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+impl fmt::Display for Cheese {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Cheese::Mozzarella => "mozzarella",
+            Cheese::Cheddar => "cheddar",
+            Cheese::Parmesan => "parmesan",
+            Cheese::Vegan => "vegan cheese",
+            Cheese::NoCheese => "no cheese",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+impl Size {
+    // How many slices the pizza is cut in
+    pub fn slices(&self) -> u32 {
+        match self {
+            Size::Small => 6,
+            Size::Medium => 8,
+            Size::Large => 10,
+        }
+    }
+}
+
+impl fmt::Display for Size {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Size::Small => "small",
+            Size::Medium => "medium",
+            Size::Large => "large",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct Topping {
+    pub name: String,
+    // Prices are in cents, so we never have to round a float
+    pub price_cents: u32,
+    // A topping without tags has no allergens we know of, and fits no diet
+    #[serde(default)]
+    pub allergens: Vec<Allergen>,
+    #[serde(default)]
+    pub diets: Vec<Diet>,
+}
+
+impl Topping {
+    pub fn new(name: &str, price_cents: u32) -> Topping {
+        Topping {
+            name: String::from(name),
+            price_cents,
+            allergens: Vec::new(),
+            diets: Vec::new(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Menu {
+    pub doughs: Vec<Dough>,
+    pub cheeses: Vec<Cheese>,
+    // Every size we make, with the price of the pizza before the toppings, in cents
+    pub sizes: BTreeMap<Size, u32>,
+    pub toppings: Vec<Topping>,
+    pub max_toppings: usize,
+    // Pairs of toppings we refuse to put on the same pizza
+    #[serde(default)]
+    pub incompatible: Vec<(String, String)>,
+}
+
+impl Menu {
+    // The menu.json that comes with the tutorial, it's part of the binary so it can't be missing
+    pub fn standard() -> Menu {
+        Menu::from_json(STANDARD_MENU).expect("menu.json is a valid menu")
+    }
+
+    pub fn from_json(json: &str) -> io::Result<Menu> {
+        serde_json::from_str(json).map_err(|err| io::Error::new(ErrorKind::InvalidData, err))
+    }
+
+    pub fn load(path: &Path) -> io::Result<Menu> {
+        Menu::from_json(&fs::read_to_string(path)?)
+    }
+
+    pub fn topping(&self, name: &str) -> Option<&Topping> {
+        self.toppings.iter().find(|topping| topping.name == name)
+    }
+
+    // The size of the pizza plus its toppings, at the prices of this menu. A topping that is not on
+    // this menu keeps the price it had when the pizza was made
+    pub fn price_of(&self, pizza: &Pizza) -> Money {
+        let base = Money::cents(self.sizes.get(&pizza.size).copied().unwrap_or(0) as i64);
+        pizza.toppings.iter().fold(base, |price, topping| {
+            let cents = self.topping(&topping.name).unwrap_or(topping).price_cents;
+            price + Money::cents(cents as i64)
+        })
+    }
+
+    pub fn are_incompatible(&self, first: &str, second: &str) -> bool {
+        self.incompatible
+            .iter()
+            .any(|(a, b)| (a == first && b == second) || (a == second && b == first))
+    }
+
+    pub fn topping_names(&self) -> Vec<&str> {
+        self.toppings.iter().map(|topping| topping.name.as_str()).collect()
+    }
+}
@@ -0,0 +1,252 @@
+// Pizzas and how we order them
+// A Pizza is made of a size, a dough, a cheese and its toppings. Pizza::lunch is the quick
+// lunch special, any other pizza goes through the PizzaBuilder, which checks the choices against the menu.
+// Eating a pizza changes it, so eat takes &mut self, and boxing the leftovers takes the pizza itself:
+// once it's in the takeaway box it's not on the plate anymore, and the compiler makes sure of it.
+
+use std::error::Error;
+use std::fmt;
+use std::io;
+
+use serde::{Deserialize, Serialize};
+
+pub mod dietary;
+pub mod help_customer;
+pub mod menu;
+
+use menu::{Cheese, Dough, Menu, Size, Topping};
+
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct Pizza {
+    pub size: Size,
+    pub dough: Dough,
+    pub cheese: Cheese,
+    pub toppings: Vec<Topping>,
+    // Slices already eaten, the orders saved before pizzas had slices were never eaten
+    #[serde(default)]
+    pub eaten: u32,
+}
+
+impl Pizza {
+    // The lunch special doesn't go through the builder, the cook puts whatever topping we ask for
+    pub fn lunch(topping: &str) -> Pizza {
+        let topping = Menu::standard()
+            .topping(topping)
+            .cloned()
+            .unwrap_or_else(|| Topping::new(topping, 0));
+        Pizza {
+            size: Size::Medium,
+            dough: Dough::Regular,
+            cheese: Cheese::Mozzarella,
+            toppings: vec![topping],
+            eaten: 0,
+        }
+    }
+
+    pub fn slices(&self) -> u32 {
+        self.size.slices()
+    }
+
+    pub fn slices_left(&self) -> u32 {
+        self.slices().saturating_sub(self.eaten)
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.slices_left() == 0
+    }
+
+    // Eats some slices and returns how many are left
+    pub fn eat(&mut self, slices: u32) -> Result<u32, EatError> {
+        let left = self.slices_left();
+        if left == 0 {
+            return Err(EatError::Finished);
+        }
+        if slices > left {
+            return Err(EatError::NotEnoughSlices { asked: slices, left });
+        }
+        self.eaten += slices;
+        Ok(self.slices_left())
+    }
+
+    // What is left goes home in a box, there is nothing to box when the pizza is finished
+    pub fn box_leftovers(self) -> Result<TakeawayBox, EatError> {
+        if self.is_finished() {
+            return Err(EatError::Finished);
+        }
+        Ok(TakeawayBox { pizza: self })
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum EatError {
+    Finished,
+    NotEnoughSlices { asked: u32, left: u32 },
+}
+
+impl fmt::Display for EatError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            EatError::Finished => write!(f, "the pizza is already finished"),
+            EatError::NotEnoughSlices { asked, left } => {
+                write!(f, "{} slices were asked but only {} are left", asked, left)
+            }
+        }
+    }
+}
+
+impl Error for EatError {}
+
+impl From<EatError> for io::Error {
+    fn from(err: EatError) -> io::Error {
+        io::Error::other(err)
+    }
+}
+
+// The leftovers of a pizza, to eat at home
+#[derive(Clone, PartialEq, Debug)]
+pub struct TakeawayBox {
+    pizza: Pizza,
+}
+
+impl TakeawayBox {
+    pub fn pizza(&self) -> &Pizza {
+        &self.pizza
+    }
+
+    pub fn slices(&self) -> u32 {
+        self.pizza.slices_left()
+    }
+
+    pub fn eat(&mut self, slices: u32) -> Result<u32, EatError> {
+        self.pizza.eat(slices)
+    }
+}
+
+// "takeaway box with 2 slices of medium pizza with regular dough, mozzarella and veggies"
+impl fmt::Display for TakeawayBox {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let plural = if self.slices() == 1 { "" } else { "s" };
+        write!(f, "takeaway box with {} slice{} of {}", self.slices(), plural, self.pizza)
+    }
+}
+
+// "medium pizza with regular dough, mozzarella and veggies"
+impl fmt::Display for Pizza {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut ingredients = vec![self.dough.to_string(), self.cheese.to_string()];
+        ingredients.extend(self.toppings.iter().map(|topping| topping.name.clone()));
+        let last = ingredients.pop().unwrap_or_default();
+        write!(f, "{} pizza with {} and {}", self.size, ingredients.join(", "), last)
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum PizzaError {
+    // A dough, cheese or size the menu doesn't offer
+    NotOnMenu(String),
+    UnknownTopping(String),
+    DuplicateTopping(String),
+    TooManyToppings { max: usize, requested: usize },
+    Incompatible(String, String),
+}
+
+impl fmt::Display for PizzaError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PizzaError::NotOnMenu(item) => write!(f, "{} is not on the menu", item),
+            PizzaError::UnknownTopping(name) => write!(f, "we don't have {} as a topping", name),
+            PizzaError::DuplicateTopping(name) => write!(f, "{} was asked twice", name),
+            PizzaError::TooManyToppings { max, requested } => {
+                write!(f, "{} toppings were asked, a pizza fits at most {}", requested, max)
+            }
+            PizzaError::Incompatible(first, second) => write!(f, "{} and {} don't go together", first, second),
+        }
+    }
+}
+
+impl Error for PizzaError {}
+
+// Starts with the lunch defaults, medium, regular dough and mozzarella, and no toppings:
+// PizzaBuilder::new(&menu).size(Size::Large).topping("veggies").build()
+pub struct PizzaBuilder<'a> {
+    menu: &'a Menu,
+    size: Size,
+    dough: Dough,
+    cheese: Cheese,
+    toppings: Vec<String>,
+}
+
+impl<'a> PizzaBuilder<'a> {
+    pub fn new(menu: &'a Menu) -> PizzaBuilder<'a> {
+        PizzaBuilder {
+            menu,
+            size: Size::Medium,
+            dough: Dough::Regular,
+            cheese: Cheese::Mozzarella,
+            toppings: Vec::new(),
+        }
+    }
+
+    pub fn menu(&self) -> &'a Menu {
+        self.menu
+    }
+
+    pub fn size(mut self, size: Size) -> PizzaBuilder<'a> {
+        self.size = size;
+        self
+    }
+
+    pub fn dough(mut self, dough: Dough) -> PizzaBuilder<'a> {
+        self.dough = dough;
+        self
+    }
+
+    pub fn cheese(mut self, cheese: Cheese) -> PizzaBuilder<'a> {
+        self.cheese = cheese;
+        self
+    }
+
+    pub fn topping(mut self, name: &str) -> PizzaBuilder<'a> {
+        self.toppings.push(String::from(name));
+        self
+    }
+
+    pub fn build(&self) -> Result<Pizza, PizzaError> {
+        let menu = self.menu;
+        if !menu.sizes.contains_key(&self.size) {
+            return Err(PizzaError::NotOnMenu(format!("a {} pizza", self.size)));
+        }
+        if !menu.doughs.contains(&self.dough) {
+            return Err(PizzaError::NotOnMenu(self.dough.to_string()));
+        }
+        if !menu.cheeses.contains(&self.cheese) {
+            return Err(PizzaError::NotOnMenu(self.cheese.to_string()));
+        }
+        if self.toppings.len() > menu.max_toppings {
+            return Err(PizzaError::TooManyToppings {
+                max: menu.max_toppings,
+                requested: self.toppings.len(),
+            });
+        }
+
+        let mut toppings: Vec<Topping> = Vec::new();
+        for name in &self.toppings {
+            let topping = menu.topping(name).ok_or_else(|| PizzaError::UnknownTopping(name.clone()))?;
+            if toppings.contains(topping) {
+                return Err(PizzaError::DuplicateTopping(name.clone()));
+            }
+            if let Some(other) = toppings.iter().find(|other| menu.are_incompatible(&other.name, name)) {
+                return Err(PizzaError::Incompatible(other.name.clone(), name.clone()));
+            }
+            toppings.push(topping.clone());
+        }
+
+        Ok(Pizza {
+            size: self.size,
+            dough: self.dough,
+            cheese: self.cheese,
+            toppings,
+            eaten: 0,
+        })
+    }
+}
@@ -0,0 +1,255 @@
+// Promotions and loyalty points
+// The promotions of the restaurant are declared in a JSON file, promotions.json next to this module
+// is an example. Whatever their order in the file, they are applied in a fixed order, each one on
+// what is left to pay after the ones before it:
+//
+//   1. buy_get_free, free pizzas, the cheapest ones of the bill
+//   2. happy_hour, a percent off when the order was placed in a time window
+//   3. coupon, an amount or a percent off, when the customer gives its code
+//   4. loyalty points the customer has, when they are enough to redeem
+//
+// The happy hours are on the clocks of the restaurant, `utc_offset_minutes` says how far those are
+// from UTC, 0 when the file doesn't say. Every discount that applied is a line of the bill that
+// says why. The points are earned on what the customer paid for the food, after the discounts.
+
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::io::{self, ErrorKind};
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::restaurant::billing::{Bill, Discount, Money, Rate};
+use crate::restaurant::clock::{Timestamp, UtcOffset};
+
+const STANDARD_PROMOTIONS: &str = include_str!("promotions.json");
+
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Rule {
+    // For every `buy` pizzas, the next `free` ones are free
+    BuyGetFree { buy: usize, free: usize },
+    // Hours on the clocks of the restaurant, `to_hour` is not included, and 22 to 2 goes past midnight
+    HappyHour { from_hour: u64, to_hour: u64, percent: u32 },
+    // Either a fixed amount or a percent off
+    Coupon {
+        code: String,
+        #[serde(default)]
+        amount_cents: i64,
+        #[serde(default)]
+        percent: u32,
+    },
+}
+
+impl Rule {
+    fn precedence(&self) -> u8 {
+        match self {
+            Rule::BuyGetFree { .. } => 1,
+            Rule::HappyHour { .. } => 2,
+            Rule::Coupon { .. } => 3,
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct Promotion {
+    pub name: String,
+    #[serde(flatten)]
+    pub rule: Rule,
+}
+
+// The default program gives no points
+#[derive(Clone, Copy, PartialEq, Debug, Default, Serialize, Deserialize)]
+pub struct LoyaltyProgram {
+    pub points_per_dollar: u32,
+    pub point_value_cents: u32,
+    // Points are only redeemed once the customer has this many
+    pub min_redeem: u32,
+}
+
+impl LoyaltyProgram {
+    pub fn points_for(&self, paid: Money) -> u32 {
+        (paid.as_cents().max(0) / 100) as u32 * self.points_per_dollar
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum PromotionError {
+    UnknownCoupon(String),
+    NotEnoughPoints { customer: String, balance: u32, requested: u32 },
+}
+
+impl fmt::Display for PromotionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PromotionError::UnknownCoupon(code) => write!(f, "there is no coupon {}", code),
+            PromotionError::NotEnoughPoints { customer, balance, requested } => {
+                write!(f, "{} has {} points, {} can't be redeemed", customer, balance, requested)
+            }
+        }
+    }
+}
+
+impl Error for PromotionError {}
+
+impl From<PromotionError> for io::Error {
+    fn from(err: PromotionError) -> io::Error {
+        io::Error::other(err)
+    }
+}
+
+// The discounts of a bill, and the points they use
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct Applied {
+    pub discounts: Vec<Discount>,
+    pub points_redeemed: u32,
+}
+
+#[derive(Clone, PartialEq, Debug, Default, Serialize, Deserialize)]
+pub struct Promotions {
+    #[serde(default, rename = "utc_offset_minutes")]
+    pub utc_offset: UtcOffset,
+    pub promotions: Vec<Promotion>,
+    #[serde(default)]
+    pub loyalty: LoyaltyProgram,
+}
+
+impl Promotions {
+    // The promotions.json that comes with the tutorial
+    pub fn standard() -> Promotions {
+        Promotions::from_json(STANDARD_PROMOTIONS).expect("promotions.json has valid promotions")
+    }
+
+    pub fn from_json(json: &str) -> io::Result<Promotions> {
+        serde_json::from_str(json).map_err(|err| io::Error::new(ErrorKind::InvalidData, err))
+    }
+
+    pub fn load(path: &Path) -> io::Result<Promotions> {
+        Promotions::from_json(&fs::read_to_string(path)?)
+    }
+
+    // The promotions.json of the config directory `dir`, or the ones that come with the tutorial
+    // when there is none
+    pub fn open(dir: &Path) -> io::Result<Promotions> {
+        match Promotions::load(&dir.join("promotions.json")) {
+            Ok(promotions) => Ok(promotions),
+            Err(err) if err.kind() == ErrorKind::NotFound => Ok(Promotions::standard()),
+            Err(err) => Err(err),
+        }
+    }
+
+    // The discounts of the bill for an order placed at `ordered_at`, with the coupon the customer
+    // gave, if any, and the loyalty points they have
+    pub fn apply(&self, bill: &Bill, ordered_at: Timestamp, coupon: Option<&str>, points: u32) -> Result<Applied, PromotionError> {
+        if let Some(code) = coupon {
+            let known = self
+                .promotions
+                .iter()
+                .any(|promotion| matches!(&promotion.rule, Rule::Coupon { code: known, .. } if known.eq_ignore_ascii_case(code)));
+            if !known {
+                return Err(PromotionError::UnknownCoupon(String::from(code)));
+            }
+        }
+
+        let mut promotions: Vec<&Promotion> = self.promotions.iter().collect();
+        // A stable sort, promotions of the same kind keep the order of the file
+        promotions.sort_by_key(|promotion| promotion.rule.precedence());
+
+        let mut applied = Applied::default();
+        let mut left = bill.subtotal();
+        for promotion in promotions {
+            let discount = match discount_of(promotion, bill, left, ordered_at.local(self.utc_offset), coupon) {
+                Some(discount) if discount.amount > Money::ZERO => discount,
+                _ => continue,
+            };
+            left -= discount.amount;
+            applied.discounts.push(discount);
+        }
+
+        let program = self.loyalty;
+        if program.point_value_cents > 0 && points >= program.min_redeem {
+            let redeemed = points.min((left.as_cents().max(0) / program.point_value_cents as i64) as u32);
+            if redeemed > 0 {
+                applied.discounts.push(Discount {
+                    description: format!("Loyalty: {} points redeemed", redeemed),
+                    amount: Money::cents(program.point_value_cents as i64).times(redeemed as i64),
+                });
+                applied.points_redeemed = redeemed;
+            }
+        }
+        Ok(applied)
+    }
+}
+
+// What the promotion takes off, `left` is what there is to pay after the promotions before it, and
+// `ordered_at` is on the clocks of the restaurant
+fn discount_of(promotion: &Promotion, bill: &Bill, left: Money, ordered_at: Timestamp, coupon: Option<&str>) -> Option<Discount> {
+    let (amount, why) = match &promotion.rule {
+        Rule::BuyGetFree { buy, free } => {
+            let group = buy + free;
+            let free_pizzas = bill.lines.len().checked_div(group).unwrap_or(0) * free;
+            let mut prices: Vec<Money> = bill.lines.iter().map(|line| line.amount).collect();
+            prices.sort();
+            let amount: Money = prices.iter().take(free_pizzas).copied().sum();
+            (amount, format!("{} of {} pizzas free", free_pizzas, bill.lines.len()))
+        }
+        Rule::HappyHour { from_hour, to_hour, percent } => {
+            let hour = ordered_at.hour();
+            let inside = if from_hour <= to_hour {
+                *from_hour <= hour && hour < *to_hour
+            } else {
+                *from_hour <= hour || hour < *to_hour
+            };
+            if !inside {
+                return None;
+            }
+            let why = format!("{}% off from {:02}:00 to {:02}:00", percent, from_hour, to_hour);
+            (left.apply(Rate::percent(*percent)), why)
+        }
+        Rule::Coupon { code, amount_cents, percent } => {
+            if !coupon.is_some_and(|given| given.eq_ignore_ascii_case(code)) {
+                return None;
+            }
+            let amount = Money::cents(*amount_cents) + left.apply(Rate::percent(*percent));
+            (amount, format!("coupon {}", code))
+        }
+    };
+    Some(Discount {
+        description: format!("{}: {}", promotion.name, why),
+        amount: amount.min(left),
+    })
+}
+
+// The points of every customer, by name
+#[derive(Clone, PartialEq, Debug, Default, Serialize, Deserialize)]
+pub struct LoyaltyAccounts {
+    points: BTreeMap<String, u32>,
+}
+
+impl LoyaltyAccounts {
+    pub fn balance(&self, customer: &str) -> u32 {
+        self.points.get(customer).copied().unwrap_or(0)
+    }
+
+    // Returns the new balance
+    pub fn earn(&mut self, customer: &str, points: u32) -> u32 {
+        let balance = self.points.entry(String::from(customer)).or_insert(0);
+        *balance += points;
+        *balance
+    }
+
+    pub fn redeem(&mut self, customer: &str, points: u32) -> Result<u32, PromotionError> {
+        let balance = self.balance(customer);
+        if points > balance {
+            return Err(PromotionError::NotEnoughPoints {
+                customer: String::from(customer),
+                balance,
+                requested: points,
+            });
+        }
+        self.points.insert(String::from(customer), balance - points);
+        Ok(balance - points)
+    }
+}
@@ -0,0 +1,218 @@
+// The end of the day
+// The DailyReport reads the orders of a day from the order history and sums them up: what we sold,
+// how much it made, when we were the busiest and who our best customers were. Revenue is what the
+// paid orders were charged for the food, after the discounts and before taxes and tips, which are not
+// ours. An order saved before orders kept their charge is counted at the prices of the menu. The day
+// and the hours are the ones of the clocks of the restaurant, given as a UtcOffset.
+// The report prints as a table for the terminal, or as CSV and JSON for a spreadsheet or another program.
+
+use std::collections::BTreeMap;
+use std::io::{self, ErrorKind, Write};
+
+use serde::Serialize;
+
+use crate::restaurant::billing::Money;
+use crate::restaurant::clock::UtcOffset;
+use crate::restaurant::order::OrderStatus;
+use crate::restaurant::pizza_order::menu::Menu;
+use crate::restaurant::store::{OrderFilter, OrderStore, StoredOrder};
+
+// How many customers the report ranks
+const TOP_CUSTOMERS: usize = 5;
+
+#[derive(Clone, PartialEq, Debug, Serialize)]
+pub struct ToppingSales {
+    pub topping: String,
+    pub pizzas: usize,
+}
+
+#[derive(Clone, Copy, PartialEq, Debug, Serialize)]
+pub struct HourSales {
+    // From 0 to 23, on the clocks of the restaurant
+    pub hour: u64,
+    pub orders: usize,
+}
+
+#[derive(Clone, PartialEq, Debug, Serialize)]
+pub struct CustomerSales {
+    pub customer: String,
+    pub orders: usize,
+    #[serde(rename = "spent_cents")]
+    pub spent: Money,
+}
+
+#[derive(Clone, PartialEq, Debug, Serialize)]
+pub struct DailyReport {
+    pub date: String,
+    pub orders: usize,
+    pub paid_orders: usize,
+    pub cancelled_orders: usize,
+    pub pizzas_sold: usize,
+    #[serde(rename = "revenue_cents")]
+    pub revenue: Money,
+    // Revenue divided by the paid orders, zero when nobody paid
+    #[serde(rename = "average_ticket_cents")]
+    pub average_ticket: Money,
+    // The hour the most parties were seated, the earliest one when it's a tie
+    pub busiest_hour: Option<HourSales>,
+    // The most sold first
+    pub toppings: Vec<ToppingSales>,
+    // The ones who spent the most first
+    pub top_customers: Vec<CustomerSales>,
+}
+
+impl DailyReport {
+    // `date` is the day the parties were seated, "2024-03-15" in UTC like every Timestamp
+    pub fn for_date(store: &OrderStore, date: &str, menu: &Menu) -> DailyReport {
+        DailyReport::for_local_date(store, date, menu, UtcOffset::default())
+    }
+
+    // The same day on the clocks of the restaurant, `utc_offset` from UTC
+    pub fn for_local_date(store: &OrderStore, date: &str, menu: &Menu, utc_offset: UtcOffset) -> DailyReport {
+        let filter = OrderFilter {
+            date: Some(String::from(date)),
+            utc_offset,
+            ..OrderFilter::default()
+        };
+        DailyReport::from_orders(date, &store.find(&filter), menu, utc_offset)
+    }
+
+    pub fn from_orders(date: &str, orders: &[&StoredOrder], menu: &Menu, utc_offset: UtcOffset) -> DailyReport {
+        let priced: Vec<(&StoredOrder, &Menu)> = orders.iter().map(|stored| (*stored, menu)).collect();
+        DailyReport::from_priced(date, &priced, utc_offset)
+    }
+
+    // Every order with the menu it was sold from, the branches of a chain don't have the same prices
+    pub fn from_priced(date: &str, orders: &[(&StoredOrder, &Menu)], utc_offset: UtcOffset) -> DailyReport {
+        let paid: Vec<(&StoredOrder, &Menu)> = orders
+            .iter()
+            .copied()
+            .filter(|(stored, _)| stored.order.status() == OrderStatus::Paid)
+            .collect();
+        let spent = |stored: &StoredOrder, menu: &Menu| -> Money {
+            match stored.order.charged {
+                Some(charge) => charge.food(),
+                None => stored.order.pizzas.iter().map(|pizza| menu.price_of(pizza)).sum(),
+            }
+        };
+        let revenue: Money = paid.iter().map(|(stored, menu)| spent(stored, menu)).sum();
+
+        let mut hours: BTreeMap<u64, usize> = BTreeMap::new();
+        for (stored, _) in orders {
+            *hours.entry(stored.order.history()[0].at.local(utc_offset).hour()).or_insert(0) += 1;
+        }
+        let busiest_hour = hours
+            .iter()
+            .max_by(|a, b| a.1.cmp(b.1).then(b.0.cmp(a.0)))
+            .map(|(hour, orders)| HourSales { hour: *hour, orders: *orders });
+
+        let mut toppings: BTreeMap<&str, usize> = BTreeMap::new();
+        for pizza in paid.iter().flat_map(|(stored, _)| &stored.order.pizzas) {
+            for topping in &pizza.toppings {
+                *toppings.entry(&topping.name).or_insert(0) += 1;
+            }
+        }
+        let mut toppings: Vec<ToppingSales> = toppings
+            .into_iter()
+            .map(|(topping, pizzas)| ToppingSales { topping: String::from(topping), pizzas })
+            .collect();
+        toppings.sort_by(|a, b| b.pizzas.cmp(&a.pizzas).then(a.topping.cmp(&b.topping)));
+
+        let mut customers: Vec<CustomerSales> = Vec::new();
+        for (stored, menu) in &paid {
+            match customers.iter_mut().find(|customer| customer.customer == stored.order.customer) {
+                Some(customer) => {
+                    customer.orders += 1;
+                    customer.spent += spent(stored, menu);
+                }
+                None => customers.push(CustomerSales {
+                    customer: stored.order.customer.clone(),
+                    orders: 1,
+                    spent: spent(stored, menu),
+                }),
+            }
+        }
+        customers.sort_by(|a, b| b.spent.cmp(&a.spent).then(a.customer.cmp(&b.customer)));
+        customers.truncate(TOP_CUSTOMERS);
+
+        DailyReport {
+            date: String::from(date),
+            orders: orders.len(),
+            paid_orders: paid.len(),
+            cancelled_orders: orders.iter().filter(|(stored, _)| stored.order.status() == OrderStatus::Cancelled).count(),
+            pizzas_sold: paid.iter().map(|(stored, _)| stored.order.pizzas.len()).sum(),
+            revenue,
+            average_ticket: if paid.is_empty() { Money::ZERO } else { Money::cents(revenue.as_cents() / paid.len() as i64) },
+            busiest_hour,
+            toppings,
+            top_customers: customers,
+        }
+    }
+
+    pub fn print(&self, out: &mut dyn Write) -> io::Result<()> {
+        writeln!(out, "Sales of {}", self.date)?;
+        writeln!(out, "{:<20} {:>12}", "Orders", self.orders)?;
+        writeln!(out, "{:<20} {:>12}", "Paid", self.paid_orders)?;
+        writeln!(out, "{:<20} {:>12}", "Cancelled", self.cancelled_orders)?;
+        writeln!(out, "{:<20} {:>12}", "Pizzas sold", self.pizzas_sold)?;
+        writeln!(out, "{:<20} {:>12}", "Revenue", self.revenue)?;
+        writeln!(out, "{:<20} {:>12}", "Average ticket", self.average_ticket)?;
+        if let Some(busiest) = self.busiest_hour {
+            let hours = format!("{:02}:00-{:02}:00", busiest.hour, busiest.hour + 1);
+            writeln!(out, "{:<20} {:>12} ({} orders)", "Busiest hour", hours, busiest.orders)?;
+        }
+
+        writeln!(out)?;
+        writeln!(out, "{:<20} {:>12}", "Topping", "Pizzas")?;
+        for sales in &self.toppings {
+            writeln!(out, "{:<20} {:>12}", sales.topping, sales.pizzas)?;
+        }
+
+        writeln!(out)?;
+        writeln!(out, "{:<20} {:>12} {:>12}", "Customer", "Orders", "Spent")?;
+        for customer in &self.top_customers {
+            writeln!(out, "{:<20} {:>12} {:>12}", customer.customer, customer.orders, customer.spent)?;
+        }
+        Ok(())
+    }
+
+    // One row per number, `section,name,count,cents`, amounts in cents like in the JSON
+    pub fn write_csv(&self, out: &mut dyn Write) -> io::Result<()> {
+        writeln!(out, "section,name,count,cents")?;
+        let mut row = |section: &str, name: &str, count: Option<usize>, cents: Option<Money>| {
+            let count = count.map(|count| count.to_string()).unwrap_or_default();
+            let cents = cents.map(|cents| cents.as_cents().to_string()).unwrap_or_default();
+            writeln!(out, "{},{},{},{}", section, csv_field(name), count, cents)
+        };
+        row("summary", "orders", Some(self.orders), None)?;
+        row("summary", "paid_orders", Some(self.paid_orders), None)?;
+        row("summary", "cancelled_orders", Some(self.cancelled_orders), None)?;
+        row("summary", "pizzas_sold", Some(self.pizzas_sold), None)?;
+        row("summary", "revenue", None, Some(self.revenue))?;
+        row("summary", "average_ticket", None, Some(self.average_ticket))?;
+        if let Some(busiest) = self.busiest_hour {
+            row("busiest_hour", &format!("{:02}:00", busiest.hour), Some(busiest.orders), None)?;
+        }
+        for sales in &self.toppings {
+            row("topping", &sales.topping, Some(sales.pizzas), None)?;
+        }
+        for customer in &self.top_customers {
+            row("customer", &customer.customer, Some(customer.orders), Some(customer.spent))?;
+        }
+        Ok(())
+    }
+
+    pub fn write_json(&self, out: &mut dyn Write) -> io::Result<()> {
+        serde_json::to_writer_pretty(&mut *out, self).map_err(|err| io::Error::new(ErrorKind::InvalidData, err))?;
+        writeln!(out)
+    }
+}
+
+// Names with a comma or a quote go between quotes, with their quotes doubled
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        String::from(field)
+    }
+}
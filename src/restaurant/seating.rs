@@ -0,0 +1,363 @@
+// The dining room
+// The Restaurant knows its floor plan: the tables, how many people fit in each one, who is sitting
+// where and since when. A party gets the smallest free table that fits them, or goes to the waitlist
+// with an estimate of the wait. Reservations hold a table for a time slot, and every party that
+// leaves is recorded, so we can see how many parties each table served. That's all the Restaurant
+// does, what the parties order and pay goes through the Counter.
+
+use std::error::Error;
+use std::fmt;
+use std::io::{self, Write};
+
+use crate::restaurant::clock::{format_duration, Timestamp};
+use crate::restaurant::pizza_order::dietary::DietaryNeeds;
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Party {
+    pub name: String,
+    pub size: u32,
+    // What the party can't or won't eat, the waiter shows them the menu with that in mind
+    pub needs: DietaryNeeds,
+}
+
+impl Party {
+    pub fn new(name: &str, size: u32) -> Party {
+        Party {
+            name: String::from(name),
+            size,
+            needs: DietaryNeeds::default(),
+        }
+    }
+
+    pub fn with_needs(mut self, needs: DietaryNeeds) -> Party {
+        self.needs = needs;
+        self
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Table {
+    pub number: u32,
+    pub capacity: u32,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct Reservation {
+    pub party: Party,
+    pub table: u32,
+    pub at: Timestamp,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct WaitingParty {
+    pub party: Party,
+    pub since: Timestamp,
+}
+
+// A party that sat at a table and left
+#[derive(Clone, PartialEq, Debug)]
+pub struct Visit {
+    pub table: u32,
+    pub party: Party,
+    pub seated_at: Timestamp,
+    pub left_at: Timestamp,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct TableTurnover {
+    pub table: u32,
+    pub parties: usize,
+    pub average_stay_seconds: u64,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub enum Seating {
+    Seated(u32),
+    // Position 1 is the next party to be seated
+    Waitlisted { position: usize, estimated_wait_seconds: u64 },
+}
+
+#[derive(Debug, PartialEq)]
+pub enum SeatingError {
+    // Not even the biggest table fits the party
+    PartyTooBig(u32),
+    UnknownTable(u32),
+    TableNotOccupied(u32),
+    TableOccupied(u32),
+    NoTableForSlot(Timestamp),
+    NoReservation(String),
+}
+
+impl fmt::Display for SeatingError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SeatingError::PartyTooBig(size) => write!(f, "no table fits a party of {}", size),
+            SeatingError::UnknownTable(table) => write!(f, "there is no table {}", table),
+            SeatingError::TableNotOccupied(table) => write!(f, "nobody is sitting at table {}", table),
+            SeatingError::TableOccupied(table) => write!(f, "table {} is still occupied", table),
+            SeatingError::NoTableForSlot(at) => write!(f, "every table is reserved around {}", at),
+            SeatingError::NoReservation(name) => write!(f, "there is no reservation for {}", name),
+        }
+    }
+}
+
+impl Error for SeatingError {}
+
+impl From<SeatingError> for io::Error {
+    fn from(err: SeatingError) -> io::Error {
+        io::Error::other(err)
+    }
+}
+
+struct Occupant {
+    party: Party,
+    since: Timestamp,
+}
+
+struct FloorTable {
+    table: Table,
+    occupant: Option<Occupant>,
+}
+
+pub struct Restaurant {
+    floor: Vec<FloorTable>,
+    // How long a party usually stays, it's the length of a reservation slot and the base of the wait estimates
+    meal_seconds: u64,
+    waitlist: Vec<WaitingParty>,
+    reservations: Vec<Reservation>,
+    visits: Vec<Visit>,
+}
+
+impl Restaurant {
+    pub fn new(tables: Vec<Table>, meal_seconds: u64) -> Restaurant {
+        Restaurant {
+            floor: tables.into_iter().map(|table| FloorTable { table, occupant: None }).collect(),
+            meal_seconds,
+            waitlist: Vec::new(),
+            reservations: Vec::new(),
+            visits: Vec::new(),
+        }
+    }
+
+    // Two tables for two, two for four, one for six and one for eight, and meals of 45 minutes
+    pub fn standard() -> Restaurant {
+        let capacities = [2, 2, 4, 4, 6, 8];
+        let tables = capacities
+            .iter()
+            .zip(1..)
+            .map(|(capacity, number)| Table { number, capacity: *capacity })
+            .collect();
+        Restaurant::new(tables, 45 * 60)
+    }
+
+    pub fn tables(&self) -> Vec<Table> {
+        self.floor.iter().map(|floor_table| floor_table.table).collect()
+    }
+
+    pub fn occupant(&self, table: u32) -> Option<&Party> {
+        self.floor_table(table)?.occupant.as_ref().map(|occupant| &occupant.party)
+    }
+
+    pub fn waitlist(&self) -> &[WaitingParty] {
+        &self.waitlist
+    }
+
+    pub fn reservations(&self) -> &[Reservation] {
+        &self.reservations
+    }
+
+    pub fn visits(&self) -> &[Visit] {
+        &self.visits
+    }
+
+    fn floor_table(&self, table: u32) -> Option<&FloorTable> {
+        self.floor.iter().find(|floor_table| floor_table.table.number == table)
+    }
+
+    fn biggest_table(&self) -> u32 {
+        self.floor.iter().map(|floor_table| floor_table.table.capacity).max().unwrap_or(0)
+    }
+
+    // Two visits of `meal_seconds` starting at `first` and `second` would overlap
+    fn overlaps(&self, first: Timestamp, second: Timestamp) -> bool {
+        first.since(second) < self.meal_seconds && second.since(first) < self.meal_seconds
+    }
+
+    fn reserved_around(&self, table: u32, at: Timestamp) -> bool {
+        self.reservations
+            .iter()
+            .any(|reservation| reservation.table == table && self.overlaps(reservation.at, at))
+    }
+
+    // The smallest free table that fits the party and isn't reserved for the time they'd be eating
+    fn free_table_for(&self, size: u32, at: Timestamp) -> Option<u32> {
+        self.floor
+            .iter()
+            .filter(|floor_table| floor_table.occupant.is_none() && floor_table.table.capacity >= size)
+            .filter(|floor_table| !self.reserved_around(floor_table.table.number, at))
+            .min_by_key(|floor_table| (floor_table.table.capacity, floor_table.table.number))
+            .map(|floor_table| floor_table.table.number)
+    }
+
+    fn sit(&mut self, table: u32, party: Party, at: Timestamp) {
+        if let Some(floor_table) = self.floor.iter_mut().find(|floor_table| floor_table.table.number == table) {
+            floor_table.occupant = Some(Occupant { party, since: at });
+        }
+    }
+
+    // A party walks in: they get a table now, or a place in the waitlist
+    pub fn arrive(&mut self, party: Party, at: Timestamp) -> Result<Seating, SeatingError> {
+        if party.size > self.biggest_table() {
+            return Err(SeatingError::PartyTooBig(party.size));
+        }
+        if let Some(table) = self.free_table_for(party.size, at) {
+            self.sit(table, party, at);
+            return Ok(Seating::Seated(table));
+        }
+
+        let estimated_wait_seconds = self.estimated_wait(party.size, at);
+        self.waitlist.push(WaitingParty { party, since: at });
+        Ok(Seating::Waitlisted {
+            position: self.waitlist.len(),
+            estimated_wait_seconds,
+        })
+    }
+
+    // When a party of `size` arriving now would get a table: every table that fits them is expected to
+    // be free `meal_seconds` after its party sat down, and each party already waiting takes the first
+    // one that frees up before us. It's an estimate, a small party may find a small table sooner
+    pub fn estimated_wait(&self, size: u32, at: Timestamp) -> u64 {
+        let mut free_at: Vec<Timestamp> = self
+            .floor
+            .iter()
+            .filter(|floor_table| floor_table.table.capacity >= size)
+            .map(|floor_table| match &floor_table.occupant {
+                Some(occupant) => occupant.since.plus(self.meal_seconds).max(at),
+                // A free table that is reserved is taken by the party with the reservation
+                None if self.reserved_around(floor_table.table.number, at) => at.plus(self.meal_seconds),
+                None => at,
+            })
+            .collect();
+        if free_at.is_empty() {
+            return 0;
+        }
+
+        for _ in &self.waitlist {
+            free_at.sort();
+            free_at[0] = free_at[0].plus(self.meal_seconds);
+        }
+        free_at.iter().min().map(|first| first.since(at)).unwrap_or(0)
+    }
+
+    // The party of `table` pays and leaves, and the parties in the waitlist that fit a free table
+    // are seated, in the order they arrived. Returns who was seated and where.
+    pub fn leave(&mut self, table: u32, at: Timestamp) -> Result<Vec<(Party, u32)>, SeatingError> {
+        let floor_table = self
+            .floor
+            .iter_mut()
+            .find(|floor_table| floor_table.table.number == table)
+            .ok_or(SeatingError::UnknownTable(table))?;
+        let occupant = floor_table.occupant.take().ok_or(SeatingError::TableNotOccupied(table))?;
+        self.visits.push(Visit {
+            table,
+            party: occupant.party,
+            seated_at: occupant.since,
+            left_at: at,
+        });
+
+        let mut seated = Vec::new();
+        let mut position = 0;
+        while position < self.waitlist.len() {
+            match self.free_table_for(self.waitlist[position].party.size, at) {
+                Some(free) => {
+                    let waiting = self.waitlist.remove(position);
+                    self.sit(free, waiting.party.clone(), at);
+                    seated.push((waiting.party, free));
+                }
+                None => position += 1,
+            }
+        }
+        Ok(seated)
+    }
+
+    // Holds the smallest table that fits the party and is not reserved around `at`
+    pub fn reserve(&mut self, party: Party, at: Timestamp) -> Result<Reservation, SeatingError> {
+        if party.size > self.biggest_table() {
+            return Err(SeatingError::PartyTooBig(party.size));
+        }
+        let table = self
+            .floor
+            .iter()
+            .filter(|floor_table| floor_table.table.capacity >= party.size)
+            .filter(|floor_table| !self.reserved_around(floor_table.table.number, at))
+            .min_by_key(|floor_table| (floor_table.table.capacity, floor_table.table.number))
+            .map(|floor_table| floor_table.table.number)
+            .ok_or(SeatingError::NoTableForSlot(at))?;
+
+        let reservation = Reservation { party, table, at };
+        self.reservations.push(reservation.clone());
+        Ok(reservation)
+    }
+
+    // The party with a reservation arrived, they sit at the table we held for them
+    pub fn check_in(&mut self, name: &str, at: Timestamp) -> Result<u32, SeatingError> {
+        let index = self
+            .reservations
+            .iter()
+            .position(|reservation| reservation.party.name == name)
+            .ok_or_else(|| SeatingError::NoReservation(String::from(name)))?;
+        let table = self.reservations[index].table;
+        if self.occupant(table).is_some() {
+            return Err(SeatingError::TableOccupied(table));
+        }
+        let reservation = self.reservations.remove(index);
+        self.sit(table, reservation.party, at);
+        Ok(table)
+    }
+
+    // How many parties each table served, and how long they stayed on average
+    pub fn turnover(&self) -> Vec<TableTurnover> {
+        self.floor
+            .iter()
+            .map(|floor_table| {
+                let number = floor_table.table.number;
+                let stays: Vec<u64> = self
+                    .visits
+                    .iter()
+                    .filter(|visit| visit.table == number)
+                    .map(|visit| visit.left_at.since(visit.seated_at))
+                    .collect();
+                TableTurnover {
+                    table: number,
+                    parties: stays.len(),
+                    average_stay_seconds: if stays.is_empty() { 0 } else { stays.iter().sum::<u64>() / stays.len() as u64 },
+                }
+            })
+            .collect()
+    }
+
+    pub fn print(&self, at: Timestamp, out: &mut dyn Write) -> io::Result<()> {
+        writeln!(out, "{:>5}  {:>5}  {:<16} {:>8}", "Table", "Seats", "Party", "For")?;
+        for floor_table in &self.floor {
+            let (party, time) = match &floor_table.occupant {
+                Some(occupant) => (
+                    format!("{} ({})", occupant.party.name, occupant.party.size),
+                    format_duration(at.since(occupant.since)),
+                ),
+                None => (String::from("-"), String::new()),
+            };
+            writeln!(out, "{:>5}  {:>5}  {:<16} {:>8}", floor_table.table.number, floor_table.table.capacity, party, time)?;
+        }
+        for (position, waiting) in self.waitlist.iter().enumerate() {
+            writeln!(
+                out,
+                "Waiting {}: {} ({}) for {}",
+                position + 1,
+                waiting.party.name,
+                waiting.party.size,
+                format_duration(at.since(waiting.since))
+            )?;
+        }
+        Ok(())
+    }
+}
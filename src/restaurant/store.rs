@@ -0,0 +1,240 @@
+// The order history
+// orders.jsonl has one JSON object per line, {"id": 1, "order": {...}}. We only ever append to it:
+// a new order is a new line, and a change to an order is a new line with the same id, the last line
+// of an id wins when the file is loaded. If the program dies in the middle of writing a line, that
+// line is broken, we report it and keep everything before it, instead of refusing to start.
+//
+// The history is the learner's. A lesson only gets it when the runner of the classes hands it over
+// with with_history, `run <class>` does, so the Class 21 script adds its orders to it. `show`, the
+// menu, `run --all` and the book only show what the lessons do, that's not a day of the restaurant.
+
+use std::cell::RefCell;
+use std::fs::{self, OpenOptions};
+use std::io::{self, ErrorKind, Write};
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::restaurant::clock::UtcOffset;
+use crate::restaurant::counter::Counter;
+use crate::restaurant::order::{Order, OrderStatus};
+
+// The restaurant of the learner: where its orders are saved, and the counter with its promotions
+// and the loyalty points of its customers
+pub struct History {
+    pub store: OrderStore,
+    pub counter: Counter,
+}
+
+thread_local! {
+    // Only set inside with_history
+    static HISTORY: RefCell<Option<History>> = const { RefCell::new(None) };
+}
+
+// Puts back the history that was there before, even when the lessons panic
+struct Restore(Option<History>);
+
+impl Drop for Restore {
+    fn drop(&mut self) {
+        let before = self.0.take();
+        HISTORY.with(|history| *history.borrow_mut() = before);
+    }
+}
+
+// Runs `lessons` with the history of the learner: what they sell is recorded in its store
+pub fn with_history<T>(history: History, lessons: impl FnOnce() -> T) -> T {
+    let _restore = Restore(HISTORY.with(|current| current.replace(Some(history))));
+    lessons()
+}
+
+// Gives `lesson` the history with_history set, None outside of it
+pub fn using_history<T>(lesson: impl FnOnce(Option<&mut History>) -> T) -> T {
+    HISTORY.with(|history| lesson(history.borrow_mut().as_mut()))
+}
+
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct StoredOrder {
+    pub id: u64,
+    pub order: Order,
+}
+
+// A line of the file that is not a stored order
+#[derive(Clone, PartialEq, Debug)]
+pub struct CorruptLine {
+    // Starting from 1, like an editor shows it
+    pub number: usize,
+    pub error: String,
+    // Only followed by other broken lines, usually a write that was interrupted
+    pub trailing: bool,
+}
+
+// Everything is optional, an empty filter matches every order
+#[derive(Clone, Default, Debug)]
+pub struct OrderFilter {
+    // The day the customer was seated, "2024-03-15" on the clocks `utc_offset` from UTC
+    pub date: Option<String>,
+    pub utc_offset: UtcOffset,
+    pub customer: Option<String>,
+    pub status: Option<OrderStatus>,
+    pub topping: Option<String>,
+}
+
+impl OrderFilter {
+    pub fn matches(&self, stored: &StoredOrder) -> bool {
+        let order = &stored.order;
+        let seated = order.history()[0].at;
+        self.date.as_ref().is_none_or(|date| seated.local(self.utc_offset).date() == *date)
+            && self.customer.as_ref().is_none_or(|customer| order.customer.eq_ignore_ascii_case(customer))
+            && self.status.is_none_or(|status| order.status() == status)
+            && self.topping.as_ref().is_none_or(|topping| {
+                order
+                    .pizzas
+                    .iter()
+                    .any(|pizza| pizza.toppings.iter().any(|on_pizza| on_pizza.name == *topping))
+            })
+    }
+}
+
+pub struct OrderStore {
+    path: PathBuf,
+    // The last version of every order, by id
+    orders: Vec<StoredOrder>,
+    corrupted: Vec<CorruptLine>,
+    // The file doesn't end with a new line, the next line we append has to start with one
+    needs_newline: bool,
+}
+
+impl OrderStore {
+    // Loads the history, a file that doesn't exist yet is an empty history
+    pub fn open(path: &Path) -> io::Result<OrderStore> {
+        let content = match fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(err) if err.kind() == ErrorKind::NotFound => String::new(),
+            Err(err) => return Err(err),
+        };
+
+        let mut orders: Vec<StoredOrder> = Vec::new();
+        let mut corrupted: Vec<CorruptLine> = Vec::new();
+        for (index, line) in content.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            match serde_json::from_str::<StoredOrder>(line) {
+                Ok(stored) => {
+                    // A good line after broken ones means they were not the end of the file
+                    corrupted.iter_mut().for_each(|line| line.trailing = false);
+                    match orders.iter_mut().find(|existing| existing.id == stored.id) {
+                        Some(existing) => *existing = stored,
+                        None => orders.push(stored),
+                    }
+                }
+                Err(err) => corrupted.push(CorruptLine {
+                    number: index + 1,
+                    error: err.to_string(),
+                    trailing: true,
+                }),
+            }
+        }
+        orders.sort_by_key(|stored| stored.id);
+
+        Ok(OrderStore {
+            path: path.to_path_buf(),
+            orders,
+            corrupted,
+            needs_newline: !content.is_empty() && !content.ends_with('\n'),
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn corrupted(&self) -> &[CorruptLine] {
+        &self.corrupted
+    }
+
+    fn append(&mut self, stored: &StoredOrder) -> io::Result<()> {
+        let mut line = serde_json::to_string(stored).map_err(|err| io::Error::new(ErrorKind::InvalidData, err))?;
+        if self.needs_newline {
+            line.insert(0, '\n');
+        }
+        line.push('\n');
+
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let mut file = OpenOptions::new().create(true).append(true).open(&self.path)?;
+        file.write_all(line.as_bytes())?;
+        self.needs_newline = false;
+        Ok(())
+    }
+
+    // Saves a new order and returns its id
+    pub fn insert(&mut self, order: &Order) -> io::Result<u64> {
+        let id = self.orders.last().map(|stored| stored.id + 1).unwrap_or(1);
+        let stored = StoredOrder { id, order: order.clone() };
+        self.append(&stored)?;
+        self.orders.push(stored);
+        Ok(id)
+    }
+
+    // Saves the new version of an order that is already in the history
+    pub fn update(&mut self, id: u64, order: &Order) -> io::Result<()> {
+        let index = self
+            .orders
+            .iter()
+            .position(|stored| stored.id == id)
+            .ok_or_else(|| io::Error::new(ErrorKind::NotFound, format!("there is no order {}", id)))?;
+        let stored = StoredOrder { id, order: order.clone() };
+        self.append(&stored)?;
+        self.orders[index] = stored;
+        Ok(())
+    }
+
+    pub fn get(&self, id: u64) -> Option<&StoredOrder> {
+        self.orders.iter().find(|stored| stored.id == id)
+    }
+
+    pub fn orders(&self) -> &[StoredOrder] {
+        &self.orders
+    }
+
+    pub fn find(&self, filter: &OrderFilter) -> Vec<&StoredOrder> {
+        self.orders.iter().filter(|stored| filter.matches(stored)).collect()
+    }
+
+    pub fn on_date(&self, date: &str) -> Vec<&StoredOrder> {
+        self.find(&OrderFilter { date: Some(String::from(date)), ..OrderFilter::default() })
+    }
+
+    pub fn by_customer(&self, customer: &str) -> Vec<&StoredOrder> {
+        self.find(&OrderFilter { customer: Some(String::from(customer)), ..OrderFilter::default() })
+    }
+
+    pub fn by_status(&self, status: OrderStatus) -> Vec<&StoredOrder> {
+        self.find(&OrderFilter { status: Some(status), ..OrderFilter::default() })
+    }
+
+    pub fn with_topping(&self, topping: &str) -> Vec<&StoredOrder> {
+        self.find(&OrderFilter { topping: Some(String::from(topping)), ..OrderFilter::default() })
+    }
+}
+
+// The Seated column is on the clocks of the restaurant, `utc_offset` from UTC
+pub fn print_orders(orders: &[&StoredOrder], utc_offset: UtcOffset, out: &mut dyn Write) -> io::Result<()> {
+    writeln!(out, "{:>5}  {:<19}  {:<12} {:<10}  Pizzas", "Order", "Seated", "Customer", "Status")?;
+    for stored in orders {
+        let order = &stored.order;
+        let pizzas: Vec<String> = order.pizzas.iter().map(|pizza| pizza.to_string()).collect();
+        writeln!(
+            out,
+            "{:>5}  {:<19}  {:<12} {:<10}  {}",
+            stored.id,
+            order.history()[0].at.local(utc_offset).to_string(),
+            order.customer,
+            order.status().to_string(),
+            pizzas.join("; ")
+        )?;
+    }
+    Ok(())
+}
@@ -0,0 +1,87 @@
+use std::collections::VecDeque;
+use std::io::{self, Write};
+
+// Class 26 - Smart Pointers
+
+// Stack: Stores values in a last in first out format, and must have a defined fixed size
+// Heap: variable size
+
+pub fn creating_boxes(out: &mut dyn Write) -> io::Result<()> {
+    let b_int1: Box<i32> = Box::new(10);
+    writeln!(out, "b_int1 = {}", b_int1)?;
+    Ok(())
+}
+
+pub struct TreeNode<T> {
+    pub left: Option<Box<TreeNode<T>>>,
+    pub right: Option<Box<TreeNode<T>>>,
+    pub key: T,
+}
+
+impl<T> TreeNode<T> {
+    pub fn new(key: T) -> Self {
+        Self {
+            left: None,
+            right: None,
+            key,
+        }
+    }
+
+    pub fn left(mut self, node: TreeNode<T>) -> Self {
+        self.left = Some(Box::new(node));
+        self
+    }
+
+    pub fn right(mut self, node: TreeNode<T>) -> Self {
+        self.right = Some(Box::new(node));
+        self
+    }
+
+    pub fn insert(&mut self, new_value: T) {
+        let mut queue: VecDeque<&mut TreeNode<T>> = VecDeque::new();
+        queue.push_front(self);
+
+        loop {
+            // here we are basically exporting the internal members of the struct
+            // this is variable destructured like we have on Javascript
+            let TreeNode {
+                ref mut left,
+                ref mut right,
+                ..
+            } = queue.pop_back().unwrap();
+
+            match left {
+                Some(node) => {
+                    queue.push_front(node);
+                }
+                None => {
+                    *left = Some(Box::new(TreeNode::new(new_value)));
+                    return;
+                }
+            }
+
+            match right {
+                Some(node) => {
+                    queue.push_front(node);
+                }
+                None => {
+                    *right = Some(Box::new(TreeNode::new(new_value)));
+                    return;
+                }
+            }
+        }
+    }
+}
+
+pub fn binary_tree_box(out: &mut dyn Write) -> io::Result<()> {
+    let mut first_node: TreeNode<i32> = TreeNode::new(1);
+    first_node.left = Some(Box::new(TreeNode::new(2)));
+    first_node.right = Some(Box::new(TreeNode::new(3)));
+
+
+    match first_node.right {
+        Some(node) => writeln!(out, "{}", node.key)?,
+        None => writeln!(out, "there is no value")?,
+    }
+    Ok(())
+}
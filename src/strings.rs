@@ -0,0 +1,44 @@
+use std::io::{self, Write};
+
+// Class 2 - Strings
+pub fn string_example(out: &mut dyn Write) -> io::Result<()> {
+    // String is a struct type from Rust, that has a variety of methods
+    let mut name: String = String::from("Felipe");
+    // &str is the primitive type of string, A &str is made up of two components: a pointer to some bytes,
+    // and a length. You can look at these with the as_ptr and len methods:
+    let mut surename: &str = "Ramos";
+    Ok(())
+}
+
+// Class 11 - Strings Part 2
+pub fn strings_second_example(out: &mut dyn Write) -> io::Result<()> {
+    // Strings are immutable in almost all the languages, and strings are basically an array of bytes
+    // on Rust we have the primitive type str and the struct type String
+    // The str primitive type is an array of bytes, and it's immutable, there is no push method
+    // The String type, if you look inside its implementation, you have a Vector of bytes.
+
+
+    // Here is a nice example of how string works in many languages, and why they are immutable.
+    // We can see here, we have two chinese characters, so, we would have 2 elements on the byte array,
+    // but these elements can't be represented with only one byte, that is why this array is of size 6
+    // Rust in the background is applying utf-8 encoding, and the reason why strings are immutable, is that
+    // imagine if I change the byte on the first position of the byte array, I will be breaking the encoding of the string
+    // as the character 世 is represented by more than one byte.
+    // You can use the method as_bytes_mut to have a mutable byte array, so you can break the encoding, but this is a unsafe thing
+    let mut st1: &str = "世界";
+    writeln!(out, "{:?}", st1.as_bytes())?;
+
+    let mut st2: String = String::new();
+    st2.push('A');
+    st2.push_str(" word");
+    for word in st2.split_whitespace() {
+        writeln!(out, "{}", word)?;
+    }
+    st2.replace("A", "Another");
+
+    // Here we can sum strings
+    let st4: String = String::from("1 ");
+    let st5: String = String::from("2");
+    let st6: String = st4 + &st5;
+    Ok(())
+}
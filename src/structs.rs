@@ -0,0 +1,23 @@
+use std::io::{self, Write};
+
+// Class 19 - Structs
+// Here, structs can have methods, we just need to call impl <Structname> and define a set of methods
+// Structs, can also implement traits, which work like interfaces.
+// There is no inheritance in Rust, but like Golang, we have composition, which mean we can have one
+// struct, inside the another, and the child struct, can call parent struct methods, or even override parent struct
+// methods.
+pub struct Customer {
+    pub name: String,
+    pub address: String,
+    pub balance: f32,
+}
+
+pub fn structs(out: &mut dyn Write) -> io::Result<()> {
+    let mut bob: Customer = Customer {
+        name: String::from("Bob Smith"),
+        address: String::from("555 Main St"),
+        balance: 234.50,
+    };
+    bob.address = String::from("505 Main St");
+    Ok(())
+}
@@ -0,0 +1,45 @@
+use std::f32::consts::PI;
+use std::io::{self, Write};
+
+// Class 20 - Traits
+// A trait defines functionality a particular type has and can share with other types.
+// We can use traits to define shared behavior in an abstract way. We can use trait bounds to specify
+// that a generic type can be any type that has certain behavior.
+pub trait Shape {
+    fn new(length: f32, width: f32) -> Self;
+    fn area(&self) -> f32;
+}
+pub struct Rectangle {
+    pub length: f32,
+    pub width: f32,
+}
+pub struct Circle {
+    pub length: f32,
+    pub width: f32,
+}
+
+impl Shape for Rectangle {
+    fn new(length: f32, width: f32) -> Self {
+        // remember we don't need to use return statement, and ; for the last statement
+        Rectangle { length, width }
+    }
+
+    fn area(&self) -> f32 {
+        self.length * self.width
+    }
+}
+
+impl Shape for Circle {
+    fn new(length: f32, width: f32) -> Self {
+        Circle { length, width }
+    }
+
+    fn area(&self) -> f32 {
+        (self.length / 2.0).powf(2.0) * PI
+    }
+}
+
+pub fn traits(out: &mut dyn Write) -> io::Result<()> {
+    let circle = Circle::new(2 as f32, 2 as f32);
+    Ok(())
+}
@@ -0,0 +1,82 @@
+use rust_tutorial::restaurant::billing::{allocate, Bill, BillingError, Money, Rate};
+use rust_tutorial::restaurant::clock::Timestamp;
+use rust_tutorial::restaurant::order::Order;
+use rust_tutorial::restaurant::pizza_order::menu::{Menu, Size};
+use rust_tutorial::restaurant::pizza_order::PizzaBuilder;
+
+// A large with ham and olives ($14.00 + $1.80 + $1.00) and a small with veggies ($8.00 + $1.50)
+fn table_order() -> Order {
+    let menu = Menu::standard();
+    let large = PizzaBuilder::new(&menu).size(Size::Large).topping("ham").topping("olives").build().unwrap();
+    let small = PizzaBuilder::new(&menu).size(Size::Small).topping("veggies").build().unwrap();
+    let mut order = Order::seat("Ana", Timestamp(0));
+    order.place(vec![large, small], Timestamp(60)).unwrap();
+    order
+}
+
+#[test]
+fn pizzas_are_priced_by_size_and_toppings() {
+    let bill = Bill::for_order(&table_order(), &Menu::standard(), Rate::basis_points(825));
+
+    assert_eq!(bill.lines[0].amount, Money::cents(1680));
+    assert_eq!(bill.lines[1].amount, Money::cents(950));
+    assert_eq!(bill.subtotal(), Money::cents(2630));
+    // 8.25% of $26.30 is $2.16975
+    assert_eq!(bill.tax(), Money::cents(217));
+    assert_eq!(bill.total(), Money::cents(2847));
+}
+
+#[test]
+fn the_tip_is_on_the_food_before_tax() {
+    let bill = Bill::for_order(&table_order(), &Menu::standard(), Rate::basis_points(825)).with_tip(Rate::percent(18));
+
+    // 18% of $26.30 is $4.734
+    assert_eq!(bill.tip(), Money::cents(473));
+    assert_eq!(bill.total(), Money::cents(2630 + 217 + 473));
+}
+
+#[test]
+fn split_bills_always_add_up_to_the_total() {
+    let bill = Bill::for_order(&table_order(), &Menu::standard(), Rate::basis_points(825)).with_tip(Rate::percent(15));
+    let total = bill.total();
+
+    let even = bill.split_evenly(3).unwrap();
+    assert_eq!(even.iter().copied().sum::<Money>(), total);
+    assert!(even.iter().all(|part| (part.as_cents() - total.as_cents() / 3).abs() <= 1));
+
+    let by_line = bill.split_by_line(&["Ana", "Bruno"]).unwrap();
+    assert_eq!(by_line[0].0, "Ana");
+    assert_eq!(by_line[0].1 + by_line[1].1, total);
+    assert!(by_line[0].1 > by_line[1].1);
+
+    assert_eq!(bill.split_evenly(0), Err(BillingError::NobodyToPay));
+    assert_eq!(
+        bill.split_by_line(&["Ana"]),
+        Err(BillingError::OwnersDontMatch { lines: 2, owners: 1 })
+    );
+}
+
+#[test]
+fn the_cents_left_by_a_split_go_to_the_biggest_fractions() {
+    assert_eq!(allocate(Money::cents(100), &[1, 1, 1]), vec![Money::cents(34), Money::cents(33), Money::cents(33)]);
+    assert_eq!(allocate(Money::cents(10), &[1, 2]), vec![Money::cents(3), Money::cents(7)]);
+}
+
+#[test]
+fn money_and_rates_print_like_a_receipt() {
+    assert_eq!(Money::cents(1250).to_string(), "$12.50");
+    assert_eq!(Money::cents(-105).to_string(), "-$1.05");
+    assert_eq!(format!("{:>8}", Money::dollars(3)), "   $3.00");
+    assert_eq!(Rate::basis_points(825).to_string(), "8.25%");
+    assert_eq!(Rate::basis_points(750).to_string(), "7.5%");
+    assert_eq!(Rate::percent(15).to_string(), "15%");
+
+    let bill = Bill::for_order(&table_order(), &Menu::standard(), Rate::percent(10));
+    let mut out: Vec<u8> = Vec::new();
+    bill.print_receipt(&mut out).unwrap();
+    let receipt = String::from_utf8(out).unwrap();
+    assert!(receipt.starts_with("Receipt for Ana\n"));
+    assert!(receipt.contains("Tax 10%"));
+    assert!(!receipt.contains("Tip"));
+    assert!(receipt.trim_end().ends_with("$28.93"));
+}
@@ -0,0 +1,252 @@
+use std::path::PathBuf;
+use std::process::Command;
+use std::{env, fs};
+
+use rust_tutorial::restaurant::billing::Money;
+use rust_tutorial::restaurant::chain::{Branch, Chain, ChainConfig, ChainError, ChainReport, Location};
+use rust_tutorial::restaurant::clock::{Timestamp, UtcOffset};
+use rust_tutorial::restaurant::inventory::Ingredient;
+use rust_tutorial::restaurant::order::OrderStatus;
+use rust_tutorial::restaurant::pizza_order::dietary::{Allergen, DietaryNeeds};
+use rust_tutorial::restaurant::pizza_order::help_customer::Refusal;
+use rust_tutorial::restaurant::pizza_order::menu::{Cheese, Dough, Menu, Size};
+use rust_tutorial::restaurant::pizza_order::{Pizza, PizzaBuilder};
+
+// 2024-03-15 12:00:00, Downtown and Harbor are open, Campus opens at 17:00
+const NOON: Timestamp = Timestamp(1_710_504_000);
+
+fn pizza(topping: &str) -> Pizza {
+    PizzaBuilder::new(&Menu::standard()).size(Size::Medium).topping(topping).build().unwrap()
+}
+
+fn temp_dir(test: &str) -> PathBuf {
+    let dir = env::temp_dir().join("rust_tutorial_chain").join(test);
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+fn names(branches: Vec<&Branch>) -> Vec<&str> {
+    branches.iter().map(|branch| branch.name()).collect()
+}
+
+#[test]
+fn branches_override_the_menu_of_the_chain() {
+    let chain = Chain::standard();
+    let downtown = chain.branch("downtown").unwrap();
+    let harbor = chain.branch("Harbor").unwrap();
+    let campus = chain.branch("Campus").unwrap();
+
+    assert_eq!(downtown.menu().price_of(&pizza("anchovies")), Money::cents(1320));
+    assert_eq!(harbor.menu().price_of(&pizza("anchovies")), Money::cents(1280));
+    assert_eq!(campus.menu().price_of(&pizza("veggies")), Money::cents(1100));
+    assert!(campus.menu().topping("anchovies").is_none());
+    assert!(harbor.menu().topping("tuna").is_some());
+    assert!(downtown.menu().topping("tuna").is_none());
+
+    // Each branch has its own tables and stocks its own menu
+    assert_eq!(campus.restaurant().tables().len(), 8);
+    assert_eq!(harbor.counter().inventory().stock(&Ingredient::topping("tuna")), 40);
+    assert_eq!(downtown.counter().inventory().stock(&Ingredient::topping("tuna")), 0);
+    assert_eq!(chain.branch("Uptown").err(), Some(ChainError::UnknownBranch(String::from("Uptown"))));
+}
+
+#[test]
+fn an_extra_topping_the_menu_has_replaces_it() {
+    let json = r#"{
+        "branches": [
+            {
+                "name": "Farm",
+                "location": { "east": 0, "north": 0 },
+                "hours": { "from_hour": 10, "to_hour": 22 },
+                "menu": { "extra_toppings": [{ "name": "ham", "price_cents": 300, "diets": ["gluten_free"] }] }
+            }
+        ]
+    }"#;
+    let chain = Chain::new(ChainConfig::from_json(json).unwrap(), Menu::standard());
+    let farm = chain.branch("Farm").unwrap();
+    assert_eq!(farm.menu().toppings.iter().filter(|topping| topping.name == "ham").count(), 1);
+    assert_eq!(farm.menu().toppings.len(), Menu::standard().toppings.len());
+    assert_eq!(farm.menu().price_of(&pizza("ham")), Money::cents(1400));
+}
+
+#[test]
+fn opening_hours_can_close_past_midnight() {
+    let chain = Chain::standard();
+    assert_eq!(names(chain.open_at(NOON)), vec!["Downtown", "Harbor"]);
+    assert_eq!(names(chain.open_at(NOON.plus(6 * 3600))), vec!["Downtown", "Harbor", "Campus"]);
+    // 01:00 the next day
+    assert_eq!(names(chain.open_at(NOON.plus(13 * 3600))), vec!["Campus"]);
+    // 05:00
+    assert!(chain.open_at(NOON.plus(17 * 3600)).is_empty());
+}
+
+#[test]
+fn opening_hours_are_on_the_clocks_of_the_city() {
+    let mut config = ChainConfig::standard();
+    config.utc_offset = UtcOffset::hours(-5);
+    let chain = Chain::new(config, Menu::standard());
+    // 07:00 in the city
+    assert!(chain.open_at(NOON).is_empty());
+    assert_eq!(names(chain.open_at(NOON.plus(5 * 3600))), vec!["Downtown", "Harbor"]);
+    // 22:00 and 01:00 in the city
+    assert_eq!(names(chain.open_at(NOON.plus(15 * 3600))), vec!["Downtown", "Campus"]);
+    assert_eq!(names(chain.open_at(NOON.plus(18 * 3600))), vec!["Campus"]);
+}
+
+#[test]
+fn online_orders_go_to_the_nearest_open_branch() {
+    let mut chain = Chain::standard();
+    let near_harbor = Location::new(7, -2);
+
+    let routed = chain.order_online("Ana", near_harbor, vec![pizza("ham")], NOON).unwrap();
+    assert_eq!(routed.branch, "Harbor");
+    assert_eq!(routed.blocks, 2);
+    let harbor = chain.branch("Harbor").unwrap();
+    assert_eq!(harbor.orders()[0].order.status(), OrderStatus::InKitchen);
+    assert_eq!(harbor.counter().inventory().stock(&Ingredient::topping("ham")), 39);
+    assert_eq!(chain.branch("Downtown").unwrap().counter().inventory().stock(&Ingredient::topping("ham")), 40);
+
+    // At night only Campus is open, however far it is, and it doesn't make anchovies
+    let night = NOON.plus(13 * 3600);
+    assert_eq!(chain.route(near_harbor, &[pizza("ham")], night).unwrap().name(), "Campus");
+    assert_eq!(chain.route(near_harbor, &[pizza("anchovies")], night).err(), Some(ChainError::NoBranchCanMake));
+    let dawn = NOON.plus(17 * 3600);
+    assert_eq!(chain.order_online("Bo", near_harbor, vec![pizza("ham")], dawn), Err(ChainError::AllClosed(dawn)));
+}
+
+#[test]
+fn a_branch_out_of_stock_sends_the_order_to_the_next_one() {
+    let mut chain = Chain::standard();
+    // 20 large pizzas take the 40 portions of ham of Harbor
+    let large_ham = PizzaBuilder::new(&Menu::standard()).size(Size::Large).topping("ham").build().unwrap();
+    let harbor = chain.branch_mut("Harbor").unwrap();
+    harbor.counter_mut().inventory_mut().consume(&vec![large_ham; 20]).unwrap();
+
+    let routed = chain.order_online("Ana", Location::new(8, -3), vec![pizza("ham")], NOON).unwrap();
+    assert_eq!(routed.branch, "Downtown");
+    assert_eq!(routed.blocks, 11);
+
+    // A topping only Harbor has stays at Harbor
+    let tuna = PizzaBuilder::new(chain.branch("Harbor").unwrap().menu()).topping("tuna").build().unwrap();
+    assert_eq!(chain.route(Location::new(0, 0), &[tuna], NOON).unwrap().name(), "Harbor");
+}
+
+#[test]
+fn online_orders_go_through_the_counter_of_the_branch() {
+    let mut chain = Chain::standard();
+    let fish = DietaryNeeds::allergy(Allergen::Fish);
+    let refused = chain.order_online_with_needs("Ana", &fish, Location::new(8, -3), vec![pizza("anchovies")], NOON);
+    assert_eq!(refused, Err(ChainError::Refused(Refusal::Allergens(vec![String::from("anchovies has fish")]))));
+    let harbor = chain.branch("Harbor").unwrap();
+    assert_eq!(harbor.orders()[0].order.status(), OrderStatus::Cancelled);
+    assert_eq!(harbor.counter().inventory().stock(&Ingredient::topping("anchovies")), 40);
+
+    // Every open branch ran out of ham, the nearest one swaps it
+    let large_ham = PizzaBuilder::new(&Menu::standard()).size(Size::Large).topping("ham").build().unwrap();
+    for name in ["Downtown", "Harbor"] {
+        let branch = chain.branch_mut(name).unwrap();
+        branch.counter_mut().inventory_mut().consume(&vec![large_ham.clone(); 20]).unwrap();
+    }
+    let routed = chain.order_online("Bo", Location::new(8, -3), vec![pizza("ham")], NOON).unwrap();
+    assert_eq!(routed.branch, "Harbor");
+    let order = &chain.branch("Harbor").unwrap().orders()[routed.id as usize - 1].order;
+    assert_eq!(order.status(), OrderStatus::InKitchen);
+    assert_ne!(order.pizzas[0].toppings[0].name, "ham");
+}
+
+#[test]
+fn the_chain_report_sums_up_every_branch_at_its_prices() {
+    let mut chain = Chain::standard();
+    let evening = NOON.plus(6 * 3600);
+    let harbor = chain.order_online("Ana", Location::new(8, -3), vec![pizza("anchovies")], NOON).unwrap();
+    let campus = chain.order_online("Bo", Location::new(-5, 6), vec![pizza("veggies")], evening).unwrap();
+    chain.order_online("Cy", Location::new(0, 0), vec![pizza("ham")], NOON).unwrap();
+
+    for (routed, at) in [(&harbor, NOON), (&campus, evening)] {
+        let order = chain.branch_mut(&routed.branch).unwrap().order_mut(routed.id).unwrap();
+        order.mark_ready(at.plus(900)).unwrap();
+        order.serve(at.plus(1000)).unwrap();
+        order.pay(at.plus(2000)).unwrap();
+    }
+
+    let report = ChainReport::for_date(&chain, "2024-03-15");
+    let revenue: Vec<(&str, Money)> = report.branches.iter().map(|branch| (branch.branch.as_str(), branch.report.revenue)).collect();
+    assert_eq!(
+        revenue,
+        vec![("Downtown", Money::ZERO), ("Harbor", Money::cents(1280)), ("Campus", Money::cents(1100))]
+    );
+    assert_eq!(report.chain.orders, 3);
+    assert_eq!(report.chain.paid_orders, 2);
+    assert_eq!(report.chain.revenue, Money::cents(2380));
+    assert_eq!(report.chain.top_customers[0].customer, "Ana");
+
+    let mut out = Vec::new();
+    report.print(&mut out).unwrap();
+    let out = String::from_utf8(out).unwrap();
+    assert!(out.contains(&format!("{:<20} {:>8} {:>8} {:>8} {:>12} {:>12}", "Chain", 3, 2, 2, "$23.80", "$11.90")));
+}
+
+#[test]
+fn every_branch_stocks_what_chain_json_says() {
+    let chain = Chain::standard();
+    let downtown = chain.branch("Downtown").unwrap().counter().inventory();
+    let campus = chain.branch("Campus").unwrap().counter().inventory();
+
+    assert_eq!(downtown.stock(&Ingredient::Dough(Dough::Regular)), 40);
+    assert_eq!(downtown.stock(&Ingredient::topping("olives")), 40);
+    assert_eq!(campus.stock(&Ingredient::Dough(Dough::Regular)), 30);
+    assert_eq!(campus.stock(&Ingredient::Cheese(Cheese::Mozzarella)), 6_000);
+    assert_eq!(campus.stock(&Ingredient::topping("olives")), 25);
+    assert_eq!(campus.stock(&Ingredient::topping("mushrooms")), 50);
+}
+
+#[test]
+fn every_branch_keeps_its_orders_in_its_own_file() {
+    let dir = temp_dir("stores");
+    let mut chain = Chain::standard().with_stores(&dir).unwrap();
+    let routed = chain.order_online("Ana", Location::new(8, -3), vec![pizza("anchovies")], NOON).unwrap();
+    chain
+        .branch_mut(&routed.branch)
+        .unwrap()
+        .update_order(routed.id, |order| {
+            order.mark_ready(NOON.plus(900))?;
+            order.serve(NOON.plus(1000))?;
+            order.pay(NOON.plus(2000))
+        })
+        .unwrap();
+    // A change that fails leaves the order as it was
+    let harbor = chain.branch_mut("Harbor").unwrap();
+    assert!(harbor.update_order(routed.id, |order| order.cancel(NOON.plus(3000))).is_err());
+    assert_eq!(
+        harbor.update_order(7, |order| order.cancel(NOON)),
+        Err(ChainError::UnknownOrder(String::from("Harbor"), 7))
+    );
+
+    assert!(Chain::store_path(&dir, "Harbor").ends_with("orders-harbor.jsonl"));
+    assert!(!Chain::store_path(&dir, "Downtown").exists());
+
+    let chain = Chain::standard().with_stores(&dir).unwrap();
+    let harbor = chain.branch("Harbor").unwrap();
+    assert_eq!(harbor.orders().len(), 1);
+    assert_eq!(harbor.orders()[0].order.status(), OrderStatus::Paid);
+    assert!(chain.branch("Downtown").unwrap().orders().is_empty());
+
+    let output = Command::new(env!("CARGO_BIN_EXE_rust_tutorial"))
+        .args(["chain", "report", "--date", "2024-03-15"])
+        .env("RUST_TUTORIAL_CONFIG_DIR", &dir)
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains(&format!("{:<20} {:>8} {:>8} {:>8} {:>12} {:>12}", "Harbor", 1, 1, 1, "$12.80", "$12.80")));
+    assert!(stdout.contains(&format!("{:<20} {:>8} {:>8} {:>8} {:>12} {:>12}", "Chain", 1, 1, 1, "$12.80", "$12.80")));
+
+    let output = Command::new(env!("CARGO_BIN_EXE_rust_tutorial"))
+        .args(["chain", "report", "--format", "csv"])
+        .env("RUST_TUTORIAL_CONFIG_DIR", &dir)
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    assert!(String::from_utf8(output.stderr).unwrap().contains("unknown format `csv`"));
+}
@@ -0,0 +1,77 @@
+// Compile-fail fixtures
+// Some examples of Class 17, Class 22 and Class 27 are commented out in src/ownership.rs, because they don't compile.
+// Each one of them lives as a standalone file in tests/compile_fail, with the errors rustc must report
+// declared in the header, like:
+//     // error-code: E0382
+// This test compiles every fixture with the local rustc (or the one in the RUSTC environment variable),
+// and checks that it still fails with the documented errors.
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::{env, fs};
+
+fn expected_codes(source: &str) -> Vec<String> {
+    source
+        .lines()
+        .filter_map(|line| line.trim().strip_prefix("// error-code:"))
+        .map(|code| code.trim().to_string())
+        .collect()
+}
+
+// Returns a description of what went wrong, or None when the fixture failed the way it should
+fn check_fixture(rustc: &str, path: &Path, out_dir: &Path) -> Option<String> {
+    let source = fs::read_to_string(path).unwrap();
+    let codes = expected_codes(&source);
+    if codes.is_empty() {
+        return Some("no `// error-code:` declared".to_string());
+    }
+
+    let output = Command::new(rustc)
+        .args(["--edition", "2021", "--crate-type", "lib", "--emit=metadata"])
+        .arg("--out-dir")
+        .arg(out_dir)
+        .arg(path)
+        .output()
+        .unwrap_or_else(|err| panic!("Failed to run {}: {}", rustc, err));
+
+    if output.status.success() {
+        return Some("compiled successfully, but it should fail".to_string());
+    }
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let missing: Vec<&String> = codes
+        .iter()
+        .filter(|code| !stderr.contains(&format!("error[{}]", code)))
+        .collect();
+    if missing.is_empty() {
+        None
+    } else {
+        Some(format!("expected {:?} but rustc reported:\n{}", missing, stderr))
+    }
+}
+
+#[test]
+fn commented_out_examples_still_fail_to_compile() {
+    let rustc = env::var("RUSTC").unwrap_or_else(|_| "rustc".to_string());
+    let fixtures = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/compile_fail");
+    let out_dir = env::temp_dir().join("rust_tutorial_compile_fail");
+    fs::create_dir_all(&out_dir).unwrap();
+
+    let mut paths: Vec<PathBuf> = fs::read_dir(&fixtures)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "rs"))
+        .collect();
+    paths.sort();
+    assert!(!paths.is_empty(), "no fixtures found in {}", fixtures.display());
+
+    let failures: Vec<String> = paths
+        .iter()
+        .filter_map(|path| {
+            check_fixture(&rustc, path, &out_dir)
+                .map(|problem| format!("{}: {}", path.file_name().unwrap().to_string_lossy(), problem))
+        })
+        .collect();
+
+    assert!(failures.is_empty(), "\n{}", failures.join("\n\n"));
+}
@@ -0,0 +1,10 @@
+// Class 22 - References part 2, the comment below change_my_vector() in src/ownership.rs
+// another_ref_i stole the mutable reference from ref_i, so ref_i can't be used anymore.
+// error-code: E0382
+
+pub fn mutable_reference_moved() {
+    let mut i: i32 = 1;
+    let ref_i = &mut i;
+    let another_ref_i = ref_i;
+    *ref_i += 1;
+}
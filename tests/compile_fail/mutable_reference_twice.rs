@@ -0,0 +1,12 @@
+// Class 22 - References part 2, the comment above mutable_reference() in src/ownership.rs
+// We can only have one mutable reference to a variable at a time.
+// error-code: E0499
+// error-code: E0502
+
+pub fn mutable_reference() {
+    let mut str1: String = String::from("test");
+    let mut_ref: &mut String = &mut str1;
+    let mut_ref_second: &mut String = &mut str1;
+    let immut_ref: &String = &str1;
+    println!("{}", mut_ref)
+}
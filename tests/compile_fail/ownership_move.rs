@@ -0,0 +1,9 @@
+// Class 17 - Ownership, ownership() in src/ownership.rs
+// The value was moved from str1 to str2, so str1 can't be used anymore.
+// error-code: E0382
+
+pub fn ownership() {
+    let str1: String = String::from("World");
+    let str2: String = str1;
+    println!("Hello {}", str1)
+}
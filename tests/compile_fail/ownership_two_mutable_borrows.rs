@@ -0,0 +1,12 @@
+// Class 17 - Ownership, ownership() in src/ownership.rs
+// str4 can't be used, because the mutable reference was borrowed again by str5 (and str6 borrows it as immutable).
+// error-code: E0499
+// error-code: E0502
+
+pub fn ownership() {
+    let mut str3: String = String::from("Hello");
+    let str4 = &mut str3;
+    let str5 = &mut str3;
+    let str6 = &str3;
+    println!("{}", str4)
+}
@@ -0,0 +1,11 @@
+// Class 27 - Advanced Ownership (Re-Borrowing), reborrow_error() in src/ownership.rs
+// d moved the re-borrow of b away from c, so c can't be used anymore.
+// error-code: E0499
+
+pub fn reborrow_error() {
+    let mut a = 7;
+    let b = &mut a;
+    let c = &mut *b;
+    let d = &mut *b;
+    *c = 2;
+}
@@ -0,0 +1,10 @@
+// Class 27 - Advanced Ownership (Re-Borrowing), the second example of the class in src/ownership.rs
+// The mutable borrow was moved from b to c, so b can't be used.
+// error-code: E0382
+
+pub fn moved_borrow() {
+    let mut a = 0;
+    let b = &mut a;
+    let c = b;
+    println!("{b}");
+}
@@ -0,0 +1,19 @@
+// Class 27 - Advanced Ownership (Re-Borrowing), start_thread() and reborrow_thread() in src/ownership.rs
+// A re-borrow can't escape to another thread, the closure requires the reference to be 'static.
+// error-code: E0521
+// error-code: E0373
+
+use std::thread;
+
+fn start_thread(r: &mut i32) {
+    thread::spawn(|| {
+        println!("{}", r);
+    });
+}
+
+pub fn reborrow_thread() {
+    let mut a = 7;
+    let b = &mut a;
+
+    start_thread(b);
+}
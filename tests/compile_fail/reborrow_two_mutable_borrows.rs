@@ -0,0 +1,10 @@
+// Class 27 - Advanced Ownership (Re-Borrowing), the first example of the class in src/ownership.rs
+// The current borrower of the mutable reference is c, so b can't be used.
+// error-code: E0499
+
+pub fn two_mutable_borrows() {
+    let mut a = 0;
+    let b = &mut a;
+    let c = &mut a;
+    println!("{b}");
+}
@@ -0,0 +1,13 @@
+// Class 27 - Advanced Ownership (Re-Borrowing), reborrow() in src/ownership.rs
+// Using b ends the re-borrow, so c can't be used after it.
+// error-code: E0502
+
+pub fn reborrow() {
+    let mut a = 7;
+    let b = &mut a;
+    let c = &mut *b;
+    *c = 1;
+
+    println!("{b}");
+    println!("{c}");
+}
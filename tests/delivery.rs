@@ -0,0 +1,144 @@
+use rust_tutorial::restaurant::clock::{SimulatedClock, Timestamp};
+use rust_tutorial::restaurant::delivery::{Address, DeliveryConfig, DeliveryError, DeliveryMap, DeliveryService, Trip};
+use rust_tutorial::restaurant::counter::Counter;
+use rust_tutorial::restaurant::inventory::Ingredient;
+use rust_tutorial::restaurant::kitchen::{Board, KitchenConfig};
+use rust_tutorial::restaurant::order::{Order, OrderStatus};
+use rust_tutorial::restaurant::pizza_order::help_customer::take_delivery;
+use rust_tutorial::restaurant::pizza_order::menu::Menu;
+use rust_tutorial::restaurant::pizza_order::{Pizza, PizzaBuilder};
+use rust_tutorial::restaurant::seating::Party;
+
+const NOON: Timestamp = Timestamp(1_710_504_000);
+
+// A lunch pizza takes 2m30s to prepare and 10 minutes to bake
+fn lunch_order(customer: &str, at: Timestamp) -> Order {
+    let mut order = Order::seat(customer, at);
+    order.place(vec![Pizza::lunch("ham")], at).unwrap();
+    order
+}
+
+fn service(couriers: usize) -> DeliveryService {
+    let config = DeliveryConfig { couriers, ..DeliveryConfig::default() };
+    DeliveryService::new(DeliveryMap::standard(), config, KitchenConfig::default())
+}
+
+#[test]
+fn addresses_are_mapped_to_zones() {
+    let map = DeliveryMap::standard();
+    assert_eq!(map.zone_of(&Address::new("main street", 12)).unwrap().name, "Center");
+    assert_eq!(map.zone_of(&Address::new("Mill Lane", 3)).unwrap().travel_seconds, 20 * 60);
+    assert_eq!(
+        map.zone_of(&Address::new("Elm Street", 3)),
+        Err(DeliveryError::OutsideDeliveryArea(Address::new("Elm Street", 3)))
+    );
+}
+
+#[test]
+fn nearby_orders_share_a_courier() {
+    let mut service = service(2);
+    let mut board = Board::new(2);
+    let ana = service.order(lunch_order("Ana", NOON), Address::new("Main Street", 1), &mut board, NOON).unwrap();
+    // Ready at 12:12:30, and 6 minutes to the center
+    assert_eq!(ana.ready_at, NOON.plus(750));
+    assert_eq!(ana.eta, NOON.plus(750 + 360));
+
+    let bruno = service.order(lunch_order("Bruno", NOON.plus(60)), Address::new("Market Street", 2), &mut board, NOON.plus(60)).unwrap();
+    // Bruno's pizza is ready a minute later, the courier waits for it and Ana is the first stop
+    assert_eq!(bruno.eta, NOON.plus(810 + 360 + 120));
+    assert_eq!(service.estimate(ana.id), Some(NOON.plus(810 + 360)));
+
+    let carla = service.order(lunch_order("Carla", NOON.plus(120)), Address::new("River Road", 3), &mut board, NOON.plus(120)).unwrap();
+    let davi = service.order(lunch_order("Davi", NOON.plus(180)), Address::new("Church Street", 4), &mut board, NOON.plus(180)).unwrap();
+    assert_eq!(carla.eta, NOON.plus(1500 + 1200));
+    // Too late for the first trip, it waits for the first courier to come back
+    assert_eq!(davi.eta, NOON.plus(1650 + 360));
+
+    let delivered = service.run_until(NOON.plus(1200)).unwrap();
+    assert_eq!(delivered.iter().map(|delivery| delivery.id).collect::<Vec<u64>>(), vec![ana.id]);
+    assert_eq!(delivered[0].order.status(), OrderStatus::Paid);
+    assert_eq!(service.estimate(bruno.id), Some(NOON.plus(1290)));
+
+    let delivered = service.run_until(NOON.plus(4000)).unwrap();
+    assert_eq!(delivered.iter().map(|delivery| delivery.id).collect::<Vec<u64>>(), vec![bruno.id, davi.id, carla.id]);
+    assert_eq!(
+        service.trips()[0],
+        Trip {
+            courier: 1,
+            zone: String::from("Center"),
+            departs_at: NOON.plus(810),
+            back_at: NOON.plus(1290 + 360),
+            deliveries: vec![ana.id, bruno.id],
+        }
+    );
+    assert_eq!(service.trips().len(), 3);
+}
+
+#[test]
+fn a_courier_takes_at_most_max_batch_orders() {
+    let mut service = service(2);
+    let mut board = Board::new(4);
+    for (number, customer) in ["Ana", "Bruno", "Carla", "Davi"].iter().enumerate() {
+        service.order(lunch_order(customer, NOON), Address::new("Hill Road", number as u32), &mut board, NOON).unwrap();
+    }
+    service.run_until(NOON.plus(750)).unwrap();
+
+    let trips: Vec<(usize, Vec<u64>)> = service.trips().iter().map(|trip| (trip.courier, trip.deliveries.clone())).collect();
+    assert_eq!(trips, vec![(1, vec![1, 2, 3]), (2, vec![4])]);
+}
+
+#[test]
+fn the_waiter_takes_delivery_orders() {
+    let menu = Menu::standard();
+    let clock = SimulatedClock::starting_at(NOON);
+    let mut counter = Counter::standard();
+    let mut service = service(1);
+    let mut board = Board::new(2);
+    let mut out: Vec<u8> = Vec::new();
+
+    let choice = PizzaBuilder::new(&menu).topping("olives");
+    let quote = take_delivery(&mut counter, &mut service, &mut board, &Party::new("Ana", 1), &Address::new("Park Avenue", 7), choice, &clock, &mut out).unwrap();
+    assert_eq!(quote.unwrap().eta, NOON.plus(750 + 720));
+
+    let choice = PizzaBuilder::new(&menu).topping("olives");
+    let nothing = take_delivery(&mut counter, &mut service, &mut board, &Party::new("Eva", 1), &Address::new("Elm Street", 3), choice, &clock, &mut out).unwrap();
+    assert!(nothing.is_none());
+    // Only Ana's pizza used olives
+    assert_eq!(counter.inventory().stock(&Ingredient::topping("olives")), 39);
+
+    let out = String::from_utf8(out).unwrap();
+    assert!(out.contains("Ana's pizza goes to 7 Park Avenue (North), in about 24m 30s\n"));
+    assert!(out.contains("Sorry Eva, we don't deliver to 3 Elm Street\n"));
+}
+
+#[test]
+fn a_trip_waiting_for_a_late_order_doesnt_hold_the_others() {
+    let config = DeliveryConfig { couriers: 2, batch_window_seconds: 40 * 60, ..DeliveryConfig::default() };
+    let mut service = DeliveryService::new(DeliveryMap::standard(), config, KitchenConfig::default());
+    let mut board = Board::new(2);
+    let ana = service.order(lunch_order("Ana", NOON), Address::new("Main Street", 1), &mut board, NOON).unwrap();
+    let carla = service.order(lunch_order("Carla", NOON.plus(60)), Address::new("River Road", 3), &mut board, NOON.plus(60)).unwrap();
+    // Bruno orders half an hour later, still inside the window of Ana's courier
+    let bruno = service.order(lunch_order("Bruno", NOON.plus(1800)), Address::new("Market Street", 2), &mut board, NOON.plus(1800)).unwrap();
+    assert_eq!(service.estimate(ana.id), Some(NOON.plus(2550 + 360)));
+    assert_eq!(carla.eta, NOON.plus(810 + 1200));
+
+    // The trip to the center is planned first but leaves after Carla's
+    let delivered = service.run_until(carla.eta.plus(10)).unwrap();
+    assert_eq!(delivered.iter().map(|delivery| delivery.id).collect::<Vec<u64>>(), vec![carla.id]);
+    assert_eq!(service.trips().len(), 1);
+
+    let delivered = service.run_until(bruno.eta).unwrap();
+    assert_eq!(delivered.iter().map(|delivery| delivery.id).collect::<Vec<u64>>(), vec![ana.id, bruno.id]);
+}
+
+#[test]
+fn deliveries_wait_for_the_orders_of_the_tables() {
+    let mut service = service(1);
+    let mut board = Board::new(1);
+    // The only cook already has a table's pizza in the works
+    board.assign(NOON, 750);
+    let ana = service.order(lunch_order("Ana", NOON), Address::new("Main Street", 1), &mut board, NOON).unwrap();
+    assert_eq!(ana.ready_at, NOON.plus(1500));
+    assert_eq!(ana.eta, NOON.plus(1500 + 360));
+}
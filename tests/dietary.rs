@@ -0,0 +1,105 @@
+use rust_tutorial::restaurant::clock::{Clock, Timestamp};
+use rust_tutorial::restaurant::counter::Counter;
+use rust_tutorial::restaurant::inventory::{Ingredient, Inventory, Recipes};
+use rust_tutorial::restaurant::order::OrderStatus;
+use rust_tutorial::restaurant::pizza_order::dietary::{Allergen, Diet, DietaryNeeds};
+use rust_tutorial::restaurant::pizza_order::help_customer::take_order;
+use rust_tutorial::restaurant::pizza_order::menu::{Cheese, Dough, Menu};
+use rust_tutorial::restaurant::pizza_order::{Pizza, PizzaBuilder};
+use rust_tutorial::restaurant::seating::{Party, Restaurant};
+
+struct Noon;
+
+impl Clock for Noon {
+    fn now(&self) -> Timestamp {
+        Timestamp(1_710_504_000)
+    }
+}
+
+#[test]
+fn pizzas_get_their_tags_from_their_ingredients() {
+    let menu = Menu::standard();
+    let veggies = Pizza::lunch("veggies");
+    assert_eq!(veggies.allergens(), vec![Allergen::Gluten, Allergen::Dairy]);
+    assert_eq!(veggies.diets(), vec![Diet::Vegetarian]);
+
+    let vegan = PizzaBuilder::new(&menu)
+        .dough(Dough::GlutenFree)
+        .cheese(Cheese::Vegan)
+        .topping("veggies")
+        .build()
+        .unwrap();
+    assert!(vegan.allergens().is_empty());
+    assert_eq!(vegan.diets(), vec![Diet::Vegetarian, Diet::Vegan, Diet::GlutenFree]);
+
+    let anchovies = PizzaBuilder::new(&menu).cheese(Cheese::Parmesan).topping("anchovies").build().unwrap();
+    assert_eq!(anchovies.allergens(), vec![Allergen::Gluten, Allergen::Dairy, Allergen::Fish]);
+    assert!(anchovies.diets().is_empty());
+}
+
+#[test]
+fn needs_explain_what_doesnt_fit() {
+    let needs = DietaryNeeds { diets: vec![Diet::Vegetarian], allergies: vec![Allergen::Gluten] };
+    assert_eq!(needs.to_string(), "vegetarian, no gluten");
+    assert_eq!(
+        needs.problems_with(&Pizza::lunch("ham")),
+        vec!["regular dough has gluten", "ham is not vegetarian"]
+    );
+    assert!(needs.is_allergic_to(&Pizza::lunch("ham")));
+
+    let menu = needs.filter(&Menu::standard());
+    assert!(!menu.topping_names().contains(&"ham"));
+    // Sausage has gluten
+    assert!(!menu.topping_names().contains(&"sausage"));
+    assert!(menu.topping_names().contains(&"extra cheese"));
+}
+
+#[test]
+fn the_waiter_shows_the_menu_that_suits_the_party() {
+    let menu = Menu::standard();
+    let mut restaurant = Restaurant::standard();
+    let mut counter = Counter::standard();
+    let mut out: Vec<u8> = Vec::new();
+    let party = Party::new("Ana", 2).with_needs(DietaryNeeds::diet(Diet::Vegan));
+
+    let choice = PizzaBuilder::new(&menu).cheese(Cheese::Vegan).topping("ham");
+    let order = take_order(&mut restaurant, &mut counter, &party, choice, &Noon, &mut out).unwrap().unwrap();
+    // Not vegan, but it's their choice
+    assert_eq!(order.status(), OrderStatus::Ordered);
+
+    let out = String::from_utf8(out).unwrap();
+    assert!(out.contains("Today's toppings for vegan: veggies, mushrooms, olives, onions, peppers, pineapple\n"));
+    assert!(out.contains("Careful, ham is not vegan\n"));
+}
+
+#[test]
+fn allergens_are_never_served() {
+    let menu = Menu::standard();
+    let mut restaurant = Restaurant::standard();
+    let mut counter = Counter::standard();
+    let mut out: Vec<u8> = Vec::new();
+    let party = Party::new("Bruno", 2).with_needs(DietaryNeeds::allergy(Allergen::Fish));
+
+    let choice = PizzaBuilder::new(&menu).topping("anchovies");
+    let order = take_order(&mut restaurant, &mut counter, &party, choice, &Noon, &mut out).unwrap().unwrap();
+    assert_eq!(order.status(), OrderStatus::Cancelled);
+    assert!(String::from_utf8(out).unwrap().contains("Sorry, we won't serve that pizza: anchovies has fish\n"));
+}
+
+#[test]
+fn substitutes_suit_the_party_too() {
+    let menu = Menu::standard();
+    // No veggies left, ham is the closest in price but it's not vegetarian
+    let mut inventory = Inventory::new(Recipes::standard());
+    inventory.restock(Ingredient::Dough(Dough::Regular), 5);
+    inventory.restock(Ingredient::Cheese(Cheese::Mozzarella), 1000);
+    inventory.restock(Ingredient::topping("ham"), 5);
+    inventory.restock(Ingredient::topping("onions"), 5);
+    let mut restaurant = Restaurant::standard();
+    let mut counter = Counter::new(inventory);
+    let party = Party::new("Carla", 1).with_needs(DietaryNeeds::diet(Diet::Vegetarian));
+
+    let choice = PizzaBuilder::new(&menu).topping("veggies");
+    let order = take_order(&mut restaurant, &mut counter, &party, choice, &Noon, &mut Vec::new()).unwrap().unwrap();
+    assert_eq!(order.pizzas[0].toppings[0].name, "onions");
+}
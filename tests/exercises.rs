@@ -0,0 +1,33 @@
+use std::env;
+use std::process::{Command, Output};
+
+fn check(class: &str) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_rust_tutorial"))
+        .args(["check", class])
+        .env("RUST_TUTORIAL_CONFIG_DIR", env::temp_dir().join("rust_tutorial_exercises"))
+        .output()
+        .expect("Failed to start rust_tutorial")
+}
+
+// The repository ships the stubs, so every check fails with the todo!() message and a hint
+#[test]
+fn stubs_fail_with_hints() {
+    let output = check("15");
+    let stdout = String::from_utf8(output.stdout).unwrap();
+
+    assert!(!output.status.success());
+    assert!(stdout.contains("FAIL  get_sum adds two numbers: not yet implemented"), "{}", stdout);
+    assert!(stdout.contains("hint: a tuple is written as (x, y)"), "{}", stdout);
+    assert!(stdout.ends_with("0/5 tests passed\n"), "{}", stdout);
+    // the default panic message of todo!() must not leak to the terminal
+    assert!(output.stderr.is_empty(), "{}", String::from_utf8_lossy(&output.stderr));
+}
+
+#[test]
+fn classes_without_exercises_are_rejected() {
+    let output = check("17");
+    let stderr = String::from_utf8(output.stderr).unwrap();
+
+    assert!(!output.status.success());
+    assert!(stderr.contains("the available classes are: 15, 16"), "{}", stderr);
+}
@@ -0,0 +1,58 @@
+use std::path::PathBuf;
+use std::{env, fs};
+
+use rust_tutorial::book::{export, Format};
+use rust_tutorial::lessons::LESSONS;
+
+fn out_dir(name: &str) -> PathBuf {
+    let dir = env::temp_dir().join("rust_tutorial_export").join(name);
+    let _ = fs::remove_dir_all(&dir);
+    dir
+}
+
+fn chapter(dir: &PathBuf, prefix: &str) -> String {
+    let file = fs::read_dir(dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .find(|path| path.file_name().unwrap().to_string_lossy().starts_with(prefix))
+        .unwrap_or_else(|| panic!("no chapter {}", prefix));
+    fs::read_to_string(file).unwrap()
+}
+
+#[test]
+fn markdown_book_has_the_introduction_and_one_chapter_per_class() {
+    let dir = out_dir("markdown");
+    let index = export(Format::Markdown, &dir).unwrap();
+
+    // the introduction, one chapter per class and the index
+    assert_eq!(fs::read_dir(&dir).unwrap().count(), LESSONS.len() + 2);
+    let contents = fs::read_to_string(index).unwrap();
+    assert!(contents.contains("- [Introduction](00-introduction.md)"), "{}", contents);
+    assert!(chapter(&dir, "00-").contains("## Reference Cycles"));
+
+    let hash_maps = chapter(&dir, "18-");
+    assert!(hash_maps.contains("```rust\n// Class 18 - HashMaps\n"), "{}", hash_maps);
+    assert!(hash_maps.contains("```text\nBatman = Bruce Wayne\n"), "{}", hash_maps);
+    assert!(hash_maps.contains("[Next: Class 19 - Structs](19-class-19-structs.md)"), "{}", hash_maps);
+}
+
+#[test]
+fn html_book_escapes_the_code_and_converts_the_readme() {
+    let dir = out_dir("html");
+    export(Format::Html, &dir).unwrap();
+
+    let introduction = chapter(&dir, "00-");
+    assert!(introduction.contains("<h2>Smart Pointers</h2>"), "{}", introduction);
+    assert!(introduction.contains("<code>Rc&lt;T&gt;</code>"), "{}", introduction);
+
+    let io = chapter(&dir, "03-");
+    assert!(io.contains("io::Result&lt;()&gt;"), "{}", io);
+    assert!(io.contains("Hello Felipe! Nice to meet you"), "{}", io);
+}
+
+#[test]
+fn formats_are_parsed_from_the_command_line() {
+    assert_eq!("html".parse::<Format>(), Ok(Format::Html));
+    assert_eq!("markdown".parse::<Format>(), Ok(Format::Markdown));
+    assert!("pdf".parse::<Format>().is_err());
+}
@@ -0,0 +1,247 @@
+use std::{env, fs};
+
+use rust_tutorial::restaurant::clock::{Timestamp, UtcOffset};
+use rust_tutorial::restaurant::counter::Counter;
+use rust_tutorial::restaurant::front_of_house::{FrontOfHouse, Key};
+use rust_tutorial::restaurant::kitchen::KitchenConfig;
+use rust_tutorial::restaurant::order::OrderStatus;
+use rust_tutorial::restaurant::pizza_order::menu::Menu;
+use rust_tutorial::restaurant::promotions::Promotions;
+use rust_tutorial::restaurant::seating::Restaurant;
+use rust_tutorial::restaurant::store::OrderStore;
+
+// 2024-03-15 12:00:00
+const NOON: Timestamp = Timestamp(1_710_504_000);
+
+fn screen() -> FrontOfHouse {
+    FrontOfHouse::new(Restaurant::standard(), Counter::standard(), Menu::standard(), KitchenConfig::default(), NOON)
+}
+
+fn press(screen: &mut FrontOfHouse, keys: &[Key]) {
+    for key in keys {
+        assert!(screen.handle(*key));
+    }
+}
+
+fn seat(screen: &mut FrontOfHouse, party: &str) {
+    press(screen, &[Key::Char('s')]);
+    let typed: Vec<Key> = party.chars().map(Key::Char).collect();
+    press(screen, &typed);
+    press(screen, &[Key::Enter]);
+}
+
+fn shows(screen: &FrontOfHouse, text: &str) -> bool {
+    screen.render().iter().any(|line| line.contains(text))
+}
+
+#[test]
+fn seating_a_party_opens_a_ticket() {
+    let mut screen = screen();
+    seat(&mut screen, "Ana 2");
+
+    assert_eq!(screen.restaurant().occupant(1).map(|party| party.name.as_str()), Some("Ana"));
+    let orders = screen.orders();
+    assert_eq!(orders.len(), 1);
+    assert_eq!(orders[0].customer, "Ana");
+    assert_eq!(orders[0].status(), OrderStatus::Seated);
+    assert!(shows(&screen, "Ana seated at table 1"));
+    assert!(shows(&screen, "Ana (2)"));
+
+    // Without a size it's a party of one, without a name it's nobody
+    seat(&mut screen, "Bo");
+    assert_eq!(screen.orders()[1].customer, "Bo");
+    seat(&mut screen, " 3");
+    assert_eq!(screen.orders().len(), 2);
+    assert!(shows(&screen, "Type the name and the size of the party"));
+}
+
+#[test]
+fn an_order_goes_from_the_table_to_the_kitchen_and_back() {
+    let mut screen = screen();
+    seat(&mut screen, "Ana 2");
+
+    // A medium pizza with the second topping of the menu
+    press(&mut screen, &[Key::Char('o'), Key::Down, Key::Char(' ')]);
+    assert!(shows(&screen, "Pizza for Ana"));
+    assert!(shows(&screen, "Size: < medium >"));
+    press(&mut screen, &[Key::Enter]);
+    assert_eq!(screen.orders()[0].status(), OrderStatus::InKitchen);
+    assert_eq!(screen.orders()[0].pizzas.len(), 1);
+    assert!(shows(&screen, "Ticket 1 goes to cook 1, ready at 12:12:30"));
+    assert!(shows(&screen, "No pizzas waiting to be served"));
+
+    press(&mut screen, &[Key::Char('t'), Key::Char('t')]);
+    assert_eq!(screen.orders()[0].status(), OrderStatus::InKitchen);
+    press(&mut screen, &[Key::Char('t')]);
+    assert_eq!(screen.now(), NOON.plus(15 * 60));
+    assert_eq!(screen.orders()[0].status(), OrderStatus::Ready);
+    assert!(shows(&screen, "Waiting to be served: ticket 1 for Ana (2 min)"));
+
+    press(&mut screen, &[Key::Char('v')]);
+    assert_eq!(screen.orders()[0].status(), OrderStatus::Served);
+    press(&mut screen, &[Key::Char('p')]);
+    assert!(screen.orders().is_empty());
+    assert!(screen.restaurant().occupant(1).is_none());
+    assert!(shows(&screen, "Table 1 is free"));
+}
+
+#[test]
+fn keys_out_of_turn_only_explain_why() {
+    let mut screen = screen();
+    // Nothing to select yet
+    press(&mut screen, &[Key::Char('o'), Key::Char('v'), Key::Char('p'), Key::Down]);
+    assert!(screen.orders().is_empty());
+
+    seat(&mut screen, "Ana 2");
+    press(&mut screen, &[Key::Char('v')]);
+    assert!(shows(&screen, "Ticket 1 is seated, only ready orders can be served"));
+    press(&mut screen, &[Key::Char('p')]);
+    assert!(shows(&screen, "Ticket 1 is seated, only served orders can pay"));
+    assert_eq!(screen.orders()[0].status(), OrderStatus::Seated);
+
+    // Esc leaves the pizza without ordering it, and then leaves the screen
+    press(&mut screen, &[Key::Char('o'), Key::Right, Key::Esc]);
+    assert_eq!(screen.orders()[0].status(), OrderStatus::Seated);
+    assert!(!screen.handle(Key::Char('q')));
+}
+
+#[test]
+fn a_pizza_we_cant_make_leaves_the_party_at_the_table() {
+    let mut screen = screen();
+    seat(&mut screen, "Ana 2");
+
+    // Every topping of the menu is more than a pizza can have
+    press(&mut screen, &[Key::Char('o')]);
+    for _ in Menu::standard().toppings {
+        press(&mut screen, &[Key::Char(' '), Key::Down]);
+    }
+    assert!(shows(&screen, "Can't be made"));
+    press(&mut screen, &[Key::Enter]);
+    assert_eq!(screen.orders()[0].status(), OrderStatus::Cancelled);
+    assert!(shows(&screen, "Sorry, we can't make that pizza"));
+
+    // They can try again with something simpler
+    press(&mut screen, &[Key::Char('o'), Key::Char(' '), Key::Enter]);
+    assert_eq!(screen.orders()[0].status(), OrderStatus::InKitchen);
+}
+
+#[test]
+fn a_party_on_the_waitlist_gets_a_ticket_when_a_table_is_free() {
+    let mut screen = screen();
+    seat(&mut screen, "Ana 8");
+    seat(&mut screen, "Bo 7");
+    assert_eq!(screen.orders().len(), 1);
+    assert!(shows(&screen, "Waiting 1: Bo (7)"));
+
+    // Ana leaves without ordering
+    press(&mut screen, &[Key::Char('x')]);
+    let orders = screen.orders();
+    assert_eq!(orders.len(), 1);
+    assert_eq!(orders[0].customer, "Bo");
+    assert_eq!(orders[0].status(), OrderStatus::Seated);
+    assert_eq!(screen.restaurant().occupant(6).map(|party| party.name.as_str()), Some("Bo"));
+}
+
+#[test]
+fn the_party_pays_with_a_coupon() {
+    let counter = Counter::standard().with_promotions(Promotions::standard());
+    let mut screen = FrontOfHouse::new(Restaurant::standard(), counter, Menu::standard(), KitchenConfig::default(), NOON);
+    seat(&mut screen, "Ana 2");
+    press(&mut screen, &[Key::Char('o'), Key::Char(' '), Key::Enter]);
+    press(&mut screen, &[Key::Char('t'), Key::Char('t'), Key::Char('t'), Key::Char('v')]);
+
+    press(&mut screen, &[Key::Char('c')]);
+    let typed: Vec<Key> = "WELCOME5".chars().map(Key::Char).collect();
+    press(&mut screen, &typed);
+    press(&mut screen, &[Key::Enter]);
+    assert!(shows(&screen, "Ticket 1 pays with the coupon WELCOME5"));
+    press(&mut screen, &[Key::Char('p')]);
+    // $12.50 of veggies and $5.00 off, the tax is on $7.50 but the tip on $12.50
+    assert!(shows(&screen, "Ticket 1 paid $10.00"));
+    assert_eq!(screen.counter().loyalty().balance("Ana"), 7);
+}
+
+#[test]
+fn paid_and_cancelled_orders_go_to_the_history() {
+    let dir = env::temp_dir().join("rust_tutorial_front_of_house").join("history");
+    let _ = fs::remove_dir_all(&dir);
+    let path = dir.join("orders.jsonl");
+    let mut screen = screen().with_store(OrderStore::open(&path).unwrap());
+
+    seat(&mut screen, "Ana 2");
+    press(&mut screen, &[Key::Char('o'), Key::Char(' '), Key::Enter]);
+    press(&mut screen, &[Key::Char('t'), Key::Char('t'), Key::Char('t'), Key::Char('v'), Key::Char('p')]);
+    seat(&mut screen, "Bo 2");
+    press(&mut screen, &[Key::Char('x')]);
+
+    let store = OrderStore::open(&path).unwrap();
+    let orders = store.orders();
+    assert_eq!(orders.len(), 2);
+    assert_eq!(orders[0].order.customer, "Ana");
+    assert_eq!(orders[0].order.status(), OrderStatus::Paid);
+    assert!(orders[0].order.charged.is_some());
+    assert_eq!(orders[1].order.customer, "Bo");
+    assert_eq!(orders[1].order.status(), OrderStatus::Cancelled);
+}
+
+#[test]
+fn a_ticket_that_fails_to_be_saved_stays_on_the_screen() {
+    let dir = env::temp_dir().join("rust_tutorial_front_of_house").join("unwritable");
+    let _ = fs::remove_dir_all(&dir);
+    let path = dir.join("orders.jsonl");
+    let mut screen = screen().with_store(OrderStore::open(&path).unwrap());
+    seat(&mut screen, "Ana 2");
+    press(&mut screen, &[Key::Char('o'), Key::Char(' '), Key::Enter]);
+    press(&mut screen, &[Key::Char('t'), Key::Char('t'), Key::Char('t'), Key::Char('v')]);
+
+    // A folder where the history should be, the order can't be written
+    fs::create_dir_all(&path).unwrap();
+    press(&mut screen, &[Key::Char('p')]);
+    assert!(shows(&screen, "Ticket 1 paid"));
+    assert!(shows(&screen, "error: "));
+    assert_eq!(screen.orders()[0].status(), OrderStatus::Paid);
+    assert!(screen.restaurant().occupant(1).is_some());
+
+    // Once the file can be written, p saves the order that was already paid
+    fs::remove_dir(&path).unwrap();
+    press(&mut screen, &[Key::Char('p')]);
+    assert!(screen.orders().is_empty());
+    assert!(screen.restaurant().occupant(1).is_none());
+    let store = OrderStore::open(&path).unwrap();
+    assert_eq!(store.orders().len(), 1);
+    assert_eq!(store.orders()[0].order.status(), OrderStatus::Paid);
+}
+
+#[test]
+fn a_refused_pizza_goes_to_the_history_before_the_next_try() {
+    let dir = env::temp_dir().join("rust_tutorial_front_of_house").join("refused");
+    let _ = fs::remove_dir_all(&dir);
+    let path = dir.join("orders.jsonl");
+    let mut screen = screen().with_store(OrderStore::open(&path).unwrap());
+    seat(&mut screen, "Ana 2");
+    press(&mut screen, &[Key::Char('o')]);
+    for _ in Menu::standard().toppings {
+        press(&mut screen, &[Key::Char(' '), Key::Down]);
+    }
+    press(&mut screen, &[Key::Enter]);
+    press(&mut screen, &[Key::Char('o'), Key::Char(' '), Key::Enter]);
+
+    let store = OrderStore::open(&path).unwrap();
+    assert_eq!(store.orders().len(), 1);
+    assert_eq!(store.orders()[0].order.customer, "Ana");
+    assert_eq!(store.orders()[0].order.status(), OrderStatus::Cancelled);
+    assert_eq!(screen.orders()[0].status(), OrderStatus::InKitchen);
+}
+
+#[test]
+fn the_screen_shows_the_clocks_of_the_restaurant() {
+    let mut promotions = Promotions::standard();
+    promotions.utc_offset = UtcOffset::hours(2);
+    let counter = Counter::standard().with_promotions(promotions);
+    let mut screen = FrontOfHouse::new(Restaurant::standard(), counter, Menu::standard(), KitchenConfig::default(), NOON);
+    assert!(shows(&screen, "Class 21 pizzeria, 2024-03-15 14:00:00 (simulated)"));
+
+    seat(&mut screen, "Ana 2");
+    press(&mut screen, &[Key::Char('o'), Key::Char(' '), Key::Enter]);
+    assert!(shows(&screen, "Ticket 1 goes to cook 1, ready at 14:12:30"));
+}
@@ -0,0 +1,196 @@
+use std::sync::Arc;
+use std::{env, fs};
+
+use reqwest::blocking::Client;
+use reqwest::StatusCode;
+use serde_json::{json, Value};
+
+use rust_tutorial::restaurant::clock::{SimulatedClock, Timestamp};
+use rust_tutorial::restaurant::counter::Counter;
+use rust_tutorial::restaurant::http::{HttpServer, PizzaApi};
+use rust_tutorial::restaurant::inventory::{Ingredient, Inventory, Recipes};
+use rust_tutorial::restaurant::kitchen::KitchenConfig;
+use rust_tutorial::restaurant::pizza_order::menu::{Cheese, Dough, Menu};
+use rust_tutorial::restaurant::store::OrderStore;
+
+const NOON: Timestamp = Timestamp(1_710_504_000);
+
+struct Api {
+    server: HttpServer,
+    client: Client,
+    clock: Arc<SimulatedClock>,
+}
+
+impl Api {
+    fn start(test: &str) -> Api {
+        Api::start_with(test, |api| api)
+    }
+
+    fn start_with(test: &str, setup: impl FnOnce(PizzaApi) -> PizzaApi) -> Api {
+        let dir = env::temp_dir().join("rust_tutorial_http").join(test);
+        let _ = fs::remove_dir_all(&dir);
+        let store = OrderStore::open(&dir.join("orders.jsonl")).unwrap();
+        let clock = Arc::new(SimulatedClock::starting_at(NOON));
+        let api = setup(PizzaApi::new(Menu::standard(), KitchenConfig::default(), store, clock.clone()));
+        Api {
+            server: HttpServer::start("127.0.0.1:0", api).unwrap(),
+            client: Client::builder().no_proxy().build().unwrap(),
+            clock,
+        }
+    }
+
+    fn url(&self, path: &str) -> String {
+        format!("http://{}{}", self.server.address(), path)
+    }
+
+    fn get(&self, path: &str) -> (StatusCode, Value) {
+        let response = self.client.get(self.url(path)).send().unwrap();
+        (response.status(), serde_json::from_str(&response.text().unwrap()).unwrap())
+    }
+
+    fn post(&self, path: &str, body: Value) -> (StatusCode, Value) {
+        let response = self.client.post(self.url(path)).body(body.to_string()).send().unwrap();
+        (response.status(), serde_json::from_str(&response.text().unwrap()).unwrap())
+    }
+}
+
+#[test]
+fn the_menu_is_served_as_json() {
+    let api = Api::start("menu");
+    let (status, menu) = api.get("/menu");
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(menu["sizes"]["large"], 1400);
+    assert_eq!(menu["toppings"][0]["name"], "veggies");
+    assert_eq!(menu["toppings"][0]["diets"], json!(["vegetarian", "vegan", "gluten_free"]));
+    api.server.stop();
+}
+
+#[test]
+fn an_order_is_cooked_and_gets_ready() {
+    let api = Api::start("lifecycle");
+    let (status, order) = api.post(
+        "/orders",
+        json!({ "customer": "Ana", "pizzas": [{ "size": "large", "dough": "thin_crust", "toppings": ["ham"] }] }),
+    );
+
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(order["status"], "in_kitchen");
+    assert_eq!(order["price_cents"], 1400 + 180);
+    // 120s of prep, 30s for the ham and 480s for a thin crust
+    assert_eq!(order["ready_at"], NOON.seconds() + 630);
+
+    let path = format!("/orders/{}", order["id"]);
+    api.clock.advance(600);
+    assert_eq!(api.get(&path).1["status"], "in_kitchen");
+    api.clock.advance(30);
+    assert_eq!(api.get(&path).1["status"], "ready");
+
+    let (status, orders) = api.get("/orders");
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(orders.as_array().unwrap().len(), 1);
+    api.server.stop();
+}
+
+#[test]
+fn orders_can_be_cancelled_once() {
+    let api = Api::start("cancel");
+    let (_, order) = api.post("/orders", json!({ "customer": "Bruno", "pizzas": [{ "toppings": ["olives"] }] }));
+    let path = format!("/orders/{}/cancel", order["id"]);
+
+    let (status, cancelled) = api.post(&path, json!({}));
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(cancelled["status"], "cancelled");
+
+    let (status, again) = api.post(&path, json!({}));
+    assert_eq!(status, StatusCode::CONFLICT);
+    assert_eq!(again["error"], "an order that is cancelled can't be cancelled");
+    api.server.stop();
+}
+
+#[test]
+fn bad_requests_get_an_error_message() {
+    let api = Api::start("errors");
+
+    let (status, body) = api.post("/orders", json!({ "customer": "Carla", "pizzas": [{ "toppings": ["chocolate"] }] }));
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(body["error"], "we don't have chocolate as a topping");
+
+    let (status, _) = api.post("/orders", json!({ "customer": "Carla", "pizzas": [] }));
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+
+    let (status, _) = api.post("/orders", json!({ "pizzas": "many" }));
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let (status, body) = api.get("/orders/42");
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(body["error"], "there is no order 42");
+    api.server.stop();
+}
+
+#[test]
+fn orders_go_through_the_inventory_and_the_allergies() {
+    // Dough and cheese for a few pizzas, but only one portion of ham and nothing else
+    let mut pantry = Inventory::new(Recipes::standard());
+    pantry.restock(Ingredient::Dough(Dough::Regular), 5);
+    pantry.restock(Ingredient::Cheese(Cheese::Mozzarella), 1000);
+    pantry.restock(Ingredient::topping("ham"), 1);
+    let api = Api::start_with("inventory", |api| api.with_counter(Counter::new(pantry)));
+
+    // Two pizzas can't share the last portion
+    let ham = json!({ "toppings": ["ham"] });
+    let (status, body) = api.post("/orders", json!({ "customer": "Ana", "pizzas": [ham, ham] }));
+    assert_eq!(status, StatusCode::CONFLICT);
+    assert_eq!(body["error"], "we ran out of ham, 1 portion needed and 0 portions left");
+
+    let (status, _) = api.post("/orders", json!({ "customer": "Ana", "pizzas": [ham] }));
+    assert_eq!(status, StatusCode::CREATED);
+    let (status, body) = api.post("/orders", json!({ "customer": "Bruno", "pizzas": [ham] }));
+    assert_eq!(status, StatusCode::CONFLICT);
+    assert_eq!(body["error"], "we ran out of ham, 1 portion needed and 0 portions left");
+
+    let anchovies = json!({ "customer": "Carla", "allergies": ["fish"], "pizzas": [{ "toppings": ["anchovies"] }] });
+    let (status, body) = api.post("/orders", anchovies);
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(body["error"], "we won't serve that pizza: anchovies has fish");
+
+    // Only Ana's order was taken, the refused ones are in the history, cancelled
+    let (_, orders) = api.get("/orders");
+    let statuses: Vec<&str> = orders.as_array().unwrap().iter().map(|order| order["status"].as_str().unwrap()).collect();
+    assert_eq!(statuses, vec!["cancelled", "in_kitchen", "cancelled", "cancelled"]);
+    api.server.stop();
+}
+
+#[test]
+fn a_cancelled_order_gives_back_its_cook_and_its_ingredients() {
+    // Only one portion of ham
+    let mut pantry = Inventory::new(Recipes::standard());
+    pantry.restock(Ingredient::Dough(Dough::Regular), 10);
+    pantry.restock(Ingredient::Cheese(Cheese::Mozzarella), 6000);
+    pantry.restock(Ingredient::topping("olives"), 10);
+    pantry.restock(Ingredient::topping("ham"), 1);
+    let api = Api::start_with("release", |api| api.with_counter(Counter::new(pantry)));
+    let olives = json!({ "customer": "Ana", "pizzas": [{ "toppings": ["olives"] }] });
+    let ham = json!({ "customer": "Bruno", "pizzas": [{ "toppings": ["ham"] }] });
+
+    // Two cooks, 750s for each pizza: the ham waits for the first cook
+    let (_, first) = api.post("/orders", olives.clone());
+    api.post("/orders", olives.clone());
+    let (_, waiting) = api.post("/orders", ham.clone());
+    api.post("/orders", olives.clone());
+    assert_eq!(waiting["ready_at"], NOON.seconds() + 1500);
+
+    // The first cook drops the first order a minute in and starts the ham
+    api.clock.advance(60);
+    api.post(&format!("/orders/{}/cancel", first["id"]), json!({}));
+    let path = format!("/orders/{}", waiting["id"]);
+    assert_eq!(api.get(&path).1["ready_at"], NOON.seconds() + 60 + 750);
+
+    // The ham comes back, and the next order gets the first cook right away
+    let (status, _) = api.post(&format!("{}/cancel", path), json!({}));
+    assert_eq!(status, StatusCode::OK);
+    let (status, next) = api.post("/orders", ham);
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(next["ready_at"], NOON.seconds() + 60 + 750);
+    api.server.stop();
+}
@@ -0,0 +1,116 @@
+use rust_tutorial::restaurant::clock::{Clock, Timestamp};
+use rust_tutorial::restaurant::counter::Counter;
+use rust_tutorial::restaurant::inventory::{Ingredient, Inventory, InventoryError, Recipes, StockAlert, Swap};
+use rust_tutorial::restaurant::order::OrderStatus;
+use rust_tutorial::restaurant::pizza_order::help_customer::take_order;
+use rust_tutorial::restaurant::pizza_order::menu::{Cheese, Dough, Menu, Size};
+use rust_tutorial::restaurant::pizza_order::{Pizza, PizzaBuilder};
+use rust_tutorial::restaurant::seating::{Party, Restaurant};
+
+struct Noon;
+
+impl Clock for Noon {
+    fn now(&self) -> Timestamp {
+        Timestamp(1_710_504_000)
+    }
+}
+
+// Enough dough and mozzarella for a few pizzas, and only the toppings we give
+fn small_pantry(toppings: &[(&str, u32)]) -> Inventory {
+    let mut inventory = Inventory::new(Recipes::standard());
+    inventory.restock(Ingredient::Dough(Dough::Regular), 3);
+    inventory.restock(Ingredient::Cheese(Cheese::Mozzarella), 500);
+    for (name, portions) in toppings {
+        inventory.restock(Ingredient::topping(name), *portions);
+    }
+    inventory
+}
+
+#[test]
+fn recipes_grow_with_the_size() {
+    let recipes = Recipes::standard();
+    let pizza = PizzaBuilder::new(&Menu::standard()).size(Size::Large).topping("ham").build().unwrap();
+
+    assert_eq!(
+        recipes.ingredients(&pizza),
+        vec![
+            (Ingredient::Dough(Dough::Regular), 1),
+            (Ingredient::Cheese(Cheese::Mozzarella), 180),
+            (Ingredient::topping("ham"), 2),
+        ]
+    );
+    let no_cheese = PizzaBuilder::new(&Menu::standard()).cheese(Cheese::NoCheese).build().unwrap();
+    assert_eq!(recipes.ingredients(&no_cheese), vec![(Ingredient::Dough(Dough::Regular), 1)]);
+}
+
+#[test]
+fn the_lunch_special_is_not_infinite() {
+    let mut inventory = small_pantry(&[("veggies", 2)]);
+    inventory.alert_at(Ingredient::topping("veggies"), 1);
+
+    assert_eq!(
+        inventory.consume(&[Pizza::lunch("veggies")]),
+        Ok(vec![StockAlert { ingredient: Ingredient::topping("veggies"), left: 1 }])
+    );
+    // Already low, we don't warn again
+    assert_eq!(inventory.consume(&[Pizza::lunch("veggies")]), Ok(Vec::new()));
+
+    let err = inventory.consume(&[Pizza::lunch("veggies")]).unwrap_err();
+    assert_eq!(err, InventoryError::OutOfStock { ingredient: Ingredient::topping("veggies"), needed: 1, left: 0 });
+    assert_eq!(err.to_string(), "we ran out of veggies, 1 portion needed and 0 portions left");
+    assert_eq!(inventory.low_stock(), vec![StockAlert { ingredient: Ingredient::topping("veggies"), left: 0 }]);
+
+    inventory.restock(Ingredient::topping("veggies"), 10);
+    assert!(inventory.consume(&[Pizza::lunch("veggies")]).is_ok());
+    assert!(inventory.low_stock().is_empty());
+}
+
+#[test]
+fn an_order_takes_everything_or_nothing() {
+    let mut inventory = small_pantry(&[("ham", 1), ("olives", 5)]);
+    let pizzas = [Pizza::lunch("olives"), Pizza::lunch("ham"), Pizza::lunch("ham")];
+
+    assert!(inventory.consume(&pizzas).is_err());
+    assert_eq!(inventory.stock(&Ingredient::Dough(Dough::Regular)), 3);
+    assert_eq!(inventory.stock(&Ingredient::topping("olives")), 5);
+}
+
+#[test]
+fn a_missing_topping_is_replaced_by_the_closest_one() {
+    let menu = Menu::standard();
+    // Pineapple costs 130, mushrooms 120, but pineapple doesn't go with sausage
+    let inventory = small_pantry(&[("sausage", 5), ("pineapple", 5), ("mushrooms", 5), ("onions", 5)]);
+    let pizza = PizzaBuilder::new(&menu).topping("sausage").topping("veggies").build().unwrap();
+
+    let (made, swaps) = inventory.substitute(&pizza, &menu).unwrap();
+    assert_eq!(swaps, vec![Swap { missing: String::from("veggies"), replacement: String::from("mushrooms") }]);
+    assert_eq!(made.to_string(), "medium pizza with regular dough, mozzarella, sausage and mushrooms");
+
+    let thin = PizzaBuilder::new(&menu).dough(Dough::ThinCrust).topping("onions").build().unwrap();
+    assert_eq!(
+        inventory.substitute(&thin, &menu),
+        Err(InventoryError::OutOfStock { ingredient: Ingredient::Dough(Dough::ThinCrust), needed: 1, left: 0 })
+    );
+}
+
+#[test]
+fn the_waiter_substitutes_or_refuses() {
+    let menu = Menu::standard();
+    let mut restaurant = Restaurant::standard();
+    let mut counter = Counter::new(small_pantry(&[("olives", 1)]));
+    let mut out: Vec<u8> = Vec::new();
+
+    let choice = PizzaBuilder::new(&menu).topping("ham");
+    let ana = take_order(&mut restaurant, &mut counter, &Party::new("Ana", 2), choice, &Noon, &mut out).unwrap().unwrap();
+    assert_eq!(ana.status(), OrderStatus::Ordered);
+    assert_eq!(ana.pizzas[0].toppings[0].name, "olives");
+
+    let choice = PizzaBuilder::new(&menu).topping("ham");
+    let bruno = take_order(&mut restaurant, &mut counter, &Party::new("Bruno", 2), choice, &Noon, &mut out).unwrap().unwrap();
+    assert_eq!(bruno.status(), OrderStatus::Cancelled);
+
+    let out = String::from_utf8(out).unwrap();
+    assert!(out.contains("We ran out of ham, your pizza comes with olives instead"));
+    assert!(out.contains("Sorry, we ran out of ham, 1 portion needed and 0 portions left"));
+    assert_eq!(counter.inventory().stock(&Ingredient::topping("olives")), 0);
+}
@@ -0,0 +1,91 @@
+use rust_tutorial::restaurant::clock::{Clock, SimulatedClock, Timestamp};
+use rust_tutorial::restaurant::counter::Counter;
+use rust_tutorial::restaurant::kitchen::{Kitchen, KitchenConfig, KitchenReport};
+use rust_tutorial::restaurant::order::{Order, OrderStatus};
+use rust_tutorial::restaurant::pizza_order::help_customer::take_order;
+use rust_tutorial::restaurant::pizza_order::menu::{Dough, Menu};
+use rust_tutorial::restaurant::pizza_order::PizzaBuilder;
+use rust_tutorial::restaurant::seating::{Party, Restaurant};
+
+const OPENING: Timestamp = Timestamp(1_710_504_000);
+
+// Six customers, one every two minutes, each with a pizza with one topping:
+// 120s of prep + 30s for the topping + 600s in the oven = 750s per pizza
+fn lunch_service(cooks: usize) -> KitchenReport {
+    let clock = SimulatedClock::starting_at(OPENING);
+    let menu = Menu::standard();
+    let mut restaurant = Restaurant::standard();
+    let mut counter = Counter::standard();
+    let mut kitchen = Kitchen::open(KitchenConfig { cooks, ..KitchenConfig::default() });
+    for customer in ["Ana", "Bruno", "Carla", "Davi", "Elisa", "Fabio"] {
+        let party = Party::new(customer, 2);
+        let choice = PizzaBuilder::new(&menu).topping("ham");
+        let order = take_order(&mut restaurant, &mut counter, &party, choice, &clock, &mut Vec::new()).unwrap().unwrap();
+        kitchen.send(order, clock.now()).unwrap();
+        clock.advance(120);
+    }
+    kitchen.close()
+}
+
+fn waits(report: &KitchenReport) -> Vec<u64> {
+    report.orders.iter().map(|cooked| cooked.wait_seconds()).collect()
+}
+
+#[test]
+fn every_order_comes_back_ready() {
+    let report = lunch_service(2);
+
+    assert_eq!(report.orders.len(), 6);
+    for cooked in &report.orders {
+        assert_eq!(cooked.order.status(), OrderStatus::Ready);
+        assert_eq!(cooked.order.time_of(OrderStatus::InKitchen), Some(cooked.queued_at));
+        assert_eq!(cooked.order.time_of(OrderStatus::Ready), Some(cooked.ready_at));
+    }
+}
+
+#[test]
+fn one_cook_makes_everybody_wait_in_line() {
+    let report = lunch_service(1);
+
+    assert_eq!(waits(&report), vec![0, 630, 1260, 1890, 2520, 3150]);
+    assert_eq!(report.service_seconds(), 6 * 750);
+    assert_eq!(report.longest_queue(), 5);
+
+    let mut out: Vec<u8> = Vec::new();
+    report.print(&mut out).unwrap();
+    let out = String::from_utf8(out).unwrap();
+    assert!(out.contains("6 pizzas in 1h 15m with 1 cooks: 4.8 pizzas per hour"));
+    assert!(out.contains("Longest queue: 5 orders, average wait: 26m 15s"));
+}
+
+#[test]
+fn more_cooks_mean_shorter_waits() {
+    let report = lunch_service(3);
+
+    // Cook 1 takes Ana and Davi, cook 2 Bruno and Elisa, cook 3 Carla and Fabio
+    let cooks: Vec<usize> = report.orders.iter().map(|cooked| cooked.cook).collect();
+    assert_eq!(cooks, vec![1, 2, 3, 1, 2, 3]);
+    assert_eq!(waits(&report), vec![0, 0, 0, 390, 390, 390]);
+    assert!(report.pizzas_per_hour() > lunch_service(1).pizzas_per_hour());
+}
+
+#[test]
+fn the_simulation_is_the_same_every_time() {
+    for _ in 0..5 {
+        let report = lunch_service(2);
+        assert_eq!(waits(&report), vec![0, 0, 510, 510, 1020, 1020]);
+    }
+}
+
+#[test]
+fn thin_crusts_come_out_of_the_oven_earlier() {
+    let menu = Menu::standard();
+    let mut order = Order::seat("Ana", OPENING);
+    let pizza = PizzaBuilder::new(&menu).dough(Dough::ThinCrust).build().unwrap();
+    order.place(vec![pizza], OPENING).unwrap();
+
+    let mut kitchen = Kitchen::open(KitchenConfig::default());
+    kitchen.send(order, OPENING).unwrap();
+    let report = kitchen.close();
+    assert_eq!(report.orders[0].total_seconds(), 120 + 480);
+}
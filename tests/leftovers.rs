@@ -0,0 +1,57 @@
+use rust_tutorial::restaurant::clock::Timestamp;
+use rust_tutorial::restaurant::order::Order;
+use rust_tutorial::restaurant::pizza_order::help_customer::eat_pizzas;
+use rust_tutorial::restaurant::pizza_order::menu::{Menu, Size};
+use rust_tutorial::restaurant::pizza_order::{EatError, Pizza, PizzaBuilder};
+
+#[test]
+fn eating_takes_slices_until_the_pizza_is_finished() {
+    let mut pizza = Pizza::lunch("veggies");
+    assert_eq!(pizza.slices(), 8);
+
+    assert_eq!(pizza.eat(3), Ok(5));
+    assert_eq!(pizza.eat(6), Err(EatError::NotEnoughSlices { asked: 6, left: 5 }));
+    assert_eq!(pizza.slices_left(), 5);
+    assert_eq!(pizza.eat(5), Ok(0));
+    assert!(pizza.is_finished());
+
+    assert_eq!(pizza.eat(1), Err(EatError::Finished));
+    assert_eq!(pizza.eat(1).unwrap_err().to_string(), "the pizza is already finished");
+}
+
+#[test]
+fn leftovers_go_home_in_a_box() {
+    let mut pizza = PizzaBuilder::new(&Menu::standard()).size(Size::Large).topping("ham").build().unwrap();
+    pizza.eat(7).unwrap();
+
+    // box_leftovers takes the pizza, it can't be eaten at the table anymore
+    let mut leftovers = pizza.box_leftovers().unwrap();
+    assert_eq!(leftovers.slices(), 3);
+    assert_eq!(leftovers.to_string(), "takeaway box with 3 slices of large pizza with regular dough, mozzarella and ham");
+    assert_eq!(leftovers.eat(3), Ok(0));
+
+    let mut finished = Pizza::lunch("ham");
+    finished.eat(8).unwrap();
+    assert_eq!(finished.box_leftovers(), Err(EatError::Finished));
+}
+
+#[test]
+fn the_customer_eats_and_takes_the_rest() {
+    let mut order = Order::seat("Ana", Timestamp(100));
+    order.place(vec![Pizza::lunch("olives"), Pizza::lunch("ham")], Timestamp(160)).unwrap();
+    let mut out: Vec<u8> = Vec::new();
+
+    let boxes = eat_pizzas(&order, 8, &mut out).unwrap();
+    assert!(boxes.is_empty());
+    let boxes = eat_pizzas(&order, 5, &mut out).unwrap();
+    assert_eq!(boxes.iter().map(|leftovers| leftovers.slices()).collect::<Vec<u32>>(), vec![3, 3]);
+    // The order still has the pizzas as they were served
+    assert_eq!(order.pizzas[0].slices_left(), 8);
+}
+
+#[test]
+fn pizzas_saved_before_slices_were_never_eaten() {
+    let json = r#"{"size":"small","dough":"regular","cheese":"mozzarella","toppings":[]}"#;
+    let pizza: Pizza = serde_json::from_str(json).unwrap();
+    assert_eq!(pizza.slices_left(), 6);
+}
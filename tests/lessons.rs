@@ -0,0 +1,108 @@
+// Golden tests for the lessons
+// Every lesson writes its output to the sink it receives, so we run each one through the binary and
+// compare the captured output with the snapshot in tests/snapshots/<lesson>.txt.
+// After an intentional change in a lesson, regenerate the snapshots with:
+//     UPDATE_SNAPSHOTS=1 cargo test --test lessons
+
+use std::io::Write;
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+use std::{env, fs};
+
+fn run_lesson(name: &str, input: &str) -> String {
+    // Every run is a new learner, without the orders and loyalty points of the last one
+    let config_dir = env::temp_dir().join("rust_tutorial_lessons").join(name);
+    let _ = fs::remove_dir_all(&config_dir);
+    let mut child = Command::new(env!("CARGO_BIN_EXE_rust_tutorial"))
+        .args(["run", name])
+        // error_handling creates lines.txt, we don't want it in the repository
+        .current_dir(env::temp_dir())
+        // and the runs shouldn't show up in the progress of whoever is running the tests
+        .env("RUST_TUTORIAL_CONFIG_DIR", &config_dir)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("Failed to start rust_tutorial");
+
+    child.stdin.take().unwrap().write_all(input.as_bytes()).unwrap();
+    let output = child.wait_with_output().unwrap();
+    assert!(
+        output.status.success(),
+        "{} failed: {}",
+        name,
+        String::from_utf8_lossy(&output.stderr)
+    );
+    String::from_utf8(output.stdout).unwrap()
+}
+
+fn assert_snapshot(name: &str, input: &str) {
+    let actual = run_lesson(name, input);
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/snapshots")
+        .join(format!("{}.txt", name));
+
+    if env::var_os("UPDATE_SNAPSHOTS").is_some() {
+        fs::write(&path, &actual).unwrap();
+        return;
+    }
+
+    let expected = fs::read_to_string(&path).unwrap_or_else(|_| {
+        panic!("Missing snapshot {}, run with UPDATE_SNAPSHOTS=1", path.display())
+    });
+    assert_eq!(actual, expected, "the output of {} changed", name);
+}
+
+macro_rules! snapshot_tests {
+    ($($name:ident),+ $(,)?) => {
+        $(
+            #[test]
+            fn $name() {
+                assert_snapshot(stringify!($name), "");
+            }
+        )+
+    };
+}
+
+snapshot_tests![
+    hello_world,
+    string_example,
+    constants_shadow,
+    data_types,
+    if_clauses,
+    comp_operator,
+    basic_arrays,
+    mutable_arrays,
+    basic_tuples,
+    strings_second_example,
+    casting,
+    enums,
+    vectors,
+    functions,
+    generics,
+    ownership,
+    hash_maps,
+    structs,
+    traits,
+    modules,
+    error_handling,
+    immutable_reference,
+    mutable_reference,
+    question_marker,
+    iterators,
+    closures,
+    closures2,
+    creating_boxes,
+    binary_tree_box,
+    reborrow,
+    second_reborrow,
+    reborrow_thread,
+    reborrow_error,
+    reborrow_chain,
+    reborrow_hard,
+];
+
+#[test]
+fn io_example() {
+    assert_snapshot("io_example", "Felipe\n");
+}
@@ -0,0 +1,40 @@
+// The types of the lessons are public, so they can be used outside of the functions that teach them
+
+use rust_tutorial::enums::Days;
+use rust_tutorial::functions::{change_vector, get_sum};
+use rust_tutorial::smart_pointers::TreeNode;
+use rust_tutorial::traits::{Circle, Rectangle, Shape};
+
+#[test]
+fn days_know_the_weekend() {
+    assert!(Days::Saturday.is_weekend());
+    assert!(Days::Sunday.is_weekend());
+    assert!(!Days::Monday.is_weekend());
+}
+
+#[test]
+fn shapes_calculate_their_area() {
+    assert_eq!(Rectangle::new(2.0, 3.0).area(), 6.0);
+    assert!((Circle::new(2.0, 2.0).area() - std::f32::consts::PI).abs() < f32::EPSILON);
+}
+
+// insert fills the tree level by level, from left to right
+#[test]
+fn tree_insert_fills_the_first_free_position() {
+    let mut tree: TreeNode<i32> = TreeNode::new(1).left(TreeNode::new(2));
+    tree.insert(3);
+    tree.insert(4);
+
+    let right = tree.right.as_ref().unwrap();
+    let left = tree.left.as_ref().unwrap();
+    assert_eq!(right.key, 3);
+    assert_eq!(left.left.as_ref().unwrap().key, 4);
+}
+
+#[test]
+fn functions_are_reusable() {
+    let mut values = vec![1, 2, 3];
+    change_vector(&mut values);
+    assert_eq!(values, vec![2, 4, 6]);
+    assert_eq!(get_sum(2, 3), 5);
+}
@@ -0,0 +1,47 @@
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::{env, fs};
+
+fn config_dir(test: &str) -> PathBuf {
+    let dir = env::temp_dir().join("rust_tutorial_menu").join(test);
+    let _ = fs::remove_dir_all(&dir);
+    dir
+}
+
+fn tutorial(config_dir: &Path, args: &[&str], input: &str) -> String {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_rust_tutorial"))
+        .args(args)
+        .current_dir(env::temp_dir())
+        .env("RUST_TUTORIAL_CONFIG_DIR", config_dir)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .expect("Failed to start rust_tutorial");
+
+    std::io::Write::write_all(&mut child.stdin.take().unwrap(), input.as_bytes()).unwrap();
+    let output = child.wait_with_output().unwrap();
+    assert!(output.status.success());
+    String::from_utf8(output.stdout).unwrap()
+}
+
+fn row<'a>(progress: &'a str, heading: &str) -> &'a str {
+    progress
+        .lines()
+        .find(|line| line.starts_with(heading))
+        .unwrap_or_else(|| panic!("{} is not in the table:\n{}", heading, progress))
+}
+
+#[test]
+fn the_menu_asks_which_class_when_a_number_is_two_classes() {
+    let dir = config_dir("menu");
+    let output = tutorial(&dir, &["menu"], "22\nReferences part 2\nq\n");
+    assert!(
+        output.contains("`22` is more than one class, type its title: Class 22 - Error Handling or Class 22 - References part 2"),
+        "{}",
+        output
+    );
+
+    let progress = tutorial(&dir, &["progress"], "");
+    assert!(row(&progress, "Class 22 - References part 2").contains("    1  -"), "{}", progress);
+    assert!(row(&progress, "Class 22 - Error Handling").contains("    0  -"), "{}", progress);
+}
@@ -0,0 +1,194 @@
+use std::panic::{self, AssertUnwindSafe};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::{env, fs};
+
+use rust_tutorial::restaurant::clock::{Timestamp, UtcOffset};
+use rust_tutorial::restaurant::counter::Counter;
+use rust_tutorial::restaurant::order::{Order, OrderStatus};
+use rust_tutorial::restaurant::pizza_order::Pizza;
+use rust_tutorial::restaurant::store::{self, History, OrderFilter, OrderStore, StoredOrder};
+
+const MARCH_15: Timestamp = Timestamp(1_710_504_000);
+const MARCH_16: Timestamp = Timestamp(1_710_504_000 + 86_400);
+
+fn temp_dir(test: &str) -> PathBuf {
+    let dir = env::temp_dir().join("rust_tutorial_order_store").join(test);
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+fn ordered(customer: &str, topping: &str, at: Timestamp) -> Order {
+    let mut order = Order::seat(customer, at);
+    order.place(vec![Pizza::lunch(topping)], at.plus(60)).unwrap();
+    order
+}
+
+#[test]
+fn the_history_survives_a_restart() {
+    let path = temp_dir("restart").join("orders.jsonl");
+    let mut store = OrderStore::open(&path).unwrap();
+    let ana = store.insert(&ordered("Ana", "ham", MARCH_15)).unwrap();
+    store.insert(&ordered("Bruno", "olives", MARCH_16)).unwrap();
+
+    let mut cancelled = store.get(ana).unwrap().order.clone();
+    cancelled.cancel(MARCH_15.plus(120)).unwrap();
+    store.update(ana, &cancelled).unwrap();
+
+    let store = OrderStore::open(&path).unwrap();
+    assert_eq!(store.orders().len(), 2);
+    assert_eq!(store.get(ana).unwrap().order.status(), OrderStatus::Cancelled);
+    // Nothing is rewritten, the change is a new line
+    assert_eq!(fs::read_to_string(&path).unwrap().lines().count(), 3);
+    assert!(store.corrupted().is_empty());
+}
+
+fn ids(found: Vec<&StoredOrder>) -> Vec<u64> {
+    found.iter().map(|stored| stored.id).collect()
+}
+
+#[test]
+fn orders_are_found_by_date_customer_status_and_topping() {
+    let path = temp_dir("queries").join("orders.jsonl");
+    let mut store = OrderStore::open(&path).unwrap();
+    store.insert(&ordered("Ana", "ham", MARCH_15)).unwrap();
+    store.insert(&ordered("Bruno", "olives", MARCH_15)).unwrap();
+    store.insert(&ordered("Ana", "olives", MARCH_16)).unwrap();
+    store.insert(&Order::seat("Carla", MARCH_16)).unwrap();
+
+    assert_eq!(ids(store.on_date("2024-03-15")), vec![1, 2]);
+    assert_eq!(ids(store.by_customer("ana")), vec![1, 3]);
+    assert_eq!(ids(store.by_status(OrderStatus::Seated)), vec![4]);
+    assert_eq!(ids(store.with_topping("olives")), vec![2, 3]);
+
+    let filter = OrderFilter {
+        customer: Some(String::from("Ana")),
+        topping: Some(String::from("olives")),
+        ..OrderFilter::default()
+    };
+    assert_eq!(ids(store.find(&filter)), vec![3]);
+}
+
+#[test]
+fn a_broken_last_line_is_reported_and_the_rest_is_kept() {
+    let path = temp_dir("corrupted").join("orders.jsonl");
+    let mut store = OrderStore::open(&path).unwrap();
+    store.insert(&ordered("Ana", "ham", MARCH_15)).unwrap();
+    let mut content = fs::read_to_string(&path).unwrap();
+    content.push_str("{\"id\": 2, \"order\": {\"custo");
+    fs::write(&path, content).unwrap();
+
+    let mut store = OrderStore::open(&path).unwrap();
+    assert_eq!(store.orders().len(), 1);
+    assert_eq!(store.corrupted().len(), 1);
+    assert_eq!(store.corrupted()[0].number, 2);
+    assert!(store.corrupted()[0].trailing);
+
+    // The next order goes to a line of its own, and the broken line is now in the middle
+    store.insert(&ordered("Bruno", "ham", MARCH_15)).unwrap();
+    let store = OrderStore::open(&path).unwrap();
+    assert_eq!(store.orders().len(), 2);
+    assert!(!store.corrupted()[0].trailing);
+}
+
+#[test]
+fn an_order_without_history_is_a_corrupt_line() {
+    let dir = temp_dir("no_history");
+    let path = dir.join("orders.jsonl");
+    let mut store = OrderStore::open(&path).unwrap();
+    store.insert(&ordered("Ana", "ham", MARCH_15)).unwrap();
+    let mut content = fs::read_to_string(&path).unwrap();
+    content.push_str("{\"id\": 2, \"order\": {\"customer\": \"Bruno\", \"pizzas\": [], \"history\": []}}\n");
+    fs::write(&path, content).unwrap();
+
+    let store = OrderStore::open(&path).unwrap();
+    assert_eq!(ids(store.find(&OrderFilter::default())), vec![1]);
+    assert_eq!(store.corrupted().len(), 1);
+    assert_eq!(store.corrupted()[0].number, 2);
+    assert!(store.corrupted()[0].error.contains("the order has no history"));
+
+    // The commands report the line instead of crashing
+    let (orders, warnings) = tutorial(&dir, &["orders"]);
+    assert_eq!(orders.lines().count(), 2);
+    assert!(warnings.contains("line 2 at the end of"));
+    let (_, warnings) = tutorial(&dir, &["report", "--date", "2024-03-15"]);
+    assert!(warnings.contains("1 corrupted lines"));
+}
+
+#[test]
+fn orders_are_listed_on_the_clocks_of_the_restaurant() {
+    let ana = StoredOrder { id: 1, order: ordered("Ana", "ham", MARCH_15) };
+    let mut out = Vec::new();
+    store::print_orders(&[&ana], UtcOffset::hours(-3), &mut out).unwrap();
+    let out = String::from_utf8(out).unwrap();
+    assert!(out.lines().nth(1).unwrap().contains("2024-03-15 09:00:00"));
+}
+
+#[test]
+fn a_lesson_that_panics_gives_the_history_back() {
+    let dir = temp_dir("panic");
+    let history = |file: &str| History {
+        store: OrderStore::open(&dir.join(file)).unwrap(),
+        counter: Counter::standard(),
+    };
+
+    let outer = store::with_history(history("outer.jsonl"), || {
+        let inner = panic::catch_unwind(AssertUnwindSafe(|| store::with_history(history("inner.jsonl"), || panic!("the lesson failed"))));
+        assert!(inner.is_err());
+        store::using_history(|history| history.unwrap().store.path().to_path_buf())
+    });
+    assert_eq!(outer, dir.join("outer.jsonl"));
+    assert!(store::using_history(|history| history.is_none()));
+}
+
+fn tutorial(config_dir: &Path, args: &[&str]) -> (String, String) {
+    let output = Command::new(env!("CARGO_BIN_EXE_rust_tutorial"))
+        .args(args)
+        .current_dir(config_dir)
+        .env("RUST_TUTORIAL_CONFIG_DIR", config_dir)
+        .output()
+        .expect("Failed to start rust_tutorial");
+    assert!(output.status.success());
+    (String::from_utf8(output.stdout).unwrap(), String::from_utf8(output.stderr).unwrap())
+}
+
+#[test]
+fn running_every_class_or_exporting_the_book_records_nothing() {
+    let dir = temp_dir("all");
+    tutorial(&dir, &["run", "--all"]);
+    tutorial(&dir, &["export", "--format", "markdown", "--out", "book"]);
+
+    assert!(dir.join("book").join("index.md").exists());
+    assert!(!dir.join("orders.jsonl").exists());
+    assert!(!dir.join("loyalty.json").exists());
+    // The progress of the classes is still recorded
+    assert!(dir.join("progress.json").exists());
+}
+
+#[test]
+fn showing_a_class_records_nothing() {
+    let dir = temp_dir("show");
+    tutorial(&dir, &["show", "modules"]);
+
+    assert!(!dir.join("orders.jsonl").exists());
+    assert!(!dir.join("loyalty.json").exists());
+}
+
+#[test]
+fn the_orders_of_the_modules_class_are_listed() {
+    let dir = temp_dir("command");
+    tutorial(&dir, &["run", "modules"]);
+    tutorial(&dir, &["run", "modules"]);
+    let mut content = fs::read_to_string(dir.join("orders.jsonl")).unwrap();
+    content.push_str("not json");
+    fs::write(dir.join("orders.jsonl"), content).unwrap();
+
+    let (orders, warnings) = tutorial(&dir, &["orders", "--status", "paid", "--topping", "veggies"]);
+    assert_eq!(orders.lines().count(), 3);
+    assert!(orders.lines().nth(1).unwrap().contains("Customer     paid"));
+    assert!(warnings.contains("line 3 at the end of"));
+
+    let (orders, _) = tutorial(&dir, &["orders", "--status", "cancelled"]);
+    assert_eq!(orders.lines().count(), 1);
+}
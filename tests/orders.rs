@@ -0,0 +1,86 @@
+use rust_tutorial::restaurant::clock::{Clock, Timestamp};
+use rust_tutorial::restaurant::counter::Counter;
+use rust_tutorial::restaurant::order::{Order, OrderError, OrderStatus};
+use rust_tutorial::restaurant::pizza_order::help_customer::take_order;
+use rust_tutorial::restaurant::pizza_order::menu::Menu;
+use rust_tutorial::restaurant::pizza_order::{Pizza, PizzaBuilder};
+use rust_tutorial::restaurant::seating::{Party, Restaurant};
+
+// Always the same time, so the orders can be compared
+struct Noon;
+
+impl Clock for Noon {
+    fn now(&self) -> Timestamp {
+        Timestamp(1_710_504_000)
+    }
+}
+
+#[test]
+fn an_order_goes_from_the_table_to_the_bill() {
+    let mut order = Order::seat("Ana", Timestamp(100));
+    order.place(vec![Pizza::lunch("ham")], Timestamp(160)).unwrap();
+    order.send_to_kitchen(Timestamp(200)).unwrap();
+    order.mark_ready(Timestamp(900)).unwrap();
+    order.serve(Timestamp(960)).unwrap();
+    order.pay(Timestamp(2000)).unwrap();
+
+    assert_eq!(order.status(), OrderStatus::Paid);
+    assert!(order.status().is_closed());
+    assert_eq!(order.history().len(), 6);
+    assert_eq!(order.time_of(OrderStatus::Ready), Some(Timestamp(900)));
+}
+
+#[test]
+fn illegal_moves_are_rejected_and_leave_the_order_as_it_was() {
+    let mut order = Order::seat("Ana", Timestamp(100));
+    assert_eq!(
+        order.serve(Timestamp(120)),
+        Err(OrderError::InvalidTransition { from: OrderStatus::Seated, to: OrderStatus::Served })
+    );
+    assert_eq!(order.place(Vec::new(), Timestamp(130)), Err(OrderError::NoPizzas));
+    assert_eq!(order.status(), OrderStatus::Seated);
+
+    order.place(vec![Pizza::lunch("ham")], Timestamp(160)).unwrap();
+    order.send_to_kitchen(Timestamp(200)).unwrap();
+    order.mark_ready(Timestamp(900)).unwrap();
+    order.serve(Timestamp(960)).unwrap();
+    assert_eq!(
+        order.cancel(Timestamp(1000)),
+        Err(OrderError::InvalidTransition { from: OrderStatus::Served, to: OrderStatus::Cancelled })
+    );
+}
+
+#[test]
+fn take_order_places_the_order_of_the_customer() {
+    let menu = Menu::standard();
+    let mut out: Vec<u8> = Vec::new();
+    let mut restaurant = Restaurant::standard();
+    let mut counter = Counter::standard();
+    let choice = PizzaBuilder::new(&menu).topping("olives");
+    let order = take_order(&mut restaurant, &mut counter, &Party::new("Ana", 2), choice, &Noon, &mut out).unwrap().unwrap();
+
+    assert_eq!(order.status(), OrderStatus::Ordered);
+    assert_eq!(order.time_of(OrderStatus::Seated), Some(Timestamp(1_710_504_000)));
+    assert_eq!(order.pizzas[0].toppings[0].name, "olives");
+    assert_eq!(order.table, Some(1));
+}
+
+#[test]
+fn a_pizza_the_kitchen_cannot_make_cancels_the_order() {
+    let menu = Menu::standard();
+    let mut out: Vec<u8> = Vec::new();
+    let mut restaurant = Restaurant::standard();
+    let mut counter = Counter::standard();
+    let choice = PizzaBuilder::new(&menu).topping("chocolate");
+    let order = take_order(&mut restaurant, &mut counter, &Party::new("Ana", 2), choice, &Noon, &mut out).unwrap().unwrap();
+
+    assert_eq!(order.status(), OrderStatus::Cancelled);
+    assert!(order.pizzas.is_empty());
+}
+
+#[test]
+fn timestamps_are_shown_as_utc_dates() {
+    assert_eq!(Timestamp(0).to_string(), "1970-01-01 00:00:00");
+    assert_eq!(Timestamp(1_710_504_000).to_string(), "2024-03-15 12:00:00");
+    assert_eq!(Timestamp(951_782_400).to_string(), "2000-02-29 00:00:00");
+}
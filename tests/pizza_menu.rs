@@ -0,0 +1,77 @@
+use std::{env, fs};
+
+use rust_tutorial::restaurant::pizza_order::menu::{Cheese, Dough, Menu, Size};
+use rust_tutorial::restaurant::pizza_order::{Pizza, PizzaBuilder, PizzaError};
+
+#[test]
+fn the_builder_makes_the_pizza_that_was_asked() {
+    let menu = Menu::standard();
+    let pizza = PizzaBuilder::new(&menu)
+        .size(Size::Large)
+        .dough(Dough::ThinCrust)
+        .cheese(Cheese::Parmesan)
+        .topping("mushrooms")
+        .topping("olives")
+        .build()
+        .unwrap();
+
+    assert_eq!(pizza.size, Size::Large);
+    assert_eq!(pizza.toppings.len(), 2);
+    assert_eq!(pizza.toppings[0].price_cents, 120);
+    assert_eq!(pizza.to_string(), "large pizza with thin crust, parmesan, mushrooms and olives");
+}
+
+#[test]
+fn the_builder_rejects_what_the_menu_does_not_allow() {
+    let menu = Menu::standard();
+
+    let unknown = PizzaBuilder::new(&menu).topping("chocolate").build();
+    assert_eq!(unknown, Err(PizzaError::UnknownTopping(String::from("chocolate"))));
+
+    let twice = PizzaBuilder::new(&menu).topping("ham").topping("ham").build();
+    assert_eq!(twice, Err(PizzaError::DuplicateTopping(String::from("ham"))));
+
+    let incompatible = PizzaBuilder::new(&menu).topping("anchovies").topping("pineapple").build();
+    assert_eq!(
+        incompatible,
+        Err(PizzaError::Incompatible(String::from("anchovies"), String::from("pineapple")))
+    );
+
+    let mut crowded = PizzaBuilder::new(&menu);
+    for topping in ["veggies", "mushrooms", "olives", "onions", "peppers", "ham"] {
+        crowded = crowded.topping(topping);
+    }
+    assert_eq!(crowded.build(), Err(PizzaError::TooManyToppings { max: 5, requested: 6 }));
+}
+
+#[test]
+fn menus_are_loaded_from_a_file() {
+    let path = env::temp_dir().join("rust_tutorial_small_menu.json");
+    fs::write(
+        &path,
+        r#"{
+            "doughs": ["regular"],
+            "cheeses": ["mozzarella"],
+            "sizes": { "small": 700 },
+            "toppings": [{ "name": "basil", "price_cents": 50 }],
+            "max_toppings": 1
+        }"#,
+    )
+    .unwrap();
+    let menu = Menu::load(&path).unwrap();
+
+    assert!(PizzaBuilder::new(&menu).size(Size::Small).topping("basil").build().is_ok());
+    assert_eq!(
+        PizzaBuilder::new(&menu).build(),
+        Err(PizzaError::NotOnMenu(String::from("a medium pizza")))
+    );
+
+    fs::write(&path, "{ not a menu").unwrap();
+    assert!(Menu::load(&path).is_err());
+}
+
+#[test]
+fn the_lunch_special_keeps_its_defaults() {
+    let pizza = Pizza::lunch("veggies");
+    assert_eq!(pizza.to_string(), "medium pizza with regular dough, mozzarella and veggies");
+}
@@ -0,0 +1,74 @@
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::{env, fs};
+
+use rust_tutorial::lessons::LESSONS;
+use rust_tutorial::progress::Progress;
+
+fn config_dir(test: &str) -> PathBuf {
+    let dir = env::temp_dir().join("rust_tutorial_progress").join(test);
+    let _ = fs::remove_dir_all(&dir);
+    dir
+}
+
+fn tutorial(config_dir: &Path, args: &[&str], input: &str) -> String {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_rust_tutorial"))
+        .args(args)
+        .current_dir(env::temp_dir())
+        .env("RUST_TUTORIAL_CONFIG_DIR", config_dir)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .expect("Failed to start rust_tutorial");
+
+    std::io::Write::write_all(&mut child.stdin.take().unwrap(), input.as_bytes()).unwrap();
+    let output = child.wait_with_output().unwrap();
+    assert!(output.status.success());
+    String::from_utf8(output.stdout).unwrap()
+}
+
+fn row<'a>(progress: &'a str, heading: &str) -> &'a str {
+    progress
+        .lines()
+        .find(|line| line.starts_with(heading))
+        .unwrap_or_else(|| panic!("{} is not in the table:\n{}", heading, progress))
+}
+
+#[test]
+fn runs_are_counted_per_class_and_the_next_lesson_is_suggested() {
+    let dir = config_dir("runs");
+    tutorial(&dir, &["run", "1"], "");
+    tutorial(&dir, &["run", "hello_world"], "");
+    tutorial(&dir, &["run", "2"], "");
+
+    let progress = tutorial(&dir, &["progress"], "");
+    assert!(row(&progress, "Class 1 - Hello World").contains("    2  -"), "{}", progress);
+    assert!(row(&progress, "Class 2 - Strings").contains("    1  -"), "{}", progress);
+    assert!(progress.contains("Next lesson: Class 3 - IO (rust_tutorial run 3)"), "{}", progress);
+    assert!(dir.join("progress.json").exists());
+}
+
+#[test]
+fn passing_the_quiz_is_recorded() {
+    let dir = config_dir("quiz");
+    tutorial(&dir, &["quiz"], "y\n1\ny\n8\nn\ny\n2 2\ny\n3\n");
+
+    let progress = tutorial(&dir, &["progress"], "");
+    assert!(row(&progress, "Class 27").contains("passed"), "{}", progress);
+}
+
+#[test]
+fn a_class_number_shared_by_two_classes_is_suggested_by_title() {
+    let mut progress = Progress::default();
+    for lesson in LESSONS.iter().take_while(|lesson| lesson.heading() != "Class 22 - References part 2") {
+        progress.record_run(lesson);
+    }
+    let mut out = Vec::new();
+    progress.print(&mut out).unwrap();
+    let out = String::from_utf8(out).unwrap();
+    assert!(
+        out.contains("Next lesson: Class 22 - References part 2 (rust_tutorial run \"Class 22 - References part 2\")"),
+        "{}",
+        out
+    );
+}
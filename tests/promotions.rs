@@ -0,0 +1,165 @@
+use std::{env, fs};
+
+use rust_tutorial::restaurant::billing::{Bill, Discount, Money, Rate};
+use rust_tutorial::restaurant::clock::{Clock, Timestamp};
+use rust_tutorial::restaurant::counter::Counter;
+use rust_tutorial::restaurant::inventory::Inventory;
+use rust_tutorial::restaurant::order::Order;
+use rust_tutorial::restaurant::pizza_order::help_customer::{apply_promotions, charge_customer};
+use rust_tutorial::restaurant::pizza_order::menu::Menu;
+use rust_tutorial::restaurant::pizza_order::Pizza;
+use rust_tutorial::restaurant::promotions::{LoyaltyAccounts, PromotionError, Promotions};
+
+const NOON: Timestamp = Timestamp(1_710_504_000);
+const HAPPY_HOUR: Timestamp = Timestamp(1_710_504_000 + 3 * 3600 + 1800);
+
+struct Noon;
+
+impl Clock for Noon {
+    fn now(&self) -> Timestamp {
+        NOON
+    }
+}
+
+// Ham is $12.80, olives $12.00 and onions $11.80
+fn three_pizzas(at: Timestamp) -> (Order, Bill) {
+    let mut order = Order::seat("Ana", at);
+    order.place(vec![Pizza::lunch("ham"), Pizza::lunch("olives"), Pizza::lunch("onions")], at).unwrap();
+    let bill = Bill::for_order(&order, &Menu::standard(), Rate::percent(10));
+    (order, bill)
+}
+
+fn descriptions(discounts: &[Discount]) -> Vec<&str> {
+    discounts.iter().map(|discount| discount.description.as_str()).collect()
+}
+
+#[test]
+fn promotions_apply_in_order_of_precedence() {
+    let (_, bill) = three_pizzas(HAPPY_HOUR);
+    let applied = Promotions::standard().apply(&bill, HAPPY_HOUR, Some("welcome5"), 0).unwrap();
+
+    assert_eq!(
+        descriptions(&applied.discounts),
+        vec!["Three for two: 1 of 3 pizzas free", "Happy hour: 20% off from 15:00 to 17:00", "Welcome: coupon WELCOME5"]
+    );
+    // The onions are free, then 20% of the $24.80 left, then $5.00 of the $19.84 left
+    let amounts: Vec<Money> = applied.discounts.iter().map(|discount| discount.amount).collect();
+    assert_eq!(amounts, vec![Money::cents(1180), Money::cents(496), Money::cents(500)]);
+    assert_eq!(applied.points_redeemed, 0);
+}
+
+#[test]
+fn the_file_order_doesnt_change_the_precedence() {
+    let json = r#"{
+        "promotions": [
+            { "name": "Ten off", "kind": "coupon", "code": "TEN", "percent": 10 },
+            { "name": "Lunch", "kind": "happy_hour", "from_hour": 11, "to_hour": 14, "percent": 50 }
+        ]
+    }"#;
+    let promotions = Promotions::from_json(json).unwrap();
+    let (_, bill) = three_pizzas(NOON);
+
+    let applied = promotions.apply(&bill, NOON, Some("TEN"), 0).unwrap();
+    assert_eq!(descriptions(&applied.discounts), vec!["Lunch: 50% off from 11:00 to 14:00", "Ten off: coupon TEN"]);
+    assert_eq!(applied.discounts[1].amount, Money::cents(183));
+
+    let outside = promotions.apply(&bill, NOON.plus(3 * 3600), None, 0).unwrap();
+    assert!(outside.discounts.is_empty());
+    assert_eq!(
+        promotions.apply(&bill, NOON, Some("FREEPIZZA"), 0),
+        Err(PromotionError::UnknownCoupon(String::from("FREEPIZZA")))
+    );
+}
+
+#[test]
+fn the_happy_hour_is_on_the_clocks_of_the_restaurant() {
+    let json = r#"{
+        "utc_offset_minutes": 120,
+        "promotions": [
+            { "name": "Happy hour", "kind": "happy_hour", "from_hour": 15, "to_hour": 17, "percent": 20 }
+        ]
+    }"#;
+    let promotions = Promotions::from_json(json).unwrap();
+    let (_, bill) = three_pizzas(NOON);
+    // 12:00 in UTC is 14:00 in the restaurant, and 15:00 an hour later
+    assert!(promotions.apply(&bill, NOON, None, 0).unwrap().discounts.is_empty());
+    assert_eq!(promotions.apply(&bill, NOON.plus(3600), None, 0).unwrap().discounts.len(), 1);
+}
+
+#[test]
+fn discounts_come_before_the_tax_but_not_the_tip() {
+    let (_, bill) = three_pizzas(NOON);
+    let applied = Promotions::standard().apply(&bill, NOON, None, 0).unwrap();
+    let bill = bill.with_tip(Rate::percent(10)).with_discounts(applied.discounts);
+
+    assert_eq!(bill.subtotal(), Money::cents(3660));
+    assert_eq!(bill.discount(), Money::cents(1180));
+    assert_eq!(bill.tax(), Money::cents(248));
+    assert_eq!(bill.tip(), Money::cents(366));
+    assert_eq!(bill.total(), Money::cents(3660 - 1180 + 248 + 366));
+
+    let mut receipt: Vec<u8> = Vec::new();
+    bill.print_receipt(&mut receipt).unwrap();
+    let receipt = String::from_utf8(receipt).unwrap();
+    assert!(receipt.contains(&format!("{:<60} {:>10}\n", "Three for two: 1 of 3 pizzas free", "-$11.80")));
+}
+
+#[test]
+fn loyalty_points_are_earned_and_redeemed() {
+    let mut accounts = LoyaltyAccounts::default();
+    assert_eq!(accounts.earn("Ana", 40), 40);
+    assert_eq!(accounts.redeem("Ana", 50), Err(PromotionError::NotEnoughPoints { customer: String::from("Ana"), balance: 40, requested: 50 }));
+
+    let (_, bill) = three_pizzas(NOON);
+    let promotions = Promotions::standard();
+    // Below the minimum of 50 points nothing is redeemed
+    assert_eq!(promotions.apply(&bill, NOON, None, 40).unwrap().points_redeemed, 0);
+
+    let applied = promotions.apply(&bill, NOON, None, 120).unwrap();
+    assert_eq!(applied.points_redeemed, 120);
+    assert_eq!(applied.discounts.last().unwrap().description, "Loyalty: 120 points redeemed");
+    assert_eq!(applied.discounts.last().unwrap().amount, Money::cents(600));
+}
+
+#[test]
+fn the_waiter_applies_the_promotions_of_the_restaurant() {
+    let mut counter = Counter::standard().with_promotions(Promotions::standard());
+    counter.earn("Ana", 60).unwrap();
+    let (mut order, bill) = three_pizzas(NOON);
+    let mut out: Vec<u8> = Vec::new();
+
+    let bill = apply_promotions(&mut counter, &order, bill, Some("NOPE"), &Noon, &mut out).unwrap();
+    assert_eq!(bill.discount(), Money::cents(1180 + 300));
+    assert_eq!(counter.loyalty().balance("Ana"), 0);
+
+    order.send_to_kitchen(NOON).unwrap();
+    order.mark_ready(NOON).unwrap();
+    order.serve(NOON).unwrap();
+    charge_customer(&mut counter, &mut order, &bill, &Noon, &mut out).unwrap();
+    // $36.60 - $14.80 of food is 21 points
+    assert_eq!(counter.loyalty().balance("Ana"), 21);
+
+    let out = String::from_utf8(out).unwrap();
+    assert!(out.starts_with("Sorry, there is no coupon NOPE\n"));
+    assert!(out.ends_with("Ana earns 21 loyalty points, 21 in total\n"));
+}
+
+#[test]
+fn the_counter_of_the_config_directory_keeps_the_points() {
+    let dir = env::temp_dir().join("rust_tutorial_promotions").join("counter");
+    let _ = fs::remove_dir_all(&dir);
+
+    // Without a promotions.json the counter has the ones of the tutorial
+    let mut counter = Counter::open(&dir, Inventory::standard()).unwrap();
+    assert_eq!(counter.promotions(), &Promotions::standard());
+    counter.earn("Ana", 60).unwrap();
+    counter.redeem("Ana", 10).unwrap();
+    let counter = Counter::open(&dir, Inventory::standard()).unwrap();
+    assert_eq!(counter.loyalty().balance("Ana"), 50);
+
+    let own = r#"{"promotions": [{"name": "Staff", "kind": "coupon", "code": "STAFF", "percent": 50}]}"#;
+    fs::write(dir.join("promotions.json"), own).unwrap();
+    let counter = Counter::open(&dir, Inventory::standard()).unwrap();
+    assert_eq!(counter.promotions().promotions[0].name, "Staff");
+    assert_eq!(counter.loyalty().balance("Ana"), 50);
+}
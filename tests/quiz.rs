@@ -0,0 +1,32 @@
+use std::env;
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+fn quiz(test: &str, answers: &str) -> String {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_rust_tutorial"))
+        .arg("quiz")
+        .env("RUST_TUTORIAL_CONFIG_DIR", env::temp_dir().join("rust_tutorial_quiz").join(test))
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .expect("Failed to start rust_tutorial");
+
+    child.stdin.take().unwrap().write_all(answers.as_bytes()).unwrap();
+    let output = child.wait_with_output().unwrap();
+    assert!(output.status.success());
+    String::from_utf8(output.stdout).unwrap()
+}
+
+#[test]
+fn right_answers_score_everything() {
+    let output = quiz("right", "y\n1\ny\n8\nn\ny\n2 2\ny\n3\n");
+    assert!(output.ends_with("Score: 9/9\n"), "{}", output);
+}
+
+#[test]
+fn wrong_answers_show_the_right_one_and_the_explanation() {
+    let output = quiz("wrong", "y\n7\n");
+    assert!(output.contains("Wrong, it prints: 1"), "{}", output);
+    assert!(output.contains("When we use the syntax &mut *b"), "{}", output);
+    assert!(output.ends_with("Score: 1/2\n"), "{}", output);
+}
@@ -0,0 +1,175 @@
+use std::path::PathBuf;
+use std::process::Command;
+use std::{env, fs};
+
+use rust_tutorial::restaurant::billing::Money;
+use rust_tutorial::restaurant::clock::{Timestamp, UtcOffset};
+use rust_tutorial::restaurant::order::{Charge, Order};
+use rust_tutorial::restaurant::pizza_order::Pizza;
+use rust_tutorial::restaurant::pizza_order::menu::Menu;
+use rust_tutorial::restaurant::report::{CustomerSales, DailyReport, HourSales, ToppingSales};
+use rust_tutorial::restaurant::store::OrderStore;
+
+const NOON: Timestamp = Timestamp(1_710_504_000);
+const HOUR: u64 = 3600;
+
+fn temp_dir(test: &str) -> PathBuf {
+    let dir = env::temp_dir().join("rust_tutorial_sales_report").join(test);
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+fn paid(customer: &str, toppings: &[&str], at: Timestamp) -> Order {
+    let mut order = Order::seat(customer, at);
+    order.place(toppings.iter().map(|topping| Pizza::lunch(topping)).collect(), at.plus(60)).unwrap();
+    order.send_to_kitchen(at.plus(120)).unwrap();
+    order.mark_ready(at.plus(900)).unwrap();
+    order.serve(at.plus(960)).unwrap();
+    order.pay(at.plus(2400)).unwrap();
+    order
+}
+
+fn cancelled(customer: &str, at: Timestamp) -> Order {
+    let mut order = Order::seat(customer, at);
+    order.cancel(at.plus(60)).unwrap();
+    order
+}
+
+// A lunch rush at noon, a quiet evening, and a customer from the next day
+fn day_of_service(dir: &str) -> OrderStore {
+    let mut store = OrderStore::open(&temp_dir(dir).join("orders.jsonl")).unwrap();
+    store.insert(&paid("Ana", &["ham", "olives"], NOON)).unwrap();
+    store.insert(&paid("Bruno", &["olives"], NOON.plus(600))).unwrap();
+    store.insert(&cancelled("Carla", NOON.plus(1200))).unwrap();
+    store.insert(&paid("Ana", &["veggies"], NOON.plus(7 * HOUR))).unwrap();
+    store.insert(&paid("Davi", &["ham"], NOON.plus(24 * HOUR))).unwrap();
+    store
+}
+
+#[test]
+fn the_report_sums_up_the_day() {
+    let store = day_of_service("sums");
+    let report = DailyReport::for_date(&store, "2024-03-15", &Menu::standard());
+
+    assert_eq!(report.orders, 4);
+    assert_eq!(report.paid_orders, 3);
+    assert_eq!(report.cancelled_orders, 1);
+    assert_eq!(report.pizzas_sold, 4);
+    // Medium pizzas are $11.00, ham $1.80, olives $1.00 and veggies $1.50
+    assert_eq!(report.revenue, Money::cents(1280 + 1200 + 1200 + 1250));
+    assert_eq!(report.average_ticket, Money::cents(4930 / 3));
+    assert_eq!(report.busiest_hour, Some(HourSales { hour: 12, orders: 3 }));
+    assert_eq!(
+        report.toppings,
+        vec![
+            ToppingSales { topping: String::from("olives"), pizzas: 2 },
+            ToppingSales { topping: String::from("ham"), pizzas: 1 },
+            ToppingSales { topping: String::from("veggies"), pizzas: 1 },
+        ]
+    );
+    assert_eq!(
+        report.top_customers,
+        vec![
+            CustomerSales { customer: String::from("Ana"), orders: 2, spent: Money::cents(3730) },
+            CustomerSales { customer: String::from("Bruno"), orders: 1, spent: Money::cents(1200) },
+        ]
+    );
+}
+
+#[test]
+fn a_day_without_orders_is_an_empty_report() {
+    let store = day_of_service("empty");
+    let report = DailyReport::for_date(&store, "2024-03-14", &Menu::standard());
+
+    assert_eq!(report.orders, 0);
+    assert_eq!(report.average_ticket, Money::ZERO);
+    assert_eq!(report.busiest_hour, None);
+    assert!(report.top_customers.is_empty());
+}
+
+#[test]
+fn the_day_and_the_hours_are_the_ones_of_the_restaurant() {
+    let store = day_of_service("local");
+    // Six hours ahead of UTC, Ana's dinner is past midnight and Davi came in the evening
+    let offset = UtcOffset::hours(6);
+    let report = DailyReport::for_local_date(&store, "2024-03-15", &Menu::standard(), offset);
+    assert_eq!(report.orders, 3);
+    assert_eq!(report.busiest_hour, Some(HourSales { hour: 18, orders: 3 }));
+
+    let report = DailyReport::for_local_date(&store, "2024-03-16", &Menu::standard(), offset);
+    assert_eq!(report.orders, 2);
+    assert_eq!(report.busiest_hour, Some(HourSales { hour: 1, orders: 1 }));
+}
+
+#[test]
+fn the_report_is_a_table_csv_or_json() {
+    let store = day_of_service("formats");
+    let report = DailyReport::for_date(&store, "2024-03-15", &Menu::standard());
+
+    let mut table: Vec<u8> = Vec::new();
+    report.print(&mut table).unwrap();
+    let table = String::from_utf8(table).unwrap();
+    assert!(table.starts_with("Sales of 2024-03-15\n"));
+    assert!(table.contains(&format!("{:<20} {:>12}\n", "Revenue", "$49.30")));
+    assert!(table.contains("Busiest hour          12:00-13:00 (3 orders)\n"));
+
+    let mut csv: Vec<u8> = Vec::new();
+    report.write_csv(&mut csv).unwrap();
+    let csv = String::from_utf8(csv).unwrap();
+    assert!(csv.starts_with("section,name,count,cents\nsummary,orders,4,\n"));
+    assert!(csv.contains("summary,revenue,,4930\n"));
+    assert!(csv.contains("customer,Ana,2,3730\n"));
+
+    let mut json: Vec<u8> = Vec::new();
+    report.write_json(&mut json).unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&json).unwrap();
+    assert_eq!(json["revenue_cents"], 4930);
+    assert_eq!(json["busiest_hour"]["hour"], 12);
+    assert_eq!(json["top_customers"][0]["spent_cents"], 3730);
+}
+
+#[test]
+fn paid_orders_count_what_they_were_charged() {
+    let mut store = OrderStore::open(&temp_dir("charged").join("orders.jsonl")).unwrap();
+    // $12.80 of ham with $5.00 off, sold before the price of ham went up
+    let mut ana = paid("Ana", &["ham"], NOON);
+    ana.charged = Some(Charge {
+        subtotal: Money::cents(1280),
+        discount: Money::cents(500),
+    });
+    store.insert(&ana).unwrap();
+    store.insert(&paid("Bruno", &["ham"], NOON)).unwrap();
+
+    let mut menu = Menu::standard();
+    menu.toppings.iter_mut().filter(|topping| topping.name == "ham").for_each(|ham| ham.price_cents = 500);
+    let store = OrderStore::open(store.path()).unwrap();
+    let report = DailyReport::for_date(&store, "2024-03-15", &menu);
+    // Bruno's order was saved without its charge, it's counted at the prices of the menu
+    assert_eq!(report.revenue, Money::cents(780 + 1600));
+    assert_eq!(report.top_customers[0], CustomerSales { customer: String::from("Bruno"), orders: 1, spent: Money::cents(1600) });
+}
+
+#[test]
+fn the_report_command_reads_the_order_history() {
+    let dir = temp_dir("command");
+    let mut store = OrderStore::open(&dir.join("orders.jsonl")).unwrap();
+    store.insert(&paid("Ana, Jr.", &["ham"], NOON)).unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_rust_tutorial"))
+        .args(["report", "--date", "2024-03-15", "--format", "csv"])
+        .env("RUST_TUTORIAL_CONFIG_DIR", &dir)
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("customer,\"Ana, Jr.\",1,1280\n"));
+
+    let output = Command::new(env!("CARGO_BIN_EXE_rust_tutorial"))
+        .args(["report", "--format", "xml"])
+        .env("RUST_TUTORIAL_CONFIG_DIR", &dir)
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    assert!(String::from_utf8(output.stderr).unwrap().contains("unknown format `xml`"));
+}
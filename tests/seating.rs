@@ -0,0 +1,115 @@
+use rust_tutorial::restaurant::clock::{SimulatedClock, Timestamp};
+use rust_tutorial::restaurant::counter::Counter;
+use rust_tutorial::restaurant::order::Order;
+use rust_tutorial::restaurant::pizza_order::help_customer::{clear_table, take_order};
+use rust_tutorial::restaurant::pizza_order::menu::Menu;
+use rust_tutorial::restaurant::pizza_order::PizzaBuilder;
+use rust_tutorial::restaurant::seating::{Party, Restaurant, Seating, SeatingError, Table};
+
+const NOON: Timestamp = Timestamp(1_710_504_000);
+const MEAL: u64 = 45 * 60;
+
+// A table for two and a table for four
+fn small_restaurant() -> Restaurant {
+    Restaurant::new(
+        vec![Table { number: 1, capacity: 2 }, Table { number: 2, capacity: 4 }],
+        MEAL,
+    )
+}
+
+#[test]
+fn parties_get_the_smallest_table_that_fits() {
+    let mut restaurant = small_restaurant();
+
+    assert_eq!(restaurant.arrive(Party::new("Ana", 3), NOON), Ok(Seating::Seated(2)));
+    assert_eq!(restaurant.arrive(Party::new("Bruno", 1), NOON), Ok(Seating::Seated(1)));
+    assert_eq!(restaurant.occupant(2), Some(&Party::new("Ana", 3)));
+    assert_eq!(
+        restaurant.arrive(Party::new("Carla", 9), NOON),
+        Err(SeatingError::PartyTooBig(9))
+    );
+}
+
+#[test]
+fn the_waitlist_estimates_the_wait_and_is_seated_when_tables_free_up() {
+    let mut restaurant = small_restaurant();
+    restaurant.arrive(Party::new("Ana", 2), NOON).unwrap();
+    restaurant.arrive(Party::new("Bruno", 4), NOON.plus(600)).unwrap();
+
+    // Ana's table is free at 12:45, Bruno's at 12:55
+    assert_eq!(
+        restaurant.arrive(Party::new("Carla", 2), NOON.plus(900)),
+        Ok(Seating::Waitlisted { position: 1, estimated_wait_seconds: MEAL - 900 })
+    );
+    // Carla takes the 12:45 table, so Davi gets Bruno's at 12:55
+    assert_eq!(
+        restaurant.arrive(Party::new("Davi", 2), NOON.plus(900)),
+        Ok(Seating::Waitlisted { position: 2, estimated_wait_seconds: MEAL + 600 - 900 })
+    );
+
+    let seated = restaurant.leave(1, NOON.plus(MEAL)).unwrap();
+    assert_eq!(seated, vec![(Party::new("Carla", 2), 1)]);
+    assert_eq!(restaurant.waitlist().len(), 1);
+    assert_eq!(restaurant.leave(2, NOON.plus(MEAL + 600)), Ok(vec![(Party::new("Davi", 2), 2)]));
+    assert!(restaurant.waitlist().is_empty());
+
+    assert_eq!(restaurant.leave(1, NOON.plus(2 * MEAL)), Ok(Vec::new()));
+    assert_eq!(restaurant.leave(1, NOON.plus(2 * MEAL)), Err(SeatingError::TableNotOccupied(1)));
+    assert_eq!(restaurant.leave(3, NOON.plus(2 * MEAL)), Err(SeatingError::UnknownTable(3)));
+}
+
+#[test]
+fn reserved_tables_are_held_for_their_slot() {
+    let mut restaurant = small_restaurant();
+    let reservation = restaurant.reserve(Party::new("Elisa", 4), NOON.plus(1800)).unwrap();
+    assert_eq!(reservation.table, 2);
+
+    // The big table is reserved in 30 minutes, a party of 3 can't eat there now
+    assert!(matches!(
+        restaurant.arrive(Party::new("Fabio", 3), NOON),
+        Ok(Seating::Waitlisted { .. })
+    ));
+    assert_eq!(
+        restaurant.reserve(Party::new("Gil", 3), NOON.plus(3600)),
+        Err(SeatingError::NoTableForSlot(NOON.plus(3600)))
+    );
+    // But the slot is free again after Elisa's meal
+    assert!(restaurant.reserve(Party::new("Gil", 3), NOON.plus(1800 + MEAL)).is_ok());
+
+    assert_eq!(restaurant.check_in("Elisa", NOON.plus(1800)), Ok(2));
+    assert_eq!(restaurant.check_in("Elisa", NOON.plus(1800)), Err(SeatingError::NoReservation(String::from("Elisa"))));
+}
+
+#[test]
+fn turnover_counts_the_parties_of_each_table() {
+    let mut restaurant = small_restaurant();
+    restaurant.arrive(Party::new("Ana", 2), NOON).unwrap();
+    restaurant.leave(1, NOON.plus(1800)).unwrap();
+    restaurant.arrive(Party::new("Bruno", 2), NOON.plus(1800)).unwrap();
+    restaurant.leave(1, NOON.plus(1800 + 3000)).unwrap();
+
+    let turnover = restaurant.turnover();
+    assert_eq!(turnover[0].parties, 2);
+    assert_eq!(turnover[0].average_stay_seconds, 2400);
+    assert_eq!(turnover[1].parties, 0);
+}
+
+#[test]
+fn take_order_finds_a_table_first() {
+    let clock = SimulatedClock::starting_at(NOON);
+    let menu = Menu::standard();
+    let mut restaurant = small_restaurant();
+    let mut counter = Counter::standard();
+    let mut out: Vec<u8> = Vec::new();
+
+    let ana = take_order(&mut restaurant, &mut counter, &Party::new("Ana", 4), PizzaBuilder::new(&menu), &clock, &mut out).unwrap();
+    let bruno = take_order(&mut restaurant, &mut counter, &Party::new("Bruno", 3), PizzaBuilder::new(&menu), &clock, &mut out).unwrap();
+    assert_eq!(ana.as_ref().and_then(|order| order.table), Some(2));
+    assert_eq!(bruno, None);
+
+    let seated: Vec<Order> = clear_table(&mut restaurant, &ana.unwrap(), &clock, &mut out).unwrap();
+    assert_eq!(seated[0].customer, "Bruno");
+    assert_eq!(seated[0].table, Some(2));
+    let out = String::from_utf8(out).unwrap();
+    assert!(out.contains("Bruno is number 1 on the waitlist, about 45m 00s of wait"));
+}
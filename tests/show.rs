@@ -0,0 +1,59 @@
+use std::env;
+use std::process::Command;
+
+use rust_tutorial::highlight::highlight_line;
+use rust_tutorial::lessons::LESSONS;
+
+#[test]
+fn every_lesson_finds_its_source() {
+    for lesson in LESSONS {
+        let source = lesson
+            .source_code()
+            .unwrap_or_else(|| panic!("no source for {}", lesson.heading()));
+        for example in lesson.examples {
+            assert!(
+                source.contains(&format!("fn {}(", example.name)),
+                "{} is not in the source of {}",
+                example.name,
+                lesson.heading()
+            );
+        }
+    }
+}
+
+#[test]
+fn source_stops_at_the_next_class() {
+    let lesson = LESSONS.iter().find(|lesson| lesson.number == 23).unwrap();
+    let source = lesson.source_code().unwrap();
+    assert!(source.starts_with("// Class 23 - Question Marker Operator"));
+    assert!(!source.contains("Class 24"));
+}
+
+#[test]
+fn show_prints_the_code_and_then_the_output() {
+    let output = Command::new(env!("CARGO_BIN_EXE_rust_tutorial"))
+        .args(["show", "18"])
+        .env("RUST_TUTORIAL_CONFIG_DIR", env::temp_dir().join("rust_tutorial_show"))
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+
+    assert!(output.status.success());
+    let code = stdout.find("pub fn hash_maps(out: &mut dyn Write)").expect("code");
+    let result = stdout.find("Batman = Bruce Wayne").expect("output");
+    assert!(code < result, "{}", stdout);
+    // the output is not a terminal, so there are no colors
+    assert!(!stdout.contains('\x1b'));
+}
+
+#[test]
+fn highlight_colors_comments_strings_and_keywords() {
+    let line = highlight_line("let name = \"Felipe\"; // a comment");
+    assert_eq!(
+        line,
+        "\x1b[35mlet\x1b[0m name = \x1b[33m\"Felipe\"\x1b[0m; \x1b[32m// a comment\x1b[0m"
+    );
+    // a lifetime is not a char literal
+    assert_eq!(highlight_line("'a'"), "\x1b[33m'a'\x1b[0m");
+    assert_eq!(highlight_line("&'static"), "&'\x1b[35mstatic\x1b[0m");
+}